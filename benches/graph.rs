@@ -0,0 +1,56 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rugraph::digraph::DiGraph;
+use rugraph::rugraph::IDiGraph;
+use rugraph::rugraph::IGraph;
+
+/// Graph on `n` nodes where every node points to the next `degree` ones
+fn circulant(n: u32, degree: u32) -> DiGraph<u32> {
+    let mut graph = DiGraph::<u32>::with_capacity(n as usize, (n * degree) as usize);
+    for i in 0..n {
+        graph.add_node(i);
+    }
+    for i in 0..n {
+        for d in 1..=degree {
+            graph.add_edge(i, (i + d) % n);
+        }
+    }
+    graph
+}
+
+fn add_edge(c: &mut Criterion) {
+    c.bench_function("add_edge 10k nodes x 3", |b| {
+        b.iter(|| circulant(black_box(10_000), 3))
+    });
+}
+
+fn get_neighbors(c: &mut Criterion) {
+    let graph = circulant(10_000, 3);
+    c.bench_function("get_neighbors", |b| {
+        b.iter(|| graph.get_neighbors(black_box(5_000)))
+    });
+}
+
+fn is_connected(c: &mut Criterion) {
+    let graph = circulant(10_000, 3);
+    c.bench_function("is_connected 10k nodes", |b| {
+        b.iter(|| graph.is_connected(black_box(0), black_box(9_999)))
+    });
+}
+
+fn all_simple_paths(c: &mut Criterion) {
+    let mut graph = circulant(20, 3);
+    graph.add_node(20);
+    graph.add_edge(19, 20);
+    c.bench_function("all_simple_paths 20 nodes x 3", |b| {
+        b.iter(|| graph.all_simple_paths(black_box(0), black_box(20)))
+    });
+}
+
+criterion_group!(
+    benches,
+    add_edge,
+    get_neighbors,
+    is_connected,
+    all_simple_paths
+);
+criterion_main!(benches);
@@ -5,7 +5,7 @@ use rugraph::rugraph::IGraph;
 use std::fs::File;
 
 fn main() {
-    println!("Example of dot file creation. Check test1.dot file.\nTo create a picture install graphivz.\n\n$ dot -Tpng example1.dot -o example1.png\n\n");
+    println!("Example of dot file creation. Check example1.dot file.\nTo create a picture see the render example.\n\n$ cargo run --example render --features render\n\n");
 
     let mut fd = File::create("example1.dot").expect("error creating file");
     let mut graph = DiGraph::<String>::new();
@@ -19,7 +19,9 @@ fn main() {
     graph.add_edge("a".to_string(), "d".to_string());
     println!("Number of nodes of graph {}", graph.count_nodes());
 
-    graph.to_dot_file(&mut fd, &String::from("to_dot_test"));
+    graph
+        .to_dot_file(&mut fd, &String::from("to_dot_test"))
+        .expect("error writing file");
     let s = graph.to_dot_string(&String::from("to_dot_test"));
     println!("File content:\n{}", s);
 
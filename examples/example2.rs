@@ -17,7 +17,9 @@ fn main() {
     graph.add_edge("b".to_string(), "c".to_string(), "bc".to_string());
     graph.add_edge("c".to_string(), "d".to_string(), "cd".to_string());
     graph.add_edge("a".to_string(), "d".to_string(), "ad".to_string());
-    graph.to_dot_file(&mut fd, &String::from("to_dot_multidigraph_test"));
+    graph
+        .to_dot_file(&mut fd, &String::from("to_dot_multidigraph_test"))
+        .expect("error writing file");
     let s = graph.to_dot_string(&String::from("to_dot_multidigraph_test"));
     println!("File content:\n{}", s);
 }
@@ -0,0 +1,25 @@
+use rugraph::multidigraph::MultiDiGraph;
+use rugraph::render::OutputFormat;
+use rugraph::rugraph::IGraph;
+use rugraph::rugraph::IMultiDiGraph;
+
+fn main() {
+    let mut graph = MultiDiGraph::<String, i32>::new();
+    graph.add_node("a".to_string());
+    graph.add_node("b".to_string());
+    graph.add_node("c".to_string());
+    graph.add_edge("a".to_string(), "b".to_string(), 1);
+    graph.add_edge("a".to_string(), "b".to_string(), 2);
+    graph.add_edge("b".to_string(), "c".to_string(), 3);
+    graph.add_edge("c".to_string(), "a".to_string(), 4);
+
+    // Uses Graphviz dot when it is installed, a built-in layout otherwise
+    match graph.render_to_file("render.svg", OutputFormat::Svg) {
+        Ok(()) => println!("Picture written to render.svg"),
+        Err(e) => println!("Error {}", e),
+    }
+    match graph.render_to_file("render.png", OutputFormat::Png) {
+        Ok(()) => println!("Picture written to render.png"),
+        Err(e) => println!("PNG not rendered: {}", e),
+    }
+}
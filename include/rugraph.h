@@ -0,0 +1,45 @@
+#ifndef RUGRAPH_H
+#define RUGRAPH_H
+
+/* C interface of rugraph, built with the `capi` feature. Nodes are NUL
+ * terminated UTF-8 strings. Strings and paths returned by the library belong
+ * to the caller, who releases them with rugraph_string_free and
+ * rugraph_path_free.
+ *
+ * The shared and static libraries (librugraph.so and librugraph.a on Linux)
+ * are written to target/release by:
+ *
+ *     cargo rustc --release --lib --features capi --crate-type cdylib,staticlib
+ */
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct RugraphDiGraph RugraphDiGraph;
+
+RugraphDiGraph *rugraph_digraph_new(void);
+void rugraph_digraph_free(RugraphDiGraph *graph);
+
+/* Return 0, or -1 if an argument is not valid or a node doesn't exist */
+int rugraph_digraph_add_node(RugraphDiGraph *graph, const char *node);
+int rugraph_digraph_add_edge(RugraphDiGraph *graph, const char *from, const char *to);
+
+size_t rugraph_digraph_count_nodes(const RugraphDiGraph *graph);
+
+/* Path with the fewest edges from `from` to `to` as an array of *len
+ * strings, NULL if `to` can't be reached */
+char **rugraph_digraph_shortest_path(const RugraphDiGraph *graph, const char *from,
+                                     const char *to, size_t *len);
+void rugraph_path_free(char **path, size_t len);
+
+char *rugraph_digraph_to_dot(const RugraphDiGraph *graph, const char *graph_name);
+void rugraph_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
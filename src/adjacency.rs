@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+use crate::visit::VisitMap;
+
+/// Index based snapshot of a graph used internally by the algorithms.
+/// Node `i` is `nodes[i]` and `succ[i]` holds the indexes of its neighbors
+/// (one entry per edge, so parallel edges are repeated).
+pub(crate) struct Adjacency<T>
+where
+    T: Ord + Clone,
+{
+    pub nodes: Vec<T>,
+    pub succ: Vec<Vec<usize>>,
+}
+
+impl<T> Adjacency<T>
+where
+    T: Ord + Clone,
+{
+    /// Builds the snapshot from the list of nodes and a function returning
+    /// the neighbors of a node
+    pub fn new(nodes: Vec<T>, neighbors: impl Fn(T) -> Vec<T>) -> Self {
+        let mut index = BTreeMap::<T, usize>::new();
+        for (i, n) in nodes.iter().enumerate() {
+            index.insert(n.clone(), i);
+        }
+        let mut succ = Vec::<Vec<usize>>::with_capacity(nodes.len());
+        for n in nodes.iter() {
+            succ.push(neighbors(n.clone()).iter().map(|m| index[m]).collect());
+        }
+        Adjacency { nodes, succ }
+    }
+
+    /// Returns how many nodes are in the snapshot
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns for every node the indexes of the nodes pointing to it
+    pub fn predecessors(&self) -> Vec<Vec<usize>> {
+        let mut pred = vec![Vec::<usize>::new(); self.len()];
+        for (i, s) in self.succ.iter().enumerate() {
+            for j in s.iter() {
+                pred[*j].push(i);
+            }
+        }
+        pred
+    }
+
+    /// Returns every edge as `(from, to)`. In an undirected snapshot (where
+    /// every edge is stored in both directions) each edge is returned once
+    pub fn edges(&self, directed: bool) -> Vec<(usize, usize)> {
+        let mut ret = Vec::<(usize, usize)>::new();
+        for (i, s) in self.succ.iter().enumerate() {
+            for j in s.iter().filter(|j| directed || **j >= i) {
+                ret.push((i, *j));
+            }
+        }
+        ret
+    }
+
+    /// Returns the degree of every node. In a directed snapshot this is the
+    /// sum of the in and out degree, in an undirected one (where every edge
+    /// is stored in both directions) it is the number of neighbors.
+    pub fn degrees(&self, directed: bool) -> Vec<usize> {
+        let mut deg: Vec<usize> = self.succ.iter().map(|s| s.len()).collect();
+        if directed {
+            for s in self.succ.iter() {
+                for j in s.iter() {
+                    deg[*j] += 1;
+                }
+            }
+        }
+        deg
+    }
+
+    /// Returns the number of hops from node `from` to every node, `None` if
+    /// the node is not reachable
+    pub fn bfs_distances(&self, from: usize) -> Vec<Option<usize>> {
+        let mut dist = vec![None; self.len()];
+        dist[from] = Some(0);
+        let mut queue = std::collections::VecDeque::from([from]);
+        while let Some(i) = queue.pop_front() {
+            let d = dist[i].unwrap() + 1;
+            for j in self.succ[i].iter() {
+                if dist[*j].is_none() {
+                    dist[*j] = Some(d);
+                    queue.push_back(*j);
+                }
+            }
+        }
+        dist
+    }
+
+    /// Returns a path with the fewest edges from node `from` to node `to`,
+    /// both included, `None` if `to` is not reachable
+    pub fn bfs_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        // a node's parent is the node it was first reached from
+        let mut parent = vec![None; self.len()];
+        parent[from] = Some(from);
+        let mut queue = std::collections::VecDeque::from([from]);
+        while let Some(i) = queue.pop_front() {
+            if i == to {
+                break;
+            }
+            for j in self.succ[i].iter() {
+                if parent[*j].is_none() {
+                    parent[*j] = Some(i);
+                    queue.push_back(*j);
+                }
+            }
+        }
+        parent[to]?;
+        let mut path = vec![to];
+        let mut i = to;
+        while i != from {
+            i = parent[i].unwrap();
+            path.push(i);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Returns the weakly connected components as lists of node indexes
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let n = self.len();
+        let mut undirected = vec![Vec::<usize>::new(); n];
+        for (i, s) in self.succ.iter().enumerate() {
+            for j in s.iter() {
+                undirected[i].push(*j);
+                undirected[*j].push(i);
+            }
+        }
+
+        let mut seen = VisitMap::new(n);
+        let mut ret = Vec::<Vec<usize>>::new();
+        for start in 0..n {
+            if !seen.visit(start) {
+                continue;
+            }
+            let mut component = vec![start];
+            let mut to_process = vec![start];
+            while let Some(i) = to_process.pop() {
+                for j in undirected[i].iter() {
+                    if seen.visit(*j) {
+                        component.push(*j);
+                        to_process.push(*j);
+                    }
+                }
+            }
+            ret.push(component);
+        }
+        ret
+    }
+
+    /// Returns the nodes in topological order (Kahn's algorithm) or `None`
+    /// if the snapshot contains a cycle
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        let n = self.len();
+        let mut in_degree = vec![0usize; n];
+        for s in self.succ.iter() {
+            for j in s.iter() {
+                in_degree[*j] += 1;
+            }
+        }
+        let mut to_process: Vec<usize> = (0..n).filter(|i| in_degree[*i] == 0).collect();
+        let mut ret = Vec::<usize>::with_capacity(n);
+        while let Some(i) = to_process.pop() {
+            ret.push(i);
+            for j in self.succ[i].iter() {
+                in_degree[*j] -= 1;
+                if in_degree[*j] == 0 {
+                    to_process.push(*j);
+                }
+            }
+        }
+        if ret.len() == n {
+            Some(ret)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub(crate) fn adjacency(&self) -> Adjacency<T> {
+        Adjacency::new(self.get_nodes(), |n| self.get_neighbors(n))
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub(crate) fn adjacency(&self) -> Adjacency<T> {
+        Adjacency::new(self.get_nodes(), |n| self.get_neighbors(n))
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub(crate) fn adjacency(&self) -> Adjacency<T> {
+        Adjacency::new(self.get_nodes(), |n| {
+            self.get_neighbors(n).into_iter().map(|(m, _)| m).collect()
+        })
+    }
+}
@@ -0,0 +1,248 @@
+use std::ops::Add;
+use std::ops::Sub;
+use std::vec::Vec;
+
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Solves the assignment problem over a bipartite `MultiDiGraph` using the
+    /// Hungarian algorithm and returns the minimum-cost perfect matching as a
+    /// vector of tuples `(from, to, edge)`.
+    ///
+    /// The two sides are inferred from the edge direction: nodes with outgoing
+    /// edges form the left side (e.g. workers) and nodes with incoming edges the
+    /// right side (e.g. jobs). `cost` gives the cost of every edge, of a signed,
+    /// unsigned or floating point type; among parallel edges the cheapest one
+    /// is used.
+    ///
+    /// Returns an error if a node is on both sides, if the sides have
+    /// different sizes or if no perfect matching exists.
+    pub fn solve_assignment<W>(
+        &self,
+        cost: impl Fn(&E) -> W,
+    ) -> Result<Vec<(T, T, E)>, &'static str>
+    where
+        W: Copy + PartialOrd + Default + Add<Output = W> + Sub<Output = W>,
+    {
+        let nodes = self.get_nodes();
+        let mut left = Vec::<T>::new();
+        let mut right = Vec::<T>::new();
+        for n in nodes.iter() {
+            if !self.get_neighbors(n.clone()).is_empty() {
+                left.push(n.clone());
+            }
+        }
+        for n in nodes.iter() {
+            let has_incoming = left
+                .iter()
+                .any(|l| self.is_directly_connected(l.clone(), n.clone()));
+            if has_incoming {
+                if left.contains(n) {
+                    return Err("Graph is not bipartite");
+                }
+                right.push(n.clone());
+            } else if !left.contains(n) {
+                return Err("No perfect matching. Isolated node found.");
+            }
+        }
+
+        if left.len() != right.len() {
+            return Err("No perfect matching. Sides have different sizes.");
+        }
+        if left.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Cheapest edge between every pair (left, right), `None` if not connected
+        let mut best: Vec<Vec<Option<(W, E)>>> = vec![vec![None; right.len()]; left.len()];
+        for (i, l) in left.iter().enumerate() {
+            for (to, edge) in self.get_neighbors(l.clone()) {
+                let j = right.iter().position(|r| *r == to).unwrap();
+                let c = cost(&edge);
+                let replace = match &best[i][j] {
+                    None => true,
+                    Some((w, _)) => c < *w,
+                };
+                if replace {
+                    best[i][j] = Some((c, edge));
+                }
+            }
+        }
+
+        let a: Vec<Vec<Option<W>>> = best
+            .iter()
+            .map(|row| row.iter().map(|c| c.as_ref().map(|(w, _)| *w)).collect())
+            .collect();
+        let p = hungarian(&a)?;
+
+        let mut ret = Vec::<(T, T, E)>::new();
+        for (i, l) in left.iter().enumerate() {
+            let j = p[i];
+            let edge = best[i][j].as_ref().unwrap().1.clone();
+            ret.push((l.clone(), right[j].clone(), edge));
+        }
+        Ok(ret)
+    }
+}
+
+/// Hungarian algorithm with potentials over a square cost matrix where `None`
+/// means that the pair can't be assigned. Returns for every row the assigned column.
+pub(crate) fn hungarian<W>(a: &[Vec<Option<W>>]) -> Result<Vec<usize>, &'static str>
+where
+    W: Copy + PartialOrd + Default + Add<Output = W> + Sub<Output = W>,
+{
+    let n = a.len();
+    // 1-based indexes, column 0 is a sentinel. With non-negative costs the
+    // column potentials only decrease, so they are stored negated: every
+    // intermediate value is then non-negative and unsigned weights work
+    let mut u = vec![W::default(); n + 1];
+    let mut neg_v = vec![W::default(); n + 1];
+    let mut p = vec![0usize; n + 1];
+    let mut way = vec![0usize; n + 1];
+
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0usize;
+        let mut minv: Vec<Option<W>> = vec![None; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta: Option<W> = None;
+            let mut j1 = 0usize;
+            for j in 1..=n {
+                if used[j] {
+                    continue;
+                }
+                if let Some(c) = a[i0 - 1][j - 1] {
+                    let cur = c + neg_v[j] - u[i0];
+                    if minv[j].is_none_or(|m| cur < m) {
+                        minv[j] = Some(cur);
+                        way[j] = j0;
+                    }
+                }
+                if let Some(m) = minv[j] {
+                    if delta.is_none_or(|d| m < d) {
+                        delta = Some(m);
+                        j1 = j;
+                    }
+                }
+            }
+
+            let delta = match delta {
+                None => return Err("No perfect matching exists"),
+                Some(d) => d,
+            };
+            for j in 0..=n {
+                if used[j] {
+                    u[p[j]] = u[p[j]] + delta;
+                    neg_v[j] = neg_v[j] + delta;
+                } else if let Some(m) = minv[j] {
+                    minv[j] = Some(m - delta);
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+
+        loop {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut ret = vec![0usize; n];
+    for j in 1..=n {
+        ret[p[j] - 1] = j - 1;
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn assignment_min_cost() {
+        // Classic 3x3 instance, optimal cost is 5 (w1->j2, w2->j1, w3->j3)
+        let costs = [[4, 1, 3], [2, 0, 5], [3, 2, 2]];
+        let mut graph = MultiDiGraph::<String, i32>::new();
+        for i in 0..3 {
+            graph.add_node(format!("w{}", i + 1));
+            graph.add_node(format!("j{}", i + 1));
+        }
+        for (i, row) in costs.iter().enumerate() {
+            for (j, c) in row.iter().enumerate() {
+                graph.add_edge(format!("w{}", i + 1), format!("j{}", j + 1), *c);
+            }
+        }
+
+        let matching = graph.solve_assignment(|c| *c).unwrap();
+        let total: i32 = matching.iter().map(|m| m.2).sum();
+        assert_eq!(total, 5);
+        assert_eq!(
+            matching,
+            vec![
+                ("w1".to_string(), "j2".to_string(), 1),
+                ("w2".to_string(), "j1".to_string(), 2),
+                ("w3".to_string(), "j3".to_string(), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn assignment_parallel_and_missing_edges() {
+        let mut graph = MultiDiGraph::<i32, i32>::new();
+        for n in 1..=4 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 3, 10);
+        graph.add_edge(1, 3, 1);
+        graph.add_edge(1, 4, 2);
+        graph.add_edge(2, 3, 3);
+
+        let matching = graph.solve_assignment(|c| *c as f64).unwrap();
+        assert_eq!(matching, vec![(1, 4, 2), (2, 3, 3)]);
+
+        let mut graph = MultiDiGraph::<i32, u32>::new();
+        for n in 1..=6 {
+            graph.add_node(n);
+        }
+        for (from, to, c) in [
+            (1, 4, 0),
+            (1, 5, 7),
+            (2, 4, 1),
+            (2, 6, 9),
+            (3, 5, 2),
+            (3, 6, 8),
+        ] {
+            graph.add_edge(from, to, c);
+        }
+        let matching = graph.solve_assignment(|c| *c).unwrap();
+        assert_eq!(matching, vec![(1, 4, 0), (2, 6, 9), (3, 5, 2)]);
+
+        let mut graph = MultiDiGraph::<i32, i32>::new();
+        for n in 1..=4 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 3, 1);
+        graph.add_edge(2, 3, 1);
+        graph.add_edge(2, 4, 1);
+        graph.add_edge(4, 1, 1);
+        assert!(graph.solve_assignment(|c| *c).is_err());
+    }
+}
@@ -0,0 +1,440 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+
+/// Value of a node or edge attribute
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttrValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl AttrValue {
+    /// Returns the text of a `Str` value
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AttrValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of an `Int` value
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            AttrValue::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of a `Float` or `Int` value
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            AttrValue::Float(f) => Some(*f),
+            AttrValue::Int(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a `Bool` value
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            AttrValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AttrValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AttrValue::Bool(b) => write!(f, "{}", b),
+            AttrValue::Int(i) => write!(f, "{}", i),
+            AttrValue::Float(x) => write!(f, "{}", x),
+            AttrValue::Str(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<bool> for AttrValue {
+    fn from(b: bool) -> Self {
+        AttrValue::Bool(b)
+    }
+}
+
+impl From<i32> for AttrValue {
+    fn from(i: i32) -> Self {
+        AttrValue::Int(i as i64)
+    }
+}
+
+impl From<i64> for AttrValue {
+    fn from(i: i64) -> Self {
+        AttrValue::Int(i)
+    }
+}
+
+impl From<f64> for AttrValue {
+    fn from(x: f64) -> Self {
+        AttrValue::Float(x)
+    }
+}
+
+impl From<&str> for AttrValue {
+    fn from(s: &str) -> Self {
+        AttrValue::Str(s.to_string())
+    }
+}
+
+impl From<String> for AttrValue {
+    fn from(s: String) -> Self {
+        AttrValue::Str(s)
+    }
+}
+
+/// Attributes of a node or an edge, by name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AttrMap {
+    values: BTreeMap<String, AttrValue>,
+}
+
+impl AttrMap {
+    pub fn new() -> Self {
+        AttrMap {
+            values: BTreeMap::new(),
+        }
+    }
+
+    /// Sets attribute `key`, returning its previous value
+    pub fn insert(&mut self, key: &str, value: impl Into<AttrValue>) -> Option<AttrValue> {
+        self.values.insert(key.to_string(), value.into())
+    }
+
+    /// Removes attribute `key`, returning its value
+    pub fn remove(&mut self, key: &str) -> Option<AttrValue> {
+        self.values.remove(key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&AttrValue> {
+        self.values.get(key)
+    }
+
+    /// Returns attribute `key` if it is a string
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(|v| v.as_str())
+    }
+
+    /// Returns attribute `key` if it is an integer
+    pub fn get_int(&self, key: &str) -> Option<i64> {
+        self.get(key).and_then(|v| v.as_int())
+    }
+
+    /// Returns attribute `key` if it is a number
+    pub fn get_float(&self, key: &str) -> Option<f64> {
+        self.get(key).and_then(|v| v.as_float())
+    }
+
+    /// Returns attribute `key` if it is a boolean
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(|v| v.as_bool())
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// Iterates over the attributes sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &AttrValue)> {
+        self.values.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Attributes as text, as used by the dot exporter
+    pub(crate) fn to_strings(&self) -> Vec<(String, String)> {
+        self.values
+            .iter()
+            .map(|(k, v)| (k.clone(), v.to_string()))
+            .collect()
+    }
+}
+
+/// An edge of a graph with its attributes, see `DiGraph::get_edge`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeRef<'a, T> {
+    pub from: &'a T,
+    pub to: &'a T,
+    /// Attributes of the edge, `None` if none were set
+    pub attrs: Option<&'a AttrMap>,
+}
+
+/// Attribute maps of the nodes and edges of a graph. `K` identifies an edge
+pub(crate) struct Attributes<T, K>
+where
+    T: Ord,
+    K: Ord,
+{
+    pub nodes: BTreeMap<T, AttrMap>,
+    pub edges: BTreeMap<K, AttrMap>,
+}
+
+impl<T, K> Attributes<T, K>
+where
+    T: Ord,
+    K: Ord,
+{
+    pub fn new() -> Self {
+        Attributes {
+            nodes: BTreeMap::new(),
+            edges: BTreeMap::new(),
+        }
+    }
+
+    /// Attributes of `node`, empty if none were set
+    pub fn node(&self, node: &T) -> AttrMap {
+        self.nodes.get(node).cloned().unwrap_or_default()
+    }
+
+    /// Attributes of edge `key`, empty if none were set
+    pub fn edge(&self, key: &K) -> AttrMap {
+        self.edges.get(key).cloned().unwrap_or_default()
+    }
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the attributes of `node`, `None` if none were set
+    pub fn node_attrs(&self, node: &T) -> Option<&AttrMap> {
+        self.attrs.nodes.get(node)
+    }
+
+    /// Returns the attributes of `node` for modification. Attributes of nodes
+    /// not in the graph are ignored by the exporters
+    pub fn node_attrs_mut(&mut self, node: T) -> &mut AttrMap {
+        self.attrs.nodes.entry(node).or_default()
+    }
+
+    /// Returns the attributes of the edge from `from` to `to`, `None` if none
+    /// were set
+    pub fn edge_attrs(&self, from: &T, to: &T) -> Option<&AttrMap> {
+        self.attrs.edges.get(&(from.clone(), to.clone()))
+    }
+
+    /// Returns the attributes of the edge from `from` to `to` for
+    /// modification. Attributes of edges not in the graph are ignored by the
+    /// exporters
+    pub fn edge_attrs_mut(&mut self, from: T, to: T) -> &mut AttrMap {
+        self.attrs.edges.entry((from, to)).or_default()
+    }
+
+    /// Returns the edge from `from` to `to` with its attributes, `None` if
+    /// there is no such edge
+    pub fn get_edge(&self, from: &T, to: &T) -> Option<EdgeRef<'_, T>> {
+        let (i, j) = (self.index_of(from)?, self.index_of(to)?);
+        if !self.neighbor_ids(i).contains(&j) {
+            return None;
+        }
+        Some(EdgeRef {
+            from: self.node(i)?,
+            to: self.node(j)?,
+            attrs: self.edge_attrs(from, to),
+        })
+    }
+}
+
+/// Edges of undirected graphs are keyed by their ordered pair of nodes
+pub(crate) fn undirected_key<T: Ord>(a: T, b: T) -> (T, T) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the attributes of `node`, `None` if none were set
+    pub fn node_attrs(&self, node: &T) -> Option<&AttrMap> {
+        self.digraph.node_attrs(node)
+    }
+
+    /// Returns the attributes of `node` for modification. Attributes of nodes
+    /// not in the graph are ignored by the exporters
+    pub fn node_attrs_mut(&mut self, node: T) -> &mut AttrMap {
+        self.digraph.node_attrs_mut(node)
+    }
+
+    /// Returns the attributes of the edge between `a` and `b`, `None` if none
+    /// were set
+    pub fn edge_attrs(&self, a: &T, b: &T) -> Option<&AttrMap> {
+        let (a, b) = undirected_key(a, b);
+        self.digraph.edge_attrs(a, b)
+    }
+
+    /// Returns the attributes of the edge between `a` and `b` for
+    /// modification. Attributes of edges not in the graph are ignored by the
+    /// exporters
+    pub fn edge_attrs_mut(&mut self, a: T, b: T) -> &mut AttrMap {
+        let (a, b) = undirected_key(a, b);
+        self.digraph.edge_attrs_mut(a, b)
+    }
+
+    /// Returns the edge between `a` and `b` with its attributes, `None` if
+    /// there is no such edge
+    pub fn get_edge(&self, a: &T, b: &T) -> Option<EdgeRef<'_, T>> {
+        let edge = self.digraph.get_edge(a, b)?;
+        Some(EdgeRef {
+            attrs: self.edge_attrs(a, b),
+            ..edge
+        })
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the attributes of `node`, `None` if none were set
+    pub fn node_attrs(&self, node: &T) -> Option<&AttrMap> {
+        self.attrs.nodes.get(node)
+    }
+
+    /// Returns the attributes of `node` for modification. Attributes of nodes
+    /// not in the graph are ignored by the exporters
+    pub fn node_attrs_mut(&mut self, node: T) -> &mut AttrMap {
+        self.attrs.nodes.entry(node).or_default()
+    }
+
+    /// Returns the attributes of the edge from `from` to `to` labeled `edge`,
+    /// `None` if none were set
+    pub fn edge_attrs(&self, from: &T, to: &T, edge: &E) -> Option<&AttrMap> {
+        self.attrs
+            .edges
+            .get(&(from.clone(), to.clone(), edge.clone()))
+    }
+
+    /// Returns the attributes of the edge from `from` to `to` labeled `edge`
+    /// for modification. Attributes of edges not in the graph are ignored by
+    /// the exporters
+    pub fn edge_attrs_mut(&mut self, from: T, to: T, edge: E) -> &mut AttrMap {
+        self.attrs.edges.entry((from, to, edge)).or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AttrValue;
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::graphml::graph_from_graphml_str;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn attributes() {
+        let mut graph = DiGraph::<String>::new();
+        graph.add_node(String::from("a"));
+        graph.add_node(String::from("b"));
+        graph.add_edge(String::from("a"), String::from("b"));
+        graph
+            .node_attrs_mut(String::from("a"))
+            .insert("color", "red");
+        graph.node_attrs_mut(String::from("a")).insert("size", 3);
+        graph
+            .edge_attrs_mut(String::from("a"), String::from("b"))
+            .insert("weight", 1.5);
+
+        let attrs = graph.node_attrs(&String::from("a")).unwrap();
+        assert_eq!(attrs.get_str("color"), Some("red"));
+        assert_eq!(attrs.get_int("size"), Some(3));
+        assert_eq!(attrs.get_float("size"), Some(3.0));
+        assert_eq!(attrs.get_bool("size"), None);
+        assert!(graph.node_attrs(&String::from("b")).is_none());
+        let edge = graph
+            .edge_attrs(&String::from("a"), &String::from("b"))
+            .unwrap();
+        assert_eq!(edge.get("weight"), Some(&AttrValue::Float(1.5)));
+
+        assert_eq!(
+            graph.to_dot_string("g"),
+            "digraph g {\n    a [color=\"red\", size=\"3\"];\n    b;\n    a -> b [weight=\"1.5\"];\n}\n"
+        );
+        let s = graph.to_graphml_string();
+        assert!(s.contains("for=\"node\" attr.name=\"color\" attr.type=\"string\""));
+        assert!(s.contains("for=\"node\" attr.name=\"size\" attr.type=\"long\""));
+        assert!(s.contains("for=\"edge\" attr.name=\"weight\" attr.type=\"double\""));
+        assert!(s.contains("<data key=\"d2\">red</data>"));
+    }
+
+    #[test]
+    fn attributes_undirected_and_multi() {
+        let mut graph = Graph::<i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_edge(1, 2);
+        graph.edge_attrs_mut(2, 1).insert("style", "dashed");
+        assert_eq!(
+            graph.edge_attrs(&1, &2).unwrap().get_str("style"),
+            Some("dashed")
+        );
+        assert!(graph
+            .to_dot_string("g")
+            .contains("1 -- 2 [style=\"dashed\"];"));
+        let read = graph_from_graphml_str::<i32>(&graph.to_graphml_string()).unwrap();
+        assert_eq!(read.get_neighbors(1), vec![2]);
+
+        let mut graph = MultiDiGraph::<i32, String>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_edge(1, 2, String::from("x"));
+        graph.add_edge(1, 2, String::from("y"));
+        graph
+            .edge_attrs_mut(1, 2, String::from("y"))
+            .insert("bold", true);
+        let s = graph.to_dot_string("m");
+        assert!(s.contains("1 -> 2 [label=\"x\"];"));
+        assert!(s.contains("1 -> 2 [label=\"y\", bold=\"true\"];"));
+        assert!(graph
+            .to_graphml_string()
+            .contains("for=\"edge\" attr.name=\"bold\" attr.type=\"boolean\""));
+    }
+
+    #[test]
+    fn get_edge() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3)]);
+        graph.edge_attrs_mut(1, 2).insert("weight", 2.5);
+        let edge = graph.get_edge(&1, &2).unwrap();
+        assert_eq!((edge.from, edge.to), (&1, &2));
+        assert_eq!(edge.attrs.unwrap().get_float("weight"), Some(2.5));
+        assert!(graph.get_edge(&2, &3).unwrap().attrs.is_none());
+        assert!(graph.get_edge(&2, &1).is_none());
+        assert!(graph.get_edge(&1, &9).is_none());
+
+        let mut graph = Graph::<i32>::new();
+        graph.extend_with_edges([(2, 1)]);
+        graph.edge_attrs_mut(1, 2).insert("color", "red");
+        let edge = graph.get_edge(&2, &1).unwrap();
+        assert_eq!((edge.from, edge.to), (&2, &1));
+        assert_eq!(edge.attrs.unwrap().get_str("color"), Some("red"));
+        assert!(graph.get_edge(&1, &2).is_some());
+    }
+}
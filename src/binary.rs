@@ -0,0 +1,334 @@
+use std::str::FromStr;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+/// First bytes of every encoded graph
+const MAGIC: &[u8; 4] = b"RUGR";
+/// Version of the encoding, increased on incompatible changes
+const VERSION: u8 = 1;
+
+const KIND_DIGRAPH: u8 = 0;
+const KIND_GRAPH: u8 = 1;
+const KIND_MULTIDIGRAPH: u8 = 2;
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a compact binary encoding of the graph, see
+    /// `digraph_from_bytes`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let adj = self.adjacency();
+        let edges = adj.edges(true);
+        let mut w = Writer::new(KIND_DIGRAPH, &adj.nodes, edges.len());
+        for (i, j) in edges.into_iter() {
+            w.edge(i, j);
+        }
+        w.bytes
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a compact binary encoding of the graph, see `graph_from_bytes`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let adj = self.adjacency();
+        let edges = adj.edges(false);
+        let mut w = Writer::new(KIND_GRAPH, &adj.nodes, edges.len());
+        for (i, j) in edges.into_iter() {
+            w.edge(i, j);
+        }
+        w.bytes
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a compact binary encoding of the graph, see
+    /// `multidigraph_from_bytes`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let adj = self.adjacency();
+        let mut edges = Vec::<(usize, usize, E)>::new();
+        for (i, n) in adj.nodes.iter().enumerate() {
+            let values = self.get_neighbors(n.clone()).into_iter().map(|(_, e)| e);
+            for (j, e) in adj.succ[i].iter().zip(values) {
+                edges.push((i, *j, e));
+            }
+        }
+        let mut w = Writer::new(KIND_MULTIDIGRAPH, &adj.nodes, edges.len());
+        for (i, j, e) in edges.into_iter() {
+            w.edge(i, j);
+            w.string(&e.to_string());
+        }
+        w.bytes
+    }
+}
+
+/// Reads a directed graph encoded with `DiGraph::to_bytes`. Nodes are stored
+/// as text and parsed as `T`
+pub fn digraph_from_bytes<T>(bytes: &[u8]) -> Result<DiGraph<T>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let mut r = Reader::new(bytes, KIND_DIGRAPH)?;
+    let nodes = r.nodes::<T>()?;
+    let count = r.count()?;
+    let mut graph = DiGraph::<T>::with_capacity(nodes.len(), count);
+    for n in nodes.iter() {
+        graph.add_node(n.clone());
+    }
+    for _ in 0..count {
+        let (i, j) = r.edge(nodes.len())?;
+        graph.add_edge(nodes[i].clone(), nodes[j].clone());
+    }
+    r.finish()?;
+    Ok(graph)
+}
+
+/// Reads an undirected graph encoded with `Graph::to_bytes`. Nodes are stored
+/// as text and parsed as `T`
+pub fn graph_from_bytes<T>(bytes: &[u8]) -> Result<Graph<T>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let mut r = Reader::new(bytes, KIND_GRAPH)?;
+    let nodes = r.nodes::<T>()?;
+    let mut graph = Graph::<T>::new();
+    for n in nodes.iter() {
+        graph.add_node(n.clone());
+    }
+    for _ in 0..r.count()? {
+        let (i, j) = r.edge(nodes.len())?;
+        graph.add_edge(nodes[i].clone(), nodes[j].clone());
+    }
+    r.finish()?;
+    Ok(graph)
+}
+
+/// Reads a multi directed graph encoded with `MultiDiGraph::to_bytes`. Nodes
+/// and edge values are stored as text and parsed as `T` and `E`
+pub fn multidigraph_from_bytes<T, E>(bytes: &[u8]) -> Result<MultiDiGraph<T, E>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let mut r = Reader::new(bytes, KIND_MULTIDIGRAPH)?;
+    let nodes = r.nodes::<T>()?;
+    let mut graph = MultiDiGraph::<T, E>::new();
+    for n in nodes.iter() {
+        graph.add_node(n.clone());
+    }
+    for _ in 0..r.count()? {
+        let (i, j) = r.edge(nodes.len())?;
+        let e = r.value::<E>()?;
+        graph.add_edge(nodes[i].clone(), nodes[j].clone(), e);
+    }
+    r.finish()?;
+    Ok(graph)
+}
+
+/// Encoding: magic, version, kind, node count, nodes as length prefixed
+/// text, edge count and edges as pairs of node indexes (followed by the
+/// length prefixed value in multi directed graphs). Numbers are LEB128
+struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    fn new<T: std::fmt::Display>(kind: u8, nodes: &[T], edges: usize) -> Self {
+        let mut w = Writer {
+            bytes: Vec::with_capacity(16 + nodes.len() * 8 + edges * 4),
+        };
+        w.bytes.extend_from_slice(MAGIC);
+        w.bytes.push(VERSION);
+        w.bytes.push(kind);
+        w.number(nodes.len() as u64);
+        for n in nodes.iter() {
+            w.string(&n.to_string());
+        }
+        w.number(edges as u64);
+        w
+    }
+
+    fn number(&mut self, mut n: u64) {
+        loop {
+            let byte = (n & 0x7f) as u8;
+            n >>= 7;
+            if n == 0 {
+                self.bytes.push(byte);
+                return;
+            }
+            self.bytes.push(byte | 0x80);
+        }
+    }
+
+    fn string(&mut self, s: &str) {
+        self.number(s.len() as u64);
+        self.bytes.extend_from_slice(s.as_bytes());
+    }
+
+    fn edge(&mut self, from: usize, to: usize) {
+        self.number(from as u64);
+        self.number(to as u64);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Checks the header
+    fn new(bytes: &'a [u8], kind: u8) -> Result<Self, &'static str> {
+        if bytes.len() < 6 || &bytes[..4] != MAGIC {
+            return Err("Binary graph not correct. Header not found.");
+        }
+        if bytes[4] != VERSION {
+            return Err("Binary graph not correct. Unsupported version.");
+        }
+        if bytes[5] != kind {
+            return Err("Binary graph not correct. Wrong kind of graph.");
+        }
+        Ok(Reader { bytes: &bytes[6..] })
+    }
+
+    fn number(&mut self) -> Result<u64, &'static str> {
+        let mut n = 0u64;
+        for shift in (0..64).step_by(7) {
+            let (byte, rest) = self
+                .bytes
+                .split_first()
+                .ok_or("Binary graph not correct. Unexpected end.")?;
+            self.bytes = rest;
+            n |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        Err("Binary graph not correct. Number too long.")
+    }
+
+    /// An element count, checked against the remaining bytes so corrupted
+    /// input can't trigger huge allocations
+    fn count(&mut self) -> Result<usize, &'static str> {
+        let n = self.number()?;
+        if n > self.bytes.len() as u64 {
+            return Err("Binary graph not correct. Unexpected end.");
+        }
+        Ok(n as usize)
+    }
+
+    fn value<V: FromStr>(&mut self) -> Result<V, &'static str> {
+        let len = self.count()?;
+        let (s, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        let s = std::str::from_utf8(s).map_err(|_| "Binary graph not correct. Invalid text.")?;
+        V::from_str(s).map_err(|_| "Binary graph not correct. Value can't be parsed.")
+    }
+
+    fn nodes<T: FromStr>(&mut self) -> Result<Vec<T>, &'static str> {
+        let n = self.count()?;
+        let mut ret = Vec::<T>::with_capacity(n);
+        for _ in 0..n {
+            ret.push(self.value()?);
+        }
+        Ok(ret)
+    }
+
+    fn edge(&mut self, nodes: usize) -> Result<(usize, usize), &'static str> {
+        let (i, j) = (self.number()?, self.number()?);
+        if i >= nodes as u64 || j >= nodes as u64 {
+            return Err("Binary graph not correct. Unknown node.");
+        }
+        Ok((i as usize, j as usize))
+    }
+
+    fn finish(&self) -> Result<(), &'static str> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err("Binary graph not correct. Trailing bytes.")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::digraph_from_bytes;
+    use super::graph_from_bytes;
+    use super::multidigraph_from_bytes;
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn binary_round_trip() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 0..200 {
+            graph.add_node(n);
+        }
+        for n in 0..199 {
+            graph.add_edge(n, n + 1);
+        }
+        graph.add_edge(199, 0);
+        let bytes = graph.to_bytes();
+        assert_eq!(&bytes[..6], b"RUGR\x01\x00");
+        let read = digraph_from_bytes::<i32>(&bytes).unwrap();
+        assert_eq!(read.get_nodes(), graph.get_nodes());
+        assert_eq!(read.get_neighbors(199), vec![0]);
+        assert_eq!(read.get_neighbors(150), vec![151]);
+        assert!(graph_from_bytes::<i32>(&bytes).is_err());
+
+        let mut graph = Graph::<String>::new();
+        graph.add_node(String::from("ä"));
+        graph.add_node(String::from("b"));
+        graph.add_edge(String::from("ä"), String::from("b"));
+        let read = graph_from_bytes::<String>(&graph.to_bytes()).unwrap();
+        assert_eq!(read.get_neighbors(String::from("b")), vec!["ä"]);
+
+        let mut graph = MultiDiGraph::<i32, String>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_edge(1, 2, String::from("x"));
+        graph.add_edge(1, 2, String::from("y"));
+        graph.add_edge(2, 1, String::from("z"));
+        let read = multidigraph_from_bytes::<i32, String>(&graph.to_bytes()).unwrap();
+        assert_eq!(read.get_neighbors(1), graph.get_neighbors(1));
+        assert_eq!(read.get_neighbors(2), graph.get_neighbors(2));
+    }
+
+    #[test]
+    fn binary_corrupted() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_edge(1, 2);
+        let bytes = graph.to_bytes();
+        for len in 0..bytes.len() {
+            assert!(digraph_from_bytes::<i32>(&bytes[..len]).is_err());
+        }
+        let mut extra = bytes.clone();
+        extra.push(0);
+        assert!(digraph_from_bytes::<i32>(&extra).is_err());
+        let mut version = bytes.clone();
+        version[4] = 2;
+        assert!(digraph_from_bytes::<i32>(&version).is_err());
+        let mut huge = bytes[..6].to_vec();
+        huge.extend_from_slice(&[0xff, 0xff, 0xff, 0xff, 0x0f]);
+        assert!(digraph_from_bytes::<i32>(&huge).is_err());
+    }
+}
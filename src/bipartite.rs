@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use crate::graph::Graph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+
+/// `BipartiteGraph` is a `generic` undirected graph with two node sets, left
+/// nodes of type `L` and right nodes of type `R`, where edges always join a
+/// left node with a right node. Both types must implement:
+/// `Ord + Clone + std::fmt::Display + std::fmt::Debug`
+pub struct BipartiteGraph<L, R>
+where
+    L: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    R: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    left: Vec<L>,
+    right: Vec<R>,
+    left_index: BTreeMap<L, usize>,
+    right_index: BTreeMap<R, usize>,
+    /// Right neighbors of every left node and left neighbors of every right
+    /// node, as indexes
+    left_adj: Vec<Vec<usize>>,
+    right_adj: Vec<Vec<usize>>,
+}
+
+impl<L, R> BipartiteGraph<L, R>
+where
+    L: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    R: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        BipartiteGraph::<L, R> {
+            left: Vec::new(),
+            right: Vec::new(),
+            left_index: BTreeMap::new(),
+            right_index: BTreeMap::new(),
+            left_adj: Vec::new(),
+            right_adj: Vec::new(),
+        }
+    }
+
+    /// Adds a new node `elem` to the left side
+    pub fn add_left(&mut self, elem: L) {
+        if !self.left_index.contains_key(&elem) {
+            self.left_index.insert(elem.clone(), self.left.len());
+            self.left.push(elem);
+            self.left_adj.push(Vec::new());
+        }
+    }
+
+    /// Adds a new node `elem` to the right side
+    pub fn add_right(&mut self, elem: R) {
+        if !self.right_index.contains_key(&elem) {
+            self.right_index.insert(elem.clone(), self.right.len());
+            self.right.push(elem);
+            self.right_adj.push(Vec::new());
+        }
+    }
+
+    /// Creates a new edge between left node `l` and right node `r`. Returns
+    /// an error if one of them was not added to its side
+    pub fn add_edge(&mut self, l: L, r: R) -> Result<(), &'static str> {
+        let i = *self.left_index.get(&l).ok_or("Left node not found")?;
+        let j = *self.right_index.get(&r).ok_or("Right node not found")?;
+        if !self.left_adj[i].contains(&j) {
+            self.left_adj[i].push(j);
+            self.right_adj[j].push(i);
+        }
+        Ok(())
+    }
+
+    /// Returns if left node `l` and right node `r` are joined by an edge
+    pub fn is_directly_connected(&self, l: &L, r: &R) -> bool {
+        match (self.left_index.get(l), self.right_index.get(r)) {
+            (Some(i), Some(j)) => self.left_adj[*i].contains(j),
+            _ => false,
+        }
+    }
+
+    /// Returns the left nodes
+    pub fn left_nodes(&self) -> Vec<L> {
+        self.left.clone()
+    }
+
+    /// Returns the right nodes
+    pub fn right_nodes(&self) -> Vec<R> {
+        self.right.clone()
+    }
+
+    /// Returns the right nodes joined to left node `l`
+    pub fn left_neighbors(&self, l: &L) -> Vec<R> {
+        match self.left_index.get(l) {
+            Some(i) => self.left_adj[*i]
+                .iter()
+                .map(|j| self.right[*j].clone())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the left nodes joined to right node `r`
+    pub fn right_neighbors(&self, r: &R) -> Vec<L> {
+        match self.right_index.get(r) {
+            Some(j) => self.right_adj[*j]
+                .iter()
+                .map(|i| self.left[*i].clone())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns how many edges are in the graph
+    pub fn count_edges(&self) -> usize {
+        self.left_adj.iter().map(|a| a.len()).sum()
+    }
+
+    /// Returns the projection onto the left side: a graph of the left nodes
+    /// where two nodes are joined when they share a right neighbor
+    pub fn left_projection(&self) -> Graph<L> {
+        projection(&self.left, &self.left_adj, &self.right_adj)
+    }
+
+    /// Returns the projection onto the right side: a graph of the right nodes
+    /// where two nodes are joined when they share a left neighbor
+    pub fn right_projection(&self) -> Graph<R> {
+        projection(&self.right, &self.right_adj, &self.left_adj)
+    }
+}
+
+impl<L, R> Default for BipartiteGraph<L, R>
+where
+    L: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    R: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `adj` holds the neighbors of `nodes` on the other side, `other_adj` the
+/// neighbors of the other side
+fn projection<T>(nodes: &[T], adj: &[Vec<usize>], other_adj: &[Vec<usize>]) -> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    let mut graph = Graph::<T>::new();
+    for n in nodes.iter() {
+        graph.add_node(n.clone());
+    }
+    for (i, a) in adj.iter().enumerate() {
+        for k in a.iter() {
+            for j in other_adj[*k].iter().filter(|j| **j > i) {
+                graph.add_edge(nodes[i].clone(), nodes[*j].clone());
+            }
+        }
+    }
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BipartiteGraph;
+    use crate::rugraph::IDiGraph;
+
+    #[test]
+    fn bipartite() {
+        // Users and the movies they watched
+        let mut graph = BipartiteGraph::<String, u32>::new();
+        for user in ["ann", "bob", "eve"] {
+            graph.add_left(user.to_string());
+        }
+        for movie in [1, 2, 3] {
+            graph.add_right(movie);
+        }
+        graph.add_edge("ann".to_string(), 1).unwrap();
+        graph.add_edge("ann".to_string(), 2).unwrap();
+        graph.add_edge("bob".to_string(), 2).unwrap();
+        graph.add_edge("bob".to_string(), 2).unwrap();
+        graph.add_edge("eve".to_string(), 3).unwrap();
+        assert!(graph.add_edge("joe".to_string(), 1).is_err());
+        assert!(graph.add_edge("ann".to_string(), 9).is_err());
+
+        assert_eq!(graph.count_edges(), 4);
+        assert!(graph.is_directly_connected(&"bob".to_string(), &2));
+        assert!(!graph.is_directly_connected(&"bob".to_string(), &1));
+        assert_eq!(graph.left_neighbors(&"ann".to_string()), vec![1, 2]);
+        assert_eq!(graph.right_neighbors(&2), vec!["ann", "bob"]);
+
+        let users = graph.left_projection();
+        assert_eq!(users.get_neighbors("ann".to_string()), vec!["bob"]);
+        assert!(users.get_neighbors("eve".to_string()).is_empty());
+
+        let movies = graph.right_projection();
+        assert_eq!(movies.get_neighbors(2), vec![1]);
+        assert!(movies.get_neighbors(3).is_empty());
+    }
+}
@@ -0,0 +1,232 @@
+use std::ffi::c_char;
+use std::ffi::c_int;
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::ptr;
+
+use crate::digraph::DiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+
+/// Opaque handle of a directed graph used from C, created with
+/// `rugraph_digraph_new` and released with `rugraph_digraph_free`. Nodes are
+/// NUL terminated UTF-8 strings. Strings and paths returned by the library
+/// belong to the caller, who releases them with `rugraph_string_free` and
+/// `rugraph_path_free`. The declarations are in `include/rugraph.h`
+pub struct RugraphDiGraph {
+    graph: DiGraph<String>,
+}
+
+/// Returns the string pointed by `s`, `None` if it is null or not UTF-8
+unsafe fn to_string(s: *const c_char) -> Option<String> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok().map(str::to_owned)
+}
+
+/// Returns a copy of `s` allocated by the library, null if it contains a NUL
+fn to_c_string(s: String) -> *mut c_char {
+    CString::new(s).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Returns a new empty graph
+#[no_mangle]
+pub extern "C" fn rugraph_digraph_new() -> *mut RugraphDiGraph {
+    Box::into_raw(Box::new(RugraphDiGraph {
+        graph: DiGraph::new(),
+    }))
+}
+
+/// Releases `graph`. Does nothing if it is null
+///
+/// # Safety
+/// `graph` must be null or returned by `rugraph_digraph_new` and not freed yet
+#[no_mangle]
+pub unsafe extern "C" fn rugraph_digraph_free(graph: *mut RugraphDiGraph) {
+    if !graph.is_null() {
+        drop(Box::from_raw(graph));
+    }
+}
+
+/// Adds a new node `node`. Returns 0, or -1 if an argument is not valid
+///
+/// # Safety
+/// `graph` must be a valid handle and `node` null or a NUL terminated string
+#[no_mangle]
+pub unsafe extern "C" fn rugraph_digraph_add_node(
+    graph: *mut RugraphDiGraph,
+    node: *const c_char,
+) -> c_int {
+    let (Some(graph), Some(node)) = (graph.as_mut(), to_string(node)) else {
+        return -1;
+    };
+    graph.graph.add_node(node);
+    0
+}
+
+/// Creates a new edge from node `from` to node `to`. Returns 0, or -1 if an
+/// argument is not valid or a node doesn't exist
+///
+/// # Safety
+/// `graph` must be a valid handle, `from` and `to` null or NUL terminated
+/// strings
+#[no_mangle]
+pub unsafe extern "C" fn rugraph_digraph_add_edge(
+    graph: *mut RugraphDiGraph,
+    from: *const c_char,
+    to: *const c_char,
+) -> c_int {
+    let (Some(graph), Some(from), Some(to)) = (graph.as_mut(), to_string(from), to_string(to))
+    else {
+        return -1;
+    };
+    if !graph.graph.node_exists(from.clone()) || !graph.graph.node_exists(to.clone()) {
+        return -1;
+    }
+    graph.graph.add_edge(from, to);
+    0
+}
+
+/// Returns how many nodes are in the graph, 0 if `graph` is null
+///
+/// # Safety
+/// `graph` must be null or a valid handle
+#[no_mangle]
+pub unsafe extern "C" fn rugraph_digraph_count_nodes(graph: *const RugraphDiGraph) -> usize {
+    graph.as_ref().map_or(0, |g| g.graph.count_nodes())
+}
+
+/// Returns a path with the fewest edges from node `from` to node `to`, both
+/// included, as an array of `*len` strings released with
+/// `rugraph_path_free`. Returns null, with `*len` set to 0, if `to` can't be
+/// reached or an argument is not valid
+///
+/// # Safety
+/// `graph` must be a valid handle, `from` and `to` null or NUL terminated
+/// strings and `len` a valid pointer
+#[no_mangle]
+pub unsafe extern "C" fn rugraph_digraph_shortest_path(
+    graph: *const RugraphDiGraph,
+    from: *const c_char,
+    to: *const c_char,
+    len: *mut usize,
+) -> *mut *mut c_char {
+    *len = 0;
+    let (Some(graph), Some(from), Some(to)) = (graph.as_ref(), to_string(from), to_string(to))
+    else {
+        return ptr::null_mut();
+    };
+    let Some(path) = graph.graph.shortest_path(from, to) else {
+        return ptr::null_mut();
+    };
+    let path: Box<[*mut c_char]> = path.into_iter().map(to_c_string).collect();
+    *len = path.len();
+    Box::into_raw(path) as *mut *mut c_char
+}
+
+/// Releases a path returned by `rugraph_digraph_shortest_path`. Does
+/// nothing if it is null
+///
+/// # Safety
+/// `path` must be null or returned by `rugraph_digraph_shortest_path` with
+/// length `len` and not freed yet
+#[no_mangle]
+pub unsafe extern "C" fn rugraph_path_free(path: *mut *mut c_char, len: usize) {
+    if path.is_null() {
+        return;
+    }
+    let path = Box::from_raw(ptr::slice_from_raw_parts_mut(path, len));
+    for s in path.iter() {
+        rugraph_string_free(*s);
+    }
+}
+
+/// Returns the DOT representation of the graph, released with
+/// `rugraph_string_free`. Returns null if an argument is not valid
+///
+/// # Safety
+/// `graph` must be a valid handle and `graph_name` null or a NUL terminated
+/// string
+#[no_mangle]
+pub unsafe extern "C" fn rugraph_digraph_to_dot(
+    graph: *const RugraphDiGraph,
+    graph_name: *const c_char,
+) -> *mut c_char {
+    let (Some(graph), Some(graph_name)) = (graph.as_ref(), to_string(graph_name)) else {
+        return ptr::null_mut();
+    };
+    to_c_string(graph.graph.to_dot_string(&graph_name))
+}
+
+/// Releases a string returned by the library. Does nothing if it is null
+///
+/// # Safety
+/// `s` must be null or returned by the library and not freed yet
+#[no_mangle]
+pub unsafe extern "C" fn rugraph_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rugraph_digraph_add_edge;
+    use super::rugraph_digraph_add_node;
+    use super::rugraph_digraph_count_nodes;
+    use super::rugraph_digraph_free;
+    use super::rugraph_digraph_new;
+    use super::rugraph_digraph_shortest_path;
+    use super::rugraph_digraph_to_dot;
+    use super::rugraph_path_free;
+    use super::rugraph_string_free;
+    use std::ffi::CStr;
+    use std::ffi::CString;
+    use std::ptr;
+
+    #[test]
+    fn capi_digraph() {
+        let c = |s: &str| CString::new(s).unwrap();
+        unsafe {
+            let graph = rugraph_digraph_new();
+            for n in ["a", "b", "c", "d"] {
+                assert_eq!(rugraph_digraph_add_node(graph, c(n).as_ptr()), 0);
+            }
+            assert_eq!(rugraph_digraph_add_node(graph, ptr::null()), -1);
+            assert_eq!(rugraph_digraph_count_nodes(graph), 4);
+            assert_eq!(
+                rugraph_digraph_add_edge(graph, c("a").as_ptr(), c("b").as_ptr()),
+                0
+            );
+            assert_eq!(
+                rugraph_digraph_add_edge(graph, c("b").as_ptr(), c("c").as_ptr()),
+                0
+            );
+            assert_eq!(
+                rugraph_digraph_add_edge(graph, c("a").as_ptr(), c("z").as_ptr()),
+                -1
+            );
+
+            let mut len = 0;
+            let path =
+                rugraph_digraph_shortest_path(graph, c("a").as_ptr(), c("c").as_ptr(), &mut len);
+            assert_eq!(len, 3);
+            let nodes: Vec<&str> = (0..len)
+                .map(|i| CStr::from_ptr(*path.add(i)).to_str().unwrap())
+                .collect();
+            assert_eq!(nodes, vec!["a", "b", "c"]);
+            rugraph_path_free(path, len);
+            let path =
+                rugraph_digraph_shortest_path(graph, c("c").as_ptr(), c("a").as_ptr(), &mut len);
+            assert!(path.is_null());
+            assert_eq!(len, 0);
+
+            let dot = rugraph_digraph_to_dot(graph, c("g").as_ptr());
+            assert!(CStr::from_ptr(dot).to_str().unwrap().contains("b -> c;"));
+            rugraph_string_free(dot);
+            rugraph_digraph_free(graph);
+            rugraph_digraph_free(ptr::null_mut());
+        }
+    }
+}
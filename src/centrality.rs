@@ -0,0 +1,315 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use crate::adjacency::Adjacency;
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + std::hash::Hash,
+{
+    /// Returns the PageRank of every node.
+    /// `damping` is the probability of following an edge (usually `0.85`),
+    /// the computation stops after `max_iter` iterations or when the
+    /// total change between two iterations is lower than `epsilon`
+    pub fn pagerank(&self, damping: f64, max_iter: usize, epsilon: f64) -> HashMap<T, f64> {
+        pagerank(&self.adjacency(), damping, max_iter, epsilon)
+    }
+
+    /// Returns the degree centrality of every node: its number of incoming
+    /// and outgoing edges divided by the number of other nodes
+    pub fn degree_centrality(&self) -> HashMap<T, f64> {
+        degree_centrality(&self.adjacency(), true)
+    }
+
+    /// Returns a histogram mapping every degree (incoming plus outgoing edges)
+    /// to the number of nodes with that degree
+    pub fn degree_distribution(&self) -> BTreeMap<usize, usize> {
+        degree_distribution(&self.adjacency(), true)
+    }
+
+    /// Returns the eigenvector centrality of every node computed by power
+    /// iteration, where a node is important if it is pointed to by important nodes.
+    /// Fails if the iteration doesn't converge to tolerance `tol` within `max_iter` steps
+    pub fn eigenvector_centrality(
+        &self,
+        max_iter: usize,
+        tol: f64,
+    ) -> Result<HashMap<T, f64>, &'static str> {
+        eigenvector_centrality(&self.adjacency(), max_iter, tol)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + std::hash::Hash,
+{
+    /// Returns the degree centrality of every node: its number of neighbors
+    /// divided by the number of other nodes
+    pub fn degree_centrality(&self) -> HashMap<T, f64> {
+        degree_centrality(&self.adjacency(), false)
+    }
+
+    /// Returns a histogram mapping every degree to the number of nodes with that degree
+    pub fn degree_distribution(&self) -> BTreeMap<usize, usize> {
+        degree_distribution(&self.adjacency(), false)
+    }
+
+    /// Returns the eigenvector centrality of every node computed by power
+    /// iteration, where a node is important if its neighbors are important.
+    /// Fails if the iteration doesn't converge to tolerance `tol` within `max_iter` steps
+    pub fn eigenvector_centrality(
+        &self,
+        max_iter: usize,
+        tol: f64,
+    ) -> Result<HashMap<T, f64>, &'static str> {
+        eigenvector_centrality(&self.adjacency(), max_iter, tol)
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + std::hash::Hash,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the PageRank of every node. Parallel edges count as many links.
+    /// `damping` is the probability of following an edge (usually `0.85`),
+    /// the computation stops after `max_iter` iterations or when the
+    /// total change between two iterations is lower than `epsilon`
+    pub fn pagerank(&self, damping: f64, max_iter: usize, epsilon: f64) -> HashMap<T, f64> {
+        pagerank(&self.adjacency(), damping, max_iter, epsilon)
+    }
+
+    /// Returns the degree centrality of every node: its number of incoming
+    /// and outgoing edges (parallel edges included) divided by the number of other nodes
+    pub fn degree_centrality(&self) -> HashMap<T, f64> {
+        degree_centrality(&self.adjacency(), true)
+    }
+
+    /// Returns a histogram mapping every degree (incoming plus outgoing edges)
+    /// to the number of nodes with that degree
+    pub fn degree_distribution(&self) -> BTreeMap<usize, usize> {
+        degree_distribution(&self.adjacency(), true)
+    }
+}
+
+/// Power iteration PageRank. Dangling nodes spread their rank over all nodes.
+fn pagerank<T>(adj: &Adjacency<T>, damping: f64, max_iter: usize, epsilon: f64) -> HashMap<T, f64>
+where
+    T: Ord + Clone + std::hash::Hash,
+{
+    let n = adj.len();
+    let mut ret = HashMap::<T, f64>::new();
+    if n == 0 {
+        return ret;
+    }
+
+    let nf = n as f64;
+    let mut rank = vec![1.0 / nf; n];
+    for _ in 0..max_iter {
+        let dangling: f64 = (0..n)
+            .filter(|i| adj.succ[*i].is_empty())
+            .map(|i| rank[i])
+            .sum();
+        let base = (1.0 - damping) / nf + damping * dangling / nf;
+        let mut next = vec![base; n];
+        for (i, s) in adj.succ.iter().enumerate() {
+            if s.is_empty() {
+                continue;
+            }
+            let share = damping * rank[i] / s.len() as f64;
+            for j in s.iter() {
+                next[*j] += share;
+            }
+        }
+
+        let diff: f64 = next
+            .iter()
+            .zip(rank.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum();
+        rank = next;
+        if diff < epsilon {
+            break;
+        }
+    }
+
+    for (i, r) in rank.into_iter().enumerate() {
+        ret.insert(adj.nodes[i].clone(), r);
+    }
+    ret
+}
+
+/// Power iteration over the transposed adjacency. The identity is added to
+/// the matrix so the iteration also converges on bipartite graphs.
+fn eigenvector_centrality<T>(
+    adj: &Adjacency<T>,
+    max_iter: usize,
+    tol: f64,
+) -> Result<HashMap<T, f64>, &'static str>
+where
+    T: Ord + Clone + std::hash::Hash,
+{
+    let n = adj.len();
+    let mut ret = HashMap::<T, f64>::new();
+    if n == 0 {
+        return Ok(ret);
+    }
+
+    let mut x = vec![1.0 / n as f64; n];
+    for _ in 0..max_iter {
+        let mut next = x.clone();
+        for (i, s) in adj.succ.iter().enumerate() {
+            for j in s.iter() {
+                next[*j] += x[i];
+            }
+        }
+        let norm = next.iter().map(|v| v * v).sum::<f64>().sqrt();
+        for v in next.iter_mut() {
+            *v /= norm;
+        }
+
+        let diff: f64 = next.iter().zip(x.iter()).map(|(a, b)| (a - b).abs()).sum();
+        x = next;
+        if diff < n as f64 * tol {
+            for (i, v) in x.into_iter().enumerate() {
+                ret.insert(adj.nodes[i].clone(), v);
+            }
+            return Ok(ret);
+        }
+    }
+    Err("Eigenvector centrality did not converge")
+}
+
+fn degree_centrality<T>(adj: &Adjacency<T>, directed: bool) -> HashMap<T, f64>
+where
+    T: Ord + Clone + std::hash::Hash,
+{
+    let mut ret = HashMap::<T, f64>::new();
+    let n = adj.len();
+    let scale = if n > 1 { 1.0 / (n - 1) as f64 } else { 1.0 };
+    for (i, d) in adj.degrees(directed).into_iter().enumerate() {
+        ret.insert(adj.nodes[i].clone(), d as f64 * scale);
+    }
+    ret
+}
+
+fn degree_distribution<T>(adj: &Adjacency<T>, directed: bool) -> BTreeMap<usize, usize>
+where
+    T: Ord + Clone,
+{
+    let mut ret = BTreeMap::<usize, usize>::new();
+    for d in adj.degrees(directed) {
+        *ret.entry(d).or_insert(0) += 1;
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn pagerank_cycle_and_sink() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_node(3);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 1);
+
+        let pr = graph.pagerank(0.85, 100, 1e-10);
+        for n in 1..=3 {
+            assert!((pr[&n] - 1.0 / 3.0).abs() < 1e-9);
+        }
+
+        graph.add_node(4);
+        graph.add_edge(1, 4);
+        graph.add_edge(2, 4);
+        graph.add_edge(3, 4);
+        let pr = graph.pagerank(0.85, 100, 1e-10);
+        let total: f64 = pr.values().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(pr[&4] > pr[&1]);
+    }
+
+    #[test]
+    fn pagerank_multidigraph_parallel_edges() {
+        let mut graph = MultiDiGraph::<&str, i32>::new();
+        graph.add_node("a");
+        graph.add_node("b");
+        graph.add_node("c");
+        graph.add_edge("a", "b", 0);
+        graph.add_edge("a", "b", 1);
+        graph.add_edge("a", "c", 0);
+        graph.add_edge("b", "a", 0);
+        graph.add_edge("c", "a", 0);
+
+        let pr = graph.pagerank(0.85, 100, 1e-10);
+        assert!(pr["b"] > pr["c"]);
+        assert!(graph.pagerank(0.85, 100, 1e-10).len() == 3);
+    }
+
+    #[test]
+    fn degree_centrality_star() {
+        let mut graph = Graph::<i32>::new();
+        for n in 0..5 {
+            graph.add_node(n);
+        }
+        for n in 1..5 {
+            graph.add_edge(0, n);
+        }
+
+        let c = graph.degree_centrality();
+        assert_eq!(c[&0], 1.0);
+        assert_eq!(c[&1], 0.25);
+
+        let d = graph.degree_distribution();
+        assert_eq!(d.into_iter().collect::<Vec<_>>(), vec![(1, 4), (4, 1)]);
+
+        let mut graph = DiGraph::<i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_node(3);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        assert_eq!(graph.degree_centrality()[&2], 1.0);
+        assert_eq!(graph.degree_distribution()[&1], 2);
+    }
+
+    #[test]
+    fn eigenvector_centrality_path() {
+        let mut graph = Graph::<i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_node(3);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+
+        let c = graph.eigenvector_centrality(1000, 1e-9).unwrap();
+        // Leading eigenvector of the path P3 is (1, sqrt(2), 1) / 2
+        assert!((c[&2] - 2f64.sqrt() / 2.0).abs() < 1e-6);
+        assert!((c[&1] - 0.5).abs() < 1e-6);
+        assert!((c[&1] - c[&3]).abs() < 1e-9);
+
+        let mut graph = DiGraph::<i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_node(3);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 1);
+        graph.add_edge(1, 3);
+        let c = graph.eigenvector_centrality(1000, 1e-9).unwrap();
+        assert!(c[&3] > c[&2]);
+        assert!(graph.eigenvector_centrality(1, 1e-12).is_err());
+    }
+}
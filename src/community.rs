@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use crate::adjacency::Adjacency;
+use crate::graph::Graph;
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the communities of the graph found by semi-synchronous label
+    /// propagation (Cordasco and Gargano). Every node starts with its own label
+    /// and adopts the most frequent label among its neighbors until no node
+    /// changes. The result is deterministic.
+    ///
+    /// Communities are ordered by their first node and nodes keep the
+    /// insertion order of the graph
+    pub fn communities(&self) -> Vec<Vec<T>> {
+        let adj = self.adjacency();
+        let labels = label_propagation(&adj);
+
+        let mut groups = BTreeMap::<usize, usize>::new();
+        let mut ret = Vec::<Vec<T>>::new();
+        for (i, l) in labels.iter().enumerate() {
+            let g = *groups.entry(*l).or_insert_with(|| {
+                ret.push(Vec::new());
+                ret.len() - 1
+            });
+            ret[g].push(adj.nodes[i].clone());
+        }
+        ret
+    }
+
+    /// Returns the modularity of the partition `communities` of the graph.
+    /// Values close to `1` mean dense communities with few edges between them.
+    /// Nodes not present in any community are treated as singletons
+    pub fn modularity(&self, communities: &[Vec<T>]) -> f64 {
+        let adj = self.adjacency();
+        let n = adj.len();
+
+        let mut community = vec![usize::MAX; n];
+        for (c, nodes) in communities.iter().enumerate() {
+            for node in nodes.iter() {
+                if let Some(i) = adj.nodes.iter().position(|m| m == node) {
+                    community[i] = c;
+                }
+            }
+        }
+        let mut next = communities.len();
+        for c in community.iter_mut() {
+            if *c == usize::MAX {
+                *c = next;
+                next += 1;
+            }
+        }
+
+        // Self loops count twice in the degree of a node
+        let mut degree = vec![0usize; n];
+        let mut internal = vec![0usize; next];
+        let mut m = 0usize;
+        for (i, s) in adj.succ.iter().enumerate() {
+            for j in s.iter() {
+                if i > *j {
+                    continue;
+                }
+                m += 1;
+                degree[i] += 1;
+                degree[*j] += 1;
+                if community[i] == community[*j] {
+                    internal[community[i]] += 1;
+                }
+            }
+        }
+        if m == 0 {
+            return 0.0;
+        }
+
+        let mut total = vec![0usize; next];
+        for i in 0..n {
+            total[community[i]] += degree[i];
+        }
+        let m = m as f64;
+        internal
+            .iter()
+            .zip(total.iter())
+            .map(|(l, d)| *l as f64 / m - (*d as f64 / (2.0 * m)).powi(2))
+            .sum()
+    }
+}
+
+/// Semi-synchronous label propagation. Nodes are updated one color class of a
+/// greedy coloring at a time, so neighbors never update simultaneously and the
+/// labeling is guaranteed to converge. Returns the label of every node.
+fn label_propagation<T>(adj: &Adjacency<T>) -> Vec<usize>
+where
+    T: Ord + Clone,
+{
+    let n = adj.len();
+    let mut labels: Vec<usize> = (0..n).collect();
+    if n == 0 {
+        return labels;
+    }
+
+    // Greedy coloring, largest degree first
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|a, b| adj.succ[*b].len().cmp(&adj.succ[*a].len()));
+    let mut color = vec![usize::MAX; n];
+    for i in order.iter() {
+        let used: Vec<usize> = adj.succ[*i].iter().map(|j| color[*j]).collect();
+        let mut c = 0;
+        while used.contains(&c) {
+            c += 1;
+        }
+        color[*i] = c;
+    }
+    let colors = color.iter().max().unwrap() + 1;
+    let mut classes = vec![Vec::<usize>::new(); colors];
+    for i in 0..n {
+        classes[color[i]].push(i);
+    }
+
+    loop {
+        for class in classes.iter() {
+            for i in class.iter() {
+                let best = most_frequent_labels(adj, &labels, *i);
+                if !best.is_empty() && !best.contains(&labels[*i]) {
+                    labels[*i] = *best.iter().max().unwrap();
+                }
+            }
+        }
+
+        let complete = (0..n).all(|i| {
+            let best = most_frequent_labels(adj, &labels, i);
+            best.is_empty() || best.contains(&labels[i])
+        });
+        if complete {
+            return labels;
+        }
+    }
+}
+
+/// Returns the labels with the highest frequency among the neighbors of node `i`
+fn most_frequent_labels<T>(adj: &Adjacency<T>, labels: &[usize], i: usize) -> Vec<usize>
+where
+    T: Ord + Clone,
+{
+    let mut freq = BTreeMap::<usize, usize>::new();
+    for j in adj.succ[i].iter() {
+        *freq.entry(labels[*j]).or_insert(0) += 1;
+    }
+    let max = freq.values().copied().max().unwrap_or(0);
+    freq.into_iter()
+        .filter(|(_, f)| *f == max)
+        .map(|(l, _)| l)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn communities_two_triangles() {
+        let mut graph = Graph::<i32>::new();
+        for n in 1..=6 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(1, 3);
+        graph.add_edge(4, 5);
+        graph.add_edge(5, 6);
+        graph.add_edge(4, 6);
+        graph.add_edge(3, 4);
+
+        let c = graph.communities();
+        assert_eq!(c, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        // 2 * (3/7 - (7/14)^2)
+        let q = graph.modularity(&c);
+        assert!((q - 5.0 / 14.0).abs() < 1e-9);
+
+        let all = vec![vec![1, 2, 3, 4, 5, 6]];
+        assert!(graph.modularity(&all).abs() < 1e-9);
+    }
+
+    #[test]
+    fn communities_isolated_nodes() {
+        let mut graph = Graph::<i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        assert_eq!(graph.communities(), vec![vec![1], vec![2]]);
+        assert_eq!(graph.modularity(&graph.communities()), 0.0);
+        assert!(Graph::<i32>::new().communities().is_empty());
+    }
+}
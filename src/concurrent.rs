@@ -0,0 +1,239 @@
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::sync::RwLockReadGuard;
+use std::sync::RwLockWriteGuard;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::LimitedPaths;
+
+/// `ConcurrentDiGraph` is a `DiGraph` behind a read-write lock that can be
+/// shared between threads (e.g. in an `Arc`): any number of readers query it
+/// at the same time while writers are serialized. Its inherent methods only
+/// need `&self`; it also implements `IGraph` and `IDiGraph` so generic code
+/// works with it
+pub struct ConcurrentDiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    graph: RwLock<DiGraph<T>>,
+}
+
+impl<T> ConcurrentDiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        Self::from_digraph(DiGraph::new())
+    }
+
+    /// Returns a concurrent graph holding `graph`
+    pub fn from_digraph(graph: DiGraph<T>) -> Self {
+        ConcurrentDiGraph {
+            graph: RwLock::new(graph),
+        }
+    }
+
+    /// Returns the inner graph
+    pub fn into_digraph(self) -> DiGraph<T> {
+        self.graph
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the graph for reading, to run several queries on the same
+    /// version of the graph. Writers wait until the guard is dropped
+    pub fn read(&self) -> RwLockReadGuard<'_, DiGraph<T>> {
+        self.graph.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the graph for writing, to apply several changes at once
+    pub fn write(&self) -> RwLockWriteGuard<'_, DiGraph<T>> {
+        self.graph.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Adds a new node `elem`
+    pub fn add_node(&self, elem: T) {
+        self.write().add_node(elem);
+    }
+
+    /// Creates a new edge from node `from` to node `to`, both must be
+    /// previously added
+    pub fn add_edge(&self, from: T, to: T) {
+        self.write().add_edge(from, to);
+    }
+
+    /// Returns `true` if node `node` exists
+    pub fn node_exists(&self, node: T) -> bool {
+        self.read().node_exists(node)
+    }
+
+    /// Returns if a node `from` is connected to a node `to`
+    pub fn is_connected(&self, from: T, to: T) -> bool {
+        self.read().is_connected(from, to)
+    }
+
+    /// Returns if node `to` is a neighbor of `from`
+    pub fn is_directly_connected(&self, from: T, to: T) -> bool {
+        self.read().is_directly_connected(from, to)
+    }
+
+    /// Returns a vector containing the `neighbors` of node `from`
+    pub fn get_neighbors(&self, from: T) -> Vec<T> {
+        self.read().get_neighbors(from)
+    }
+
+    /// Returns a vector of the elements
+    pub fn get_nodes(&self) -> Vec<T> {
+        self.read().get_nodes()
+    }
+
+    /// Returns how many nodes are in the graph
+    pub fn count_nodes(&self) -> usize {
+        self.read().count_nodes()
+    }
+
+    /// Returns if the graph doesn't contain nodes
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Returns all the simple paths from node `from` to node `to`
+    pub fn all_simple_paths(&self, from: T, to: T) -> Vec<Vec<T>> {
+        self.read().all_simple_paths(from, to)
+    }
+}
+
+impl<T> Default for ConcurrentDiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> IGraph<T> for ConcurrentDiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn add_node(&mut self, elem: T) {
+        ConcurrentDiGraph::add_node(self, elem);
+    }
+
+    fn node_exists(&self, node: T) -> bool {
+        ConcurrentDiGraph::node_exists(self, node)
+    }
+
+    fn is_connected(&self, from: T, to: T) -> bool {
+        ConcurrentDiGraph::is_connected(self, from, to)
+    }
+
+    fn is_directly_connected(&self, from: T, to: T) -> bool {
+        ConcurrentDiGraph::is_directly_connected(self, from, to)
+    }
+
+    fn to_dot_string(&self, graph_name: &str) -> String {
+        self.read().to_dot_string(graph_name)
+    }
+
+    fn is_empty(&self) -> bool {
+        ConcurrentDiGraph::is_empty(self)
+    }
+
+    fn count_nodes(&self) -> usize {
+        ConcurrentDiGraph::count_nodes(self)
+    }
+
+    fn get_nodes(&self) -> Vec<T> {
+        ConcurrentDiGraph::get_nodes(self)
+    }
+}
+
+impl<T> IDiGraph<T> for ConcurrentDiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn add_edge(&mut self, from: T, to: T) {
+        ConcurrentDiGraph::add_edge(self, from, to);
+    }
+
+    fn all_simple_paths(&self, from: T, to: T) -> Vec<Vec<T>> {
+        ConcurrentDiGraph::all_simple_paths(self, from, to)
+    }
+
+    fn get_neighbors(&self, from: T) -> Vec<T> {
+        ConcurrentDiGraph::get_neighbors(self, from)
+    }
+
+    fn all_simple_paths_limited(
+        &self,
+        from: T,
+        to: T,
+        max_len: usize,
+        limit: usize,
+    ) -> LimitedPaths<T> {
+        self.read()
+            .all_simple_paths_limited(from, to, max_len, limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConcurrentDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::unionfind::DynamicConnectivity;
+    use std::sync::Arc;
+
+    #[test]
+    fn concurrent_digraph() {
+        let graph = Arc::new(ConcurrentDiGraph::<u32>::new());
+        for n in 0..100 {
+            graph.add_node(n);
+        }
+        let writers: Vec<_> = (0..4)
+            .map(|t| {
+                let graph = Arc::clone(&graph);
+                std::thread::spawn(move || {
+                    for n in (t..99).step_by(4) {
+                        graph.add_edge(n, n + 1);
+                    }
+                })
+            })
+            .collect();
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let graph = Arc::clone(&graph);
+                std::thread::spawn(move || (0..100).filter(|n| graph.node_exists(*n)).count())
+            })
+            .collect();
+        for w in writers {
+            w.join().unwrap();
+        }
+        for r in readers {
+            assert_eq!(r.join().unwrap(), 100);
+        }
+        assert!(graph.is_connected(0, 99));
+        assert_eq!(graph.get_neighbors(5), vec![6]);
+        {
+            let guard = graph.read();
+            assert_eq!(guard.count_nodes(), 100);
+            assert!(guard.is_directly_connected(98, 99));
+        }
+
+        let graph = Arc::try_unwrap(graph).ok().unwrap();
+        let dc = DynamicConnectivity::from_graph(&graph);
+        assert_eq!(dc.count_components(), 1);
+        let mut graph = graph;
+        IGraph::add_node(&mut graph, 100);
+        IDiGraph::add_edge(&mut graph, 100, 0);
+        assert_eq!(
+            IDiGraph::all_simple_paths(&graph, 100, 2),
+            vec![vec![100, 0, 1, 2]]
+        );
+        assert_eq!(graph.into_digraph().count_nodes(), 101);
+    }
+}
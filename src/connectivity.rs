@@ -0,0 +1,214 @@
+use std::vec::Vec;
+
+use crate::adjacency::Adjacency;
+use crate::graph::Graph;
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the bridges of the graph: the edges whose removal disconnects
+    /// their endpoints
+    pub fn bridges(&self) -> Vec<(T, T)> {
+        let adj = self.adjacency();
+        let b = Biconnectivity::new(&adj);
+        b.bridges
+            .iter()
+            .map(|(u, v)| (adj.nodes[*u].clone(), adj.nodes[*v].clone()))
+            .collect()
+    }
+
+    /// Returns the articulation points of the graph: the nodes whose removal
+    /// increases the number of connected components
+    pub fn articulation_points(&self) -> Vec<T> {
+        let adj = self.adjacency();
+        let b = Biconnectivity::new(&adj);
+        (0..adj.len())
+            .filter(|i| b.articulation[*i])
+            .map(|i| adj.nodes[i].clone())
+            .collect()
+    }
+
+    /// Returns the biconnected components of the graph: the maximal sets of
+    /// nodes that stay connected after removing any one of them.
+    /// Articulation points belong to several components and isolated nodes
+    /// to none
+    pub fn biconnected_components(&self) -> Vec<Vec<T>> {
+        let adj = self.adjacency();
+        let b = Biconnectivity::new(&adj);
+        b.components
+            .iter()
+            .map(|c| c.iter().map(|i| adj.nodes[*i].clone()).collect())
+            .collect()
+    }
+
+    /// Returns `true` if the graph is connected and has no articulation point,
+    /// so it stays connected after removing any node
+    pub fn is_biconnected(&self) -> bool {
+        let adj = self.adjacency();
+        let b = Biconnectivity::new(&adj);
+        b.components.len() == 1 && b.components[0].len() == adj.len()
+    }
+
+    /// Returns `true` if the graph has at least 3 nodes, is connected and has
+    /// no bridge, so it stays connected after removing any edge
+    pub fn is_two_edge_connected(&self) -> bool {
+        let adj = self.adjacency();
+        adj.len() >= 3
+            && adj.components().len() == 1
+            && Biconnectivity::new(&adj).bridges.is_empty()
+    }
+}
+
+/// Result of Tarjan's lowlink depth first search over an undirected snapshot
+struct Biconnectivity {
+    bridges: Vec<(usize, usize)>,
+    articulation: Vec<bool>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Biconnectivity {
+    /// Iterative version of Tarjan's algorithm, so deep graphs don't overflow the stack
+    fn new<T: Ord + Clone>(adj: &Adjacency<T>) -> Self {
+        let n = adj.len();
+        let mut disc = vec![usize::MAX; n];
+        let mut low = vec![0usize; n];
+        let mut time = 0;
+        let mut ret = Biconnectivity {
+            bridges: Vec::new(),
+            articulation: vec![false; n],
+            components: Vec::new(),
+        };
+        let mut edges = Vec::<(usize, usize)>::new();
+
+        for root in 0..n {
+            if disc[root] != usize::MAX {
+                continue;
+            }
+            disc[root] = time;
+            low[root] = time;
+            time += 1;
+            let mut root_children = 0;
+            // (node, parent, index of the next neighbor to visit)
+            let mut stack = vec![(root, usize::MAX, 0usize)];
+
+            while let Some((u, parent, idx)) = stack.last_mut() {
+                let u = *u;
+                if *idx < adj.succ[u].len() {
+                    let v = adj.succ[u][*idx];
+                    *idx += 1;
+                    if v == u || v == *parent {
+                        continue;
+                    }
+                    if disc[v] == usize::MAX {
+                        disc[v] = time;
+                        low[v] = time;
+                        time += 1;
+                        edges.push((u, v));
+                        if u == root {
+                            root_children += 1;
+                        }
+                        stack.push((v, u, 0));
+                    } else if disc[v] < disc[u] {
+                        low[u] = low[u].min(disc[v]);
+                        edges.push((u, v));
+                    }
+                    continue;
+                }
+
+                stack.pop();
+                if let Some((p, _, _)) = stack.last() {
+                    let p = *p;
+                    low[p] = low[p].min(low[u]);
+                    if low[u] > disc[p] {
+                        ret.bridges.push((p, u));
+                    }
+                    if low[u] >= disc[p] {
+                        if p != root {
+                            ret.articulation[p] = true;
+                        }
+                        let mut component = Vec::<usize>::new();
+                        while let Some((a, b)) = edges.pop() {
+                            component.push(a);
+                            component.push(b);
+                            if (a, b) == (p, u) {
+                                break;
+                            }
+                        }
+                        component.sort_unstable();
+                        component.dedup();
+                        ret.components.push(component);
+                    }
+                }
+            }
+            if root_children > 1 {
+                ret.articulation[root] = true;
+            }
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn bridges_and_articulation_points() {
+        // Two triangles joined by the bridge 3 - 4
+        let mut graph = Graph::<i32>::new();
+        for n in 1..=6 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 1);
+        graph.add_edge(3, 4);
+        graph.add_edge(4, 5);
+        graph.add_edge(5, 6);
+        graph.add_edge(6, 4);
+
+        assert_eq!(graph.bridges(), vec![(3, 4)]);
+        assert_eq!(graph.articulation_points(), vec![3, 4]);
+        let mut components = graph.biconnected_components();
+        components.sort();
+        assert_eq!(components, vec![vec![1, 2, 3], vec![3, 4], vec![4, 5, 6]]);
+        assert!(!graph.is_biconnected());
+        assert!(!graph.is_two_edge_connected());
+
+        graph.add_edge(1, 6);
+        assert!(graph.bridges().is_empty());
+        assert!(graph.is_two_edge_connected());
+        assert!(graph.is_biconnected());
+
+        // Two triangles sharing node 3
+        let mut graph = Graph::<i32>::new();
+        for n in 1..=5 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 1);
+        graph.add_edge(3, 4);
+        graph.add_edge(4, 5);
+        graph.add_edge(5, 3);
+        assert!(graph.is_two_edge_connected());
+        assert!(!graph.is_biconnected());
+        assert_eq!(graph.articulation_points(), vec![3]);
+    }
+
+    #[test]
+    fn biconnected_small_graphs() {
+        let mut graph = Graph::<i32>::new();
+        graph.add_node(1);
+        assert!(!graph.is_biconnected());
+        graph.add_node(2);
+        graph.add_edge(1, 2);
+        assert!(graph.is_biconnected());
+        assert!(!graph.is_two_edge_connected());
+        graph.add_node(3);
+        assert!(!graph.is_biconnected());
+    }
+}
@@ -0,0 +1,339 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::vec::Vec;
+
+use crate::attributes::AttrMap;
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+use crate::multigraph::MultiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+use crate::rugraph::IMultiGraph;
+
+/// How `to_undirected` handles the edges going from `a` to `b` and from `b`
+/// to `a`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Antiparallel {
+    /// An edge in either direction joins the nodes, antiparallel edges are
+    /// merged into one
+    Merge,
+    /// Only nodes joined in both directions are joined
+    Reciprocal,
+}
+
+/// Which directions `to_directed` gives to the edge between `a` and `b`
+pub enum Orientation<'a, T> {
+    /// Both directions
+    Both,
+    /// From the smaller node to the larger one, so the result has no cycles
+    /// other than self loops
+    Ascending,
+    /// From `a` to `b` if `cmp(a, b)` is `Less`, both directions if it is
+    /// `Equal`
+    By(&'a dyn Fn(&T, &T) -> Ordering),
+}
+
+/// Checks that `matrix` has a row and a column per label and the labels are
+/// unique
+fn check_matrix<T: Ord, C>(labels: &[T], matrix: &[Vec<C>]) -> Result<(), &'static str> {
+    if matrix.len() != labels.len() || matrix.iter().any(|row| row.len() != labels.len()) {
+        return Err("Matrix not correct. Expected a row and a column per node.");
+    }
+    let mut seen = BTreeSet::new();
+    if !labels.iter().all(|l| seen.insert(l)) {
+        return Err("Element already exists");
+    }
+    Ok(())
+}
+
+/// Copies into `to` the attributes of `from` it doesn't have yet
+fn merge_attrs(to: &mut AttrMap, from: &AttrMap) {
+    for (k, v) in from.iter() {
+        if !to.contains_key(k) {
+            to.insert(k, v.clone());
+        }
+    }
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns an undirected graph with the same nodes, in the same order,
+    /// joined as `policy` says. The attributes are kept, those of merged
+    /// edges are combined, the edge from the smaller node winning when both
+    /// have the same key
+    pub fn to_undirected(&self, policy: Antiparallel) -> Graph<T> {
+        let mut graph = Graph::new();
+        for n in self.get_nodes() {
+            graph.add_node(n);
+        }
+        for i in 0..self.count_nodes() {
+            for j in self.neighbor_ids(i) {
+                if policy == Antiparallel::Reciprocal && !self.neighbor_ids(*j).contains(&i) {
+                    continue;
+                }
+                let (a, b) = (self.node(i).unwrap(), self.node(*j).unwrap());
+                graph.add_edge(a.clone(), b.clone());
+            }
+        }
+        graph.digraph.attrs.nodes = self.attrs.nodes.clone();
+        // keys are sorted, so the edge from the smaller node comes first
+        for ((from, to), attrs) in self.attrs.edges.iter() {
+            if graph.is_directly_connected(from.clone(), to.clone()) {
+                merge_attrs(graph.edge_attrs_mut(from.clone(), to.clone()), attrs);
+            }
+        }
+        graph
+    }
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the graph with nodes `labels`, in that order, and an edge
+    /// from `labels[i]` to `labels[j]` if `matrix[i][j]`. Fails if the
+    /// matrix is not square with a row per label or a label is repeated
+    pub fn from_adjacency_matrix(
+        labels: Vec<T>,
+        matrix: Vec<Vec<bool>>,
+    ) -> Result<DiGraph<T>, &'static str> {
+        check_matrix(&labels, &matrix)?;
+        let succ = matrix
+            .into_iter()
+            .map(|row| (0..row.len()).filter(|j| row[*j]).collect())
+            .collect();
+        Ok(DiGraph::from_index_lists(labels, succ))
+    }
+
+    /// Returns the adjacency matrix of the graph: `m[i][j]` tells if there
+    /// is an edge from the node with id `i` to the one with id `j`, see
+    /// `index_of`
+    pub fn to_adjacency_matrix(&self) -> Vec<Vec<bool>> {
+        let n = self.count_nodes();
+        (0..n)
+            .map(|i| {
+                let mut row = vec![false; n];
+                for j in self.neighbor_ids(i) {
+                    row[*j] = true;
+                }
+                row
+            })
+            .collect()
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a directed graph with the same nodes, in the same order, and
+    /// an edge for each direction `orientation` gives to every edge. The
+    /// attributes of an edge are copied to each direction
+    pub fn to_directed(&self, orientation: Orientation<T>) -> DiGraph<T> {
+        let mut graph = DiGraph::new();
+        for n in self.get_nodes() {
+            graph.add_node(n);
+        }
+        for i in 0..self.count_nodes() {
+            for j in self.digraph.neighbor_ids(i) {
+                let (a, b) = (
+                    self.digraph.node(i).unwrap(),
+                    self.digraph.node(*j).unwrap(),
+                );
+                let keep = match orientation {
+                    Orientation::Both => true,
+                    Orientation::Ascending => a <= b,
+                    Orientation::By(cmp) => cmp(a, b) != Ordering::Greater,
+                };
+                if keep {
+                    graph.add_edge(a.clone(), b.clone());
+                    if let Some(attrs) = self.edge_attrs(a, b) {
+                        *graph.edge_attrs_mut(a.clone(), b.clone()) = attrs.clone();
+                    }
+                }
+            }
+        }
+        graph.attrs.nodes = self.digraph.attrs.nodes.clone();
+        graph
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns an undirected graph with the same nodes, in the same order,
+    /// and node attributes, joined as `policy` says. Only antiparallel edges
+    /// with the same value are merged, others are kept side by side
+    pub fn to_undirected(&self, policy: Antiparallel) -> MultiGraph<T, E> {
+        let mut graph = MultiGraph::new();
+        for n in self.get_nodes() {
+            graph.add_node(n);
+        }
+        for n in self.get_nodes() {
+            for (m, e) in self.get_neighbors(n.clone()) {
+                if policy == Antiparallel::Reciprocal
+                    && !self.is_directly_connected_by(m.clone(), n.clone(), e.clone())
+                {
+                    continue;
+                }
+                graph.add_edge(n.clone(), m, e);
+            }
+        }
+        graph.multidigraph.attrs.nodes = self.attrs.nodes.clone();
+        graph
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the graph with nodes `labels`, in that order, and an edge
+    /// from `labels[i]` to `labels[j]` labeled `e` if `matrix[i][j]` is
+    /// `Some(e)`, e.g. a weight. Fails if the matrix is not square with a
+    /// row per label or a label is repeated
+    pub fn from_adjacency_matrix(
+        labels: Vec<T>,
+        matrix: Vec<Vec<Option<E>>>,
+    ) -> Result<MultiDiGraph<T, E>, &'static str> {
+        check_matrix(&labels, &matrix)?;
+        let mut graph = MultiDiGraph::new();
+        for l in labels.iter() {
+            graph.add_node(l.clone());
+        }
+        for (i, row) in matrix.into_iter().enumerate() {
+            for (j, e) in row.into_iter().enumerate() {
+                if let Some(e) = e {
+                    graph.add_edge(labels[i].clone(), labels[j].clone(), e);
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Returns the adjacency matrix of the graph, rows and columns in the
+    /// order of `get_nodes`: `m[i][j]` is the value of the edge between the
+    /// `i`-th and the `j`-th node, `None` if there is none. Fails if two
+    /// nodes are joined by parallel edges
+    pub fn to_adjacency_matrix(&self) -> Result<Vec<Vec<Option<E>>>, &'static str> {
+        let nodes = self.get_nodes();
+        let pos: BTreeMap<&T, usize> = nodes.iter().zip(0..).collect();
+        let mut matrix = vec![vec![None; nodes.len()]; nodes.len()];
+        for (i, n) in nodes.iter().enumerate() {
+            for (m, e) in self.get_neighbors(n.clone()) {
+                let cell = &mut matrix[i][pos[&m]];
+                if cell.is_some() {
+                    return Err("Parallel edges found");
+                }
+                *cell = Some(e);
+            }
+        }
+        Ok(matrix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Antiparallel;
+    use super::Orientation;
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+    use crate::rugraph::IMultiGraph;
+
+    #[test]
+    fn to_undirected() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 1), (2, 3), (4, 3), (4, 4)]);
+        graph.node_attrs_mut(4).insert("color", "red");
+        graph.edge_attrs_mut(1, 2).insert("w", 1);
+        graph.edge_attrs_mut(2, 1).insert("w", 2);
+        graph.edge_attrs_mut(2, 1).insert("label", "back");
+
+        let undirected = graph.to_undirected(Antiparallel::Merge);
+        assert_eq!(undirected.get_nodes(), vec![1, 2, 3, 4]);
+        assert_eq!(undirected.get_neighbors(3), vec![2, 4]);
+        assert!(undirected.is_directly_connected(4, 4));
+        let attrs = undirected.edge_attrs(&2, &1).unwrap();
+        assert_eq!(attrs.get_int("w"), Some(1));
+        assert_eq!(attrs.get_str("label"), Some("back"));
+        assert!(undirected.node_attrs(&4).is_some());
+
+        let reciprocal = graph.to_undirected(Antiparallel::Reciprocal);
+        assert_eq!(reciprocal.get_neighbors(1), vec![2]);
+        assert!(reciprocal.get_neighbors(3).is_empty());
+        assert!(reciprocal.is_directly_connected(4, 4));
+
+        let mut graph = MultiDiGraph::<i32, char>::new();
+        graph.extend_with_edges([(1, 2, 'a'), (2, 1, 'a'), (2, 1, 'b')]);
+        let undirected = graph.to_undirected(Antiparallel::Merge);
+        assert_eq!(undirected.get_neighbors(1), vec![(2, 'a'), (2, 'b')]);
+        let reciprocal = graph.to_undirected(Antiparallel::Reciprocal);
+        assert_eq!(reciprocal.get_neighbors(1), vec![(2, 'a')]);
+        assert!(!reciprocal.is_directly_connected_by(1, 2, 'b'));
+    }
+
+    #[test]
+    fn to_directed() {
+        let mut graph = Graph::<i32>::new();
+        graph.extend_with_edges([(2, 1), (2, 3), (3, 3)]);
+        graph.edge_attrs_mut(1, 2).insert("w", 4);
+
+        let both = graph.to_directed(Orientation::Both);
+        assert_eq!(both.get_nodes(), vec![2, 1, 3]);
+        assert_eq!(both.get_neighbors(2), vec![1, 3]);
+        assert_eq!(both.get_neighbors(1), vec![2]);
+        assert_eq!(both.edge_attrs(&2, &1).unwrap().get_int("w"), Some(4));
+        assert_eq!(both.edge_attrs(&1, &2).unwrap().get_int("w"), Some(4));
+
+        let dag = graph.to_directed(Orientation::Ascending);
+        assert_eq!(dag.get_neighbors(1), vec![2]);
+        assert_eq!(dag.get_neighbors(2), vec![3]);
+        assert_eq!(dag.get_neighbors(3), vec![3]);
+        assert!(dag.edge_attrs(&2, &1).is_none());
+
+        let descending = |a: &i32, b: &i32| b.cmp(a);
+        let down = graph.to_directed(Orientation::By(&descending));
+        assert_eq!(down.get_neighbors(2), vec![1]);
+        assert_eq!(down.get_neighbors(3), vec![2, 3]);
+        let undirected = down.to_undirected(Antiparallel::Merge);
+        assert_eq!(undirected.get_neighbors(2), graph.get_neighbors(2));
+    }
+
+    #[test]
+    fn adjacency_matrices() {
+        let matrix = vec![
+            vec![false, true, true],
+            vec![false, false, true],
+            vec![true, false, false],
+        ];
+        let graph = DiGraph::from_adjacency_matrix(vec!["a", "b", "c"], matrix.clone()).unwrap();
+        assert_eq!(graph.get_nodes(), vec!["a", "b", "c"]);
+        assert_eq!(graph.get_neighbors("a"), vec!["b", "c"]);
+        assert_eq!(graph.get_neighbors("c"), vec!["a"]);
+        assert_eq!(graph.to_adjacency_matrix(), matrix);
+        assert!(DiGraph::from_adjacency_matrix(vec![1, 2], vec![vec![true; 2]]).is_err());
+        assert_eq!(
+            DiGraph::from_adjacency_matrix(vec![1, 1], vec![vec![false; 2]; 2]).err(),
+            Some("Element already exists")
+        );
+
+        let weights = vec![vec![None, Some(5)], vec![Some(2), Some(1)]];
+        let mut graph = MultiDiGraph::from_adjacency_matrix(vec![10, 20], weights.clone()).unwrap();
+        assert_eq!(graph.get_neighbors(20), vec![(10, 2), (20, 1)]);
+        assert_eq!(graph.to_adjacency_matrix(), Ok(weights));
+        graph.add_edge(10, 20, 7);
+        assert_eq!(graph.to_adjacency_matrix(), Err("Parallel edges found"));
+    }
+}
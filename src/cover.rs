@@ -0,0 +1,136 @@
+use std::vec::Vec;
+
+use crate::adjacency::Adjacency;
+use crate::graph::Graph;
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a vertex cover (every edge has at least one endpoint in the
+    /// result) at most twice as large as the minimum one. Both endpoints of a
+    /// greedy maximal matching are taken
+    pub fn vertex_cover(&self) -> Vec<T> {
+        let adj = self.adjacency();
+        let mut cover = vec![false; adj.len()];
+        for (i, s) in adj.succ.iter().enumerate() {
+            for j in s.iter() {
+                if !cover[i] && !cover[*j] {
+                    cover[i] = true;
+                    cover[*j] = true;
+                }
+            }
+        }
+        selected(&adj, &cover)
+    }
+
+    /// Returns a minimum vertex cover. The search is exponential in the size of
+    /// the cover so it is only suitable for small graphs, use `vertex_cover`
+    /// for an approximation otherwise
+    pub fn min_vertex_cover(&self) -> Vec<T> {
+        let adj = self.adjacency();
+        let mut best = vec![true; adj.len()];
+        let mut best_size = adj.len();
+        let mut cover = vec![false; adj.len()];
+        branch(&adj, &mut cover, 0, &mut best, &mut best_size);
+        selected(&adj, &best)
+    }
+}
+
+/// Picks an uncovered edge and tries both of its endpoints
+fn branch<T>(
+    adj: &Adjacency<T>,
+    cover: &mut [bool],
+    size: usize,
+    best: &mut Vec<bool>,
+    best_size: &mut usize,
+) where
+    T: Ord + Clone,
+{
+    if size >= *best_size {
+        return;
+    }
+
+    let mut uncovered = None;
+    for (i, s) in adj.succ.iter().enumerate() {
+        if let Some(j) = s.iter().find(|j| !cover[i] && !cover[**j]) {
+            uncovered = Some((i, *j));
+            break;
+        }
+    }
+
+    match uncovered {
+        None => {
+            *best = cover.to_vec();
+            *best_size = size;
+        }
+        Some((i, j)) => {
+            for k in [i, j] {
+                cover[k] = true;
+                branch(adj, cover, size + 1, best, best_size);
+                cover[k] = false;
+                if i == j {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn selected<T>(adj: &Adjacency<T>, cover: &[bool]) -> Vec<T>
+where
+    T: Ord + Clone,
+{
+    adj.nodes
+        .iter()
+        .zip(cover.iter())
+        .filter(|(_, c)| **c)
+        .map(|(n, _)| n.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    fn is_cover(graph: &Graph<i32>, cover: &[i32]) -> bool {
+        graph.get_nodes().iter().all(|n| {
+            graph
+                .get_neighbors(*n)
+                .iter()
+                .all(|m| cover.contains(n) || cover.contains(m))
+        })
+    }
+
+    #[test]
+    fn vertex_cover_star_and_path() {
+        let mut graph = Graph::<i32>::new();
+        for n in 0..6 {
+            graph.add_node(n);
+        }
+        for n in 1..5 {
+            graph.add_edge(0, n);
+        }
+        graph.add_edge(4, 5);
+        graph.add_edge(5, 5);
+
+        let approx = graph.vertex_cover();
+        assert!(is_cover(&graph, &approx));
+
+        let exact = graph.min_vertex_cover();
+        assert!(is_cover(&graph, &exact));
+        assert_eq!(exact, vec![0, 5]);
+        assert!(approx.len() <= 2 * exact.len());
+    }
+
+    #[test]
+    fn vertex_cover_empty() {
+        let mut graph = Graph::<i32>::new();
+        assert!(graph.min_vertex_cover().is_empty());
+        graph.add_node(1);
+        assert!(graph.vertex_cover().is_empty());
+        assert!(graph.min_vertex_cover().is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+
+/// Read-only directed graph in compressed sparse row layout: the successors
+/// of node `i` are the contiguous slice `targets[offsets[i]..offsets[i + 1]]`.
+/// Nodes are addressed by their index, in the insertion order of the graph
+/// it was built from
+pub struct CsrGraph<T>
+where
+    T: Ord + Clone,
+{
+    nodes: Vec<T>,
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    index: BTreeMap<T, usize>,
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a frozen copy of the graph, faster for read-only analytics
+    pub fn freeze(&self) -> CsrGraph<T> {
+        let adj = self.adjacency();
+        let mut offsets = Vec::<usize>::with_capacity(adj.len() + 1);
+        let mut targets = Vec::<usize>::new();
+        offsets.push(0);
+        for s in adj.succ.iter() {
+            targets.extend_from_slice(s);
+            offsets.push(targets.len());
+        }
+        let index = adj
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.clone(), i))
+            .collect();
+        CsrGraph {
+            nodes: adj.nodes,
+            offsets,
+            targets,
+            index,
+        }
+    }
+}
+
+impl<T> CsrGraph<T>
+where
+    T: Ord + Clone,
+{
+    /// Returns how many nodes are in the graph
+    pub fn count_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns how many edges are in the graph
+    pub fn count_edges(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Returns the nodes, indexed by node index
+    pub fn nodes(&self) -> &[T] {
+        &self.nodes
+    }
+
+    /// Returns the value of node `i`
+    pub fn node(&self, i: usize) -> &T {
+        &self.nodes[i]
+    }
+
+    /// Returns the index of node `node`
+    pub fn index_of(&self, node: &T) -> Option<usize> {
+        self.index.get(node).copied()
+    }
+
+    /// Returns the indexes of the successors of node `i`
+    pub fn neighbors(&self, i: usize) -> &[usize] {
+        &self.targets[self.offsets[i]..self.offsets[i + 1]]
+    }
+
+    /// Returns the number of successors of node `i`
+    pub fn out_degree(&self, i: usize) -> usize {
+        self.offsets[i + 1] - self.offsets[i]
+    }
+
+    /// Returns the number of hops from node `from` to every node, `None` if
+    /// the node is not reachable
+    pub fn bfs_distances(&self, from: usize) -> Vec<Option<usize>> {
+        let mut dist = vec![None; self.count_nodes()];
+        dist[from] = Some(0);
+        let mut queue = VecDeque::from([from]);
+        while let Some(i) = queue.pop_front() {
+            let d = dist[i].unwrap() + 1;
+            for j in self.neighbors(i) {
+                if dist[*j].is_none() {
+                    dist[*j] = Some(d);
+                    queue.push_back(*j);
+                }
+            }
+        }
+        dist
+    }
+
+    /// Returns the PageRank of every node, indexed by node index. Same
+    /// parameters and results as `DiGraph::pagerank`
+    pub fn pagerank(&self, damping: f64, max_iter: usize, epsilon: f64) -> Vec<f64> {
+        let n = self.count_nodes();
+        if n == 0 {
+            return Vec::new();
+        }
+        let nf = n as f64;
+        let mut rank = vec![1.0 / nf; n];
+        let mut next = vec![0.0; n];
+        for _ in 0..max_iter {
+            let dangling: f64 = (0..n)
+                .filter(|i| self.out_degree(*i) == 0)
+                .map(|i| rank[i])
+                .sum();
+            let base = (1.0 - damping) / nf + damping * dangling / nf;
+            next.fill(base);
+            for (i, r) in rank.iter().enumerate() {
+                let s = self.neighbors(i);
+                if s.is_empty() {
+                    continue;
+                }
+                let share = damping * r / s.len() as f64;
+                for j in s {
+                    next[*j] += share;
+                }
+            }
+            let diff: f64 = next
+                .iter()
+                .zip(rank.iter())
+                .map(|(a, b)| (a - b).abs())
+                .sum();
+            std::mem::swap(&mut rank, &mut next);
+            if diff < epsilon {
+                break;
+            }
+        }
+        rank
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn csr_matches_digraph() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 0..6 {
+            graph.add_node(n * 10);
+        }
+        for (a, b) in [(0, 1), (0, 2), (1, 2), (2, 0), (3, 2), (4, 4)] {
+            graph.add_edge(a * 10, b * 10);
+        }
+        let csr = graph.freeze();
+        assert_eq!(csr.count_nodes(), 6);
+        assert_eq!(csr.count_edges(), 6);
+        assert_eq!(csr.index_of(&30), Some(3));
+        assert_eq!(csr.index_of(&35), None);
+        assert_eq!(*csr.node(2), 20);
+        assert_eq!(csr.neighbors(0), &[1, 2]);
+        assert!(csr.neighbors(5).is_empty());
+        assert_eq!(
+            csr.bfs_distances(3),
+            vec![Some(2), Some(3), Some(1), Some(0), None, None]
+        );
+
+        let expected = graph.pagerank(0.85, 100, 1e-12);
+        let rank = csr.pagerank(0.85, 100, 1e-12);
+        for (i, r) in rank.iter().enumerate() {
+            assert!((r - expected[csr.node(i)]).abs() < 1e-9);
+        }
+    }
+}
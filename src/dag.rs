@@ -0,0 +1,112 @@
+use std::vec::Vec;
+
+use crate::adjacency::Adjacency;
+use crate::digraph::DiGraph;
+use crate::multidigraph::MultiDiGraph;
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Groups the nodes of a DAG in layers: the first layer contains the nodes
+    /// without incoming edges and every other layer the nodes whose
+    /// predecessors are all in earlier layers. Nodes of the same layer don't
+    /// depend on each other. Returns an error if the graph has a cycle
+    pub fn topological_generations(&self) -> Result<Vec<Vec<T>>, &'static str> {
+        topological_generations(&self.adjacency())
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Groups the nodes of a DAG in layers: the first layer contains the nodes
+    /// without incoming edges and every other layer the nodes whose
+    /// predecessors are all in earlier layers. Nodes of the same layer don't
+    /// depend on each other. Returns an error if the graph has a cycle
+    pub fn topological_generations(&self) -> Result<Vec<Vec<T>>, &'static str> {
+        topological_generations(&self.adjacency())
+    }
+}
+
+fn topological_generations<T>(adj: &Adjacency<T>) -> Result<Vec<Vec<T>>, &'static str>
+where
+    T: Ord + Clone,
+{
+    let n = adj.len();
+    let mut in_degree = vec![0usize; n];
+    for s in adj.succ.iter() {
+        for j in s.iter() {
+            in_degree[*j] += 1;
+        }
+    }
+
+    let mut ret = Vec::<Vec<T>>::new();
+    let mut current: Vec<usize> = (0..n).filter(|i| in_degree[*i] == 0).collect();
+    let mut processed = 0;
+    while !current.is_empty() {
+        processed += current.len();
+        let mut next = Vec::<usize>::new();
+        for i in current.iter() {
+            for j in adj.succ[*i].iter() {
+                in_degree[*j] -= 1;
+                if in_degree[*j] == 0 {
+                    next.push(*j);
+                }
+            }
+        }
+        next.sort_unstable();
+        ret.push(current.iter().map(|i| adj.nodes[*i].clone()).collect());
+        current = next;
+    }
+
+    if processed != n {
+        return Err("Graph contains a cycle");
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn generations_build_layers() {
+        let mut graph = DiGraph::<&str>::new();
+        for n in ["app", "lib", "core", "util", "test"] {
+            graph.add_node(n);
+        }
+        graph.add_edge("core", "lib");
+        graph.add_edge("util", "lib");
+        graph.add_edge("lib", "app");
+        graph.add_edge("core", "app");
+        graph.add_edge("app", "test");
+
+        let layers = graph.topological_generations().unwrap();
+        assert_eq!(
+            layers,
+            vec![vec!["core", "util"], vec!["lib"], vec!["app"], vec!["test"]]
+        );
+
+        graph.add_edge("test", "core");
+        assert!(graph.topological_generations().is_err());
+    }
+
+    #[test]
+    fn generations_parallel_edges() {
+        let mut graph = MultiDiGraph::<i32, i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_node(3);
+        graph.add_edge(1, 2, 0);
+        graph.add_edge(1, 2, 1);
+        let layers = graph.topological_generations().unwrap();
+        assert_eq!(layers, vec![vec![1, 3], vec![2]]);
+    }
+}
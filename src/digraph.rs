@@ -1,9 +1,23 @@
+use crate::attributes::Attributes;
+use crate::dot::parse_dot_reader;
+use crate::dot::DotStatement;
+use crate::dot::DotStyle;
+use crate::memory::btree_map_bytes;
+use crate::memory::MemoryReport;
+use crate::observe::GraphEvent;
+use crate::observe::Observers;
+use crate::reachability::ReachabilityCache;
 use crate::rugraph::IDiGraph;
 use crate::rugraph::IGraph;
-use std::cell::RefCell;
-use std::fs::File;
-use std::io::Write;
-use std::rc::Rc;
+use crate::rugraph::LimitedPaths;
+use crate::rugraph::PathBounds;
+use crate::snapshot::SnapshotCache;
+use crate::visit::VisitMap;
+use smallvec::SmallVec;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::sync::Mutex;
+use std::sync::PoisonError;
 use std::vec::Vec;
 
 /// `DiGraph` is actually a `generic` directed graph where each node of type `T`
@@ -12,26 +26,38 @@ pub struct DiGraph<T>
 where
     T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
 {
-    /// Nodes are stored in the heap
-    nodes: RefCell<Vec<Rc<Node<T>>>>,
+    /// Nodes are stored in an arena, edges refer to them by their index
+    nodes: Vec<Node<T>>,
+    /// Position of every node in the arena
+    index: BTreeMap<T, usize>,
+    /// Capacity of the neighbor list of new nodes
+    neighbors_capacity: usize,
+    pub(crate) attrs: Attributes<T, (T, T)>,
+    pub(crate) snapshot: Mutex<SnapshotCache<T>>,
+    pub(crate) reachability: ReachabilityCache,
+    pub(crate) observers: Observers<T, ()>,
 }
-/// A `Node` is represented as a generic `T` and a list of pointers to their neighbors (allocated in the heap)
+/// Neighbor list of a node, most nodes have only a few so they are stored
+/// inline without a heap allocation
+type Neighbors = SmallVec<[usize; 4]>;
+
+/// A `Node` is represented as a generic `T` and the indexes of their neighbors in the arena
 struct Node<T>
 where
     T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
 {
     elem: T,
-    neighbors: RefCell<Vec<Rc<Node<T>>>>,
+    neighbors: Neighbors,
 }
 
 impl<T> Node<T>
 where
     T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
 {
-    pub fn new(elem: T) -> Self {
+    pub fn new(elem: T, capacity: usize) -> Self {
         Node::<T> {
-            elem: elem,
-            neighbors: RefCell::new(Vec::new()),
+            elem,
+            neighbors: Neighbors::with_capacity(capacity),
         }
     }
 }
@@ -42,53 +68,334 @@ where
 {
     pub fn new() -> Self {
         DiGraph::<T> {
-            nodes: RefCell::new(vec![]),
+            nodes: vec![],
+            index: BTreeMap::new(),
+            neighbors_capacity: 0,
+            attrs: Attributes::new(),
+            snapshot: Mutex::new(SnapshotCache::new()),
+            reachability: ReachabilityCache::new(),
+            observers: Observers::new(),
+        }
+    }
+
+    /// Returns an empty graph with room for `nodes` nodes and `edges` edges,
+    /// spread evenly among the neighbor lists, so bulk loading doesn't
+    /// reallocate
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self {
+        let mut graph = DiGraph::<T>::new();
+        graph.nodes.reserve(nodes);
+        graph.neighbors_capacity = edges.div_ceil(nodes.max(1));
+        graph
+    }
+
+    /// Returns a graph with nodes `nodes` where the neighbors of `nodes[i]`
+    /// are the nodes at positions `succ[i]`. Nodes must be unique and
+    /// neighbor lists must not have repeated positions
+    pub(crate) fn from_index_lists(nodes: Vec<T>, succ: Vec<Vec<usize>>) -> Self {
+        let mut graph = DiGraph::<T>::new();
+        graph.index = nodes.iter().cloned().zip(0..).collect();
+        graph.nodes = nodes
+            .into_iter()
+            .zip(succ)
+            .map(|(elem, neighbors)| Node::<T> {
+                elem,
+                neighbors: Neighbors::from_vec(neighbors),
+            })
+            .collect();
+        graph
+    }
+
+    /// Reserves room for at least `additional` more nodes
+    pub fn reserve(&mut self, additional: usize) {
+        self.nodes.reserve(additional);
+    }
+
+    /// Returns how many nodes the graph can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    /// Returns an estimate of the memory used by the graph
+    pub fn memory_usage(&self) -> MemoryReport {
+        MemoryReport {
+            nodes: self.nodes.capacity() * std::mem::size_of::<Node<T>>(),
+            adjacency: self
+                .nodes
+                .iter()
+                .filter(|n| n.neighbors.spilled())
+                .map(|n| n.neighbors.capacity() * std::mem::size_of::<usize>())
+                .sum(),
+            payloads: btree_map_bytes::<T, usize>(self.index.len()),
         }
     }
 
+    /// Returns a new graph with the same nodes and every edge flipped, so the
+    /// successors of a node become its predecessors. Attributes are kept
+    pub fn reversed(&self) -> DiGraph<T> {
+        let mut graph = DiGraph::<T>::new();
+        let nodes = self.get_nodes();
+        for n in nodes.iter() {
+            graph.add_node(n.clone());
+        }
+        for n in nodes.iter() {
+            for m in self.get_neighbors(n.clone()) {
+                graph.add_edge(m, n.clone());
+            }
+        }
+        graph.attrs.nodes = self.attrs.nodes.clone();
+        for ((from, to), a) in self.attrs.edges.iter() {
+            graph.attrs.edges.insert((to.clone(), from.clone()), a.clone());
+        }
+        graph
+    }
+
+    /// Flips every edge in place, keeping the attributes: the same as
+    /// `reversed` without copying the graph
+    pub fn reverse(&mut self) {
+        self.notify_edges(|from, to| GraphEvent::RemoveEdge(from, to, ()));
+        let mut pred: Vec<Neighbors> =
+            (0..self.nodes.len()).map(|_| Neighbors::new()).collect();
+        for i in 0..self.nodes.len() {
+            for j in std::mem::take(&mut self.nodes[i].neighbors) {
+                pred[j].push(i);
+            }
+        }
+        for (n, p) in self.nodes.iter_mut().zip(pred) {
+            n.neighbors = p;
+        }
+        self.clear_caches();
+        let edges = std::mem::take(&mut self.attrs.edges);
+        self.attrs.edges = edges
+            .into_iter()
+            .map(|((from, to), a)| ((to, from), a))
+            .collect();
+        self.notify_edges(|from, to| GraphEvent::AddEdge(from, to, ()));
+    }
+
+    /// Removes, with their attributes, the edges for which
+    /// `keep(from, to)` returns `false`, in a single pass over the graph
+    pub fn retain_edges(&mut self, mut keep: impl FnMut(&T, &T) -> bool) {
+        let mut removed = Vec::new();
+        for i in 0..self.nodes.len() {
+            let mut neighbors = std::mem::take(&mut self.nodes[i].neighbors);
+            neighbors.retain(|j| {
+                let kept = keep(&self.nodes[i].elem, &self.nodes[*j].elem);
+                if !kept {
+                    removed.push((i, *j));
+                }
+                kept
+            });
+            self.nodes[i].neighbors = neighbors;
+        }
+        if removed.is_empty() {
+            return;
+        }
+        self.clear_caches();
+        for (i, j) in removed {
+            let (from, to) = (self.nodes[i].elem.clone(), self.nodes[j].elem.clone());
+            self.attrs.edges.remove(&(from.clone(), to.clone()));
+            self.observers.notify(|| GraphEvent::RemoveEdge(from, to, ()));
+        }
+    }
+
+    /// Splits node `node` into `into_in`, which gets its incoming edges, and
+    /// `into_out`, which gets its outgoing ones, joined by an edge from
+    /// `into_in` to `into_out`, e.g. to turn node capacities into edge
+    /// capacities. A self loop becomes an edge from `into_out` to `into_in`.
+    /// `into_in` takes the place and the attributes of `node`, which it may
+    /// be equal to, and `into_out` is added last. Returns an error if `node`
+    /// doesn't exist or a new node already does
+    pub fn split_node(&mut self, node: T, into_in: T, into_out: T) -> Result<(), &'static str> {
+        let idx = self.get_index_by_node_id(node.clone())?;
+        if into_in == into_out
+            || (into_in != node && self.node_exists(into_in.clone()))
+            || (into_out != node && self.node_exists(into_out.clone()))
+        {
+            return Err("Element already exists");
+        }
+        let incoming: Vec<usize> = (0..self.nodes.len())
+            .filter(|k| *k != idx && self.nodes[*k].neighbors.contains(&idx))
+            .collect();
+        for j in self.nodes[idx].neighbors.iter() {
+            self.observers.notify(|| {
+                GraphEvent::RemoveEdge(node.clone(), self.nodes[*j].elem.clone(), ())
+            });
+        }
+        for k in incoming.iter() {
+            self.observers.notify(|| {
+                GraphEvent::RemoveEdge(self.nodes[*k].elem.clone(), node.clone(), ())
+            });
+        }
+        self.observers.notify(|| GraphEvent::RemoveNode(node.clone()));
+
+        self.clear_caches();
+        let out = self.nodes.len();
+        let neighbors = std::mem::take(&mut self.nodes[idx].neighbors);
+        self.nodes[idx].neighbors.push(out);
+        self.nodes[idx].elem = into_in.clone();
+        self.nodes.push(Node::<T> {
+            elem: into_out.clone(),
+            neighbors,
+        });
+        self.index.remove(&node);
+        self.index.insert(into_in.clone(), idx);
+        self.index.insert(into_out.clone(), out);
+
+        if let Some(a) = self.attrs.nodes.remove(&node) {
+            self.attrs.nodes.insert(into_in.clone(), a);
+        }
+        let moved: Vec<(T, T)> = self
+            .attrs
+            .edges
+            .keys()
+            .filter(|(from, to)| *from == node || *to == node)
+            .cloned()
+            .collect();
+        for (from, to) in moved {
+            let a = self.attrs.edges.remove(&(from.clone(), to.clone())).unwrap();
+            let from = if from == node { into_out.clone() } else { from };
+            let to = if to == node { into_in.clone() } else { to };
+            self.attrs.edges.insert((from, to), a);
+        }
+
+        self.observers.notify(|| GraphEvent::AddNode(into_in.clone()));
+        self.observers.notify(|| GraphEvent::AddNode(into_out.clone()));
+        self.observers
+            .notify(|| GraphEvent::AddEdge(into_in.clone(), into_out.clone(), ()));
+        for j in self.nodes[out].neighbors.iter() {
+            self.observers.notify(|| {
+                GraphEvent::AddEdge(into_out.clone(), self.nodes[*j].elem.clone(), ())
+            });
+        }
+        for k in incoming {
+            self.observers.notify(|| {
+                GraphEvent::AddEdge(self.nodes[k].elem.clone(), into_in.clone(), ())
+            });
+        }
+        Ok(())
+    }
+
+    /// Same as `is_connected` but marking the nodes in `visited`, which is
+    /// reset first, so the same map can be reused between queries
+    pub fn is_connected_with(&self, from: T, to: T, visited: &mut VisitMap) -> bool {
+        let (Ok(idx_from), Ok(idx_to)) = (
+            self.get_index_by_node_id(from),
+            self.get_index_by_node_id(to),
+        ) else {
+            return false;
+        };
+        visited.reset(self.nodes.len());
+        visited.visit(idx_from);
+        let mut to_process = vec![idx_from];
+
+        while let Some(i) = to_process.pop() {
+            for n in self.nodes[i].neighbors.iter() {
+                if *n == idx_to {
+                    return true;
+                }
+                if visited.visit(*n) {
+                    to_process.push(*n);
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Returns how many neighbors the node at position `idx` has
+    pub(crate) fn out_degree_at(&self, idx: usize) -> usize {
+        self.nodes[idx].neighbors.len()
+    }
+
+    /// Returns the id of node `node`: its position in insertion order, which
+    /// never changes. `None` if it is not a node of the graph
+    pub fn index_of(&self, node: &T) -> Option<usize> {
+        self.index.get(node).copied()
+    }
+
+    /// Returns the value of the node with id `id`
+    pub fn node(&self, id: usize) -> Option<&T> {
+        self.nodes.get(id).map(|n| &n.elem)
+    }
+
+    /// Returns the ids of the neighbors of the node with id `id` without
+    /// copying them, for tight loops that can't afford `get_neighbors`.
+    /// Panics if `id` is not a node id
+    pub fn neighbor_ids(&self, id: usize) -> &[usize] {
+        &self.nodes[id].neighbors
+    }
+
+    /// Notifies the observers of `event(from, to)` for every edge
+    fn notify_edges(&self, event: impl Fn(T, T) -> GraphEvent<T, ()>) {
+        if self.observers.is_empty() {
+            return;
+        }
+        for n in self.nodes.iter() {
+            for j in n.neighbors.iter() {
+                self.observers
+                    .notify(|| event(n.elem.clone(), self.nodes[*j].elem.clone()));
+            }
+        }
+    }
+
+    /// Marks the last snapshot as outdated and, if an edge was added or
+    /// removed, forgets the memoized reachability
+    fn invalidate_caches(&mut self, new_edge: bool) {
+        self.snapshot
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .invalidate();
+        if new_edge {
+            self.reachability.invalidate();
+        }
+    }
+
+    /// Forgets the last snapshot and the memoized reachability, called when
+    /// existing edges are removed or moved
+    fn clear_caches(&mut self) {
+        self.snapshot
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        self.reachability.invalidate();
+    }
+
     fn get_index_by_node_id(&self, from: T) -> Result<usize, &'static str> {
-        let nodes = self.nodes.borrow();
-        let idx_from = nodes.iter().position(|r| r.elem == from);
-        match idx_from {
+        match self.index.get(&from).copied() {
             None => Err("Element not found"),
             Some(value) => Ok(value),
         }
     }
 
-    /// Deep first search. Helper function to get all the simple paths
+    /// Deep first search from the node at position `from`. Helper function
+    /// to get all the simple paths within `bounds`, `visited` marks the
+    /// nodes in the path
     fn dfs(
         &self,
-        from: T,
-        to: T,
+        from: usize,
+        to: usize,
+        bounds: &PathBounds,
         simple_path: &mut Vec<Vec<T>>,
         current_path: &mut Vec<T>,
-        visited: &mut Vec<T>,
+        visited: &mut VisitMap,
     ) {
-        if visited.contains(&from) {
+        if visited.is_visited(from) {
             return;
         }
-        visited.push(from.clone());
-        current_path.push(from.clone());
+        current_path.push(self.nodes[from].elem.clone());
         if from == to {
             simple_path.push(current_path.clone());
-            if visited.contains(&from) {
-                let index = visited.iter().position(|x| *x == from).unwrap();
-                visited.remove(index);
-                current_path.pop();
-                return;
+        } else if current_path.len() <= bounds.max_len {
+            visited.visit(from);
+            for n in self.nodes[from].neighbors.iter() {
+                if bounds.done(simple_path) {
+                    break;
+                }
+                self.dfs(*n, to, bounds, simple_path, current_path, visited);
             }
+            visited.unvisit(from);
         }
-
-        let neighbors = self.get_neighbors(from.clone());
-        for n in neighbors.iter() {
-            self.dfs(n.clone(), to.clone(), simple_path, current_path, visited);
-        }
-
         current_path.pop();
-        if visited.contains(&from) {
-            let index = visited.iter().position(|x| *x == from).unwrap();
-            visited.remove(index);
-        }
     }
 }
 
@@ -101,60 +408,30 @@ where
             return;
         }
 
-        let mut nodes = self.nodes.borrow_mut();
-        let n = Rc::new(Node::<T>::new(elem));
-
-        //println!("Adding new node {}", n.elem);
-
-        nodes.push(n);
-        //println!("nodes length: {}", nodes.len());
+        self.invalidate_caches(false);
+        self.index.insert(elem.clone(), self.nodes.len());
+        self.nodes.push(Node::<T>::new(elem, self.neighbors_capacity));
+        let node = &self.nodes[self.nodes.len() - 1].elem;
+        self.observers.notify(|| GraphEvent::AddNode(node.clone()));
     }
 
     fn node_exists(&self, node: T) -> bool {
-        let nodes = self.nodes.borrow();
-        let idx_from = nodes.iter().position(|r| r.elem == node);
-        match idx_from {
-            None => {
-                return false;
-            }
-            Some(_value) => {
-                return true;
-            }
-        }
+        self.index.contains_key(&node)
     }
 
     fn is_connected(&self, from: T, to: T) -> bool {
-        //println!("Checking from {} to {}", from, to);
-        let mut seen = Vec::<T>::new();
-        let mut to_process = Vec::<T>::new();
-        seen.push(from.clone());
-        to_process.push(from.clone());
-
-        let mut end = false;
-        while !end {
-            let node_id = to_process.pop().unwrap().clone();
-
-            let neighbors = self.get_neighbors(node_id.clone());
-            //println!("  |-> Node {} neighbors {:?}", node_id, neighbors);
-            if neighbors.contains(&to) {
-                return true;
-            } else {
-                for n in neighbors.iter() {
-                    if !seen.contains(n) {
-                        to_process.push(n.clone());
-                        seen.push(n.clone());
-                    }
-                }
+        if let (Ok(idx_from), Ok(idx_to)) = (
+            self.get_index_by_node_id(from.clone()),
+            self.get_index_by_node_id(to.clone()),
+        ) {
+            if let Some(connected) = self.cached_is_connected(idx_from, idx_to) {
+                return connected;
             }
-
-            end = to_process.is_empty();
         }
-
-        return false;
+        self.is_connected_with(from, to, &mut VisitMap::default())
     }
 
     fn is_directly_connected(&self, from: T, to: T) -> bool {
-        let nodes = self.nodes.borrow();
         let ret_idx_from = self.get_index_by_node_id(from.clone());
         let idx_from;
         match ret_idx_from {
@@ -175,55 +452,41 @@ where
             }
         };
 
-        let n = &nodes[idx_from];
-        let m = nodes[idx_to].clone();
-        for e in n.neighbors.borrow().iter() {
-            if Rc::ptr_eq(e, &m) {
-                //println!("Node {} is connected to {}", from, to);
-                return true;
-            }
-        }
-        //println!("Node {} is NOT connected to {}", from, to);
-        return false;
-    }
-
-    fn to_dot_file(&self, file: &mut File, graph_name: &str) {
-        let s = self.to_dot_string(graph_name);
-        file.write_all(s.as_bytes()).expect("Error writing file!");
+        self.nodes[idx_from].neighbors.contains(&idx_to)
     }
 
+    /// Returns a dot representation of the graph with a statement for every
+    /// node and every edge, so it parses back into an equal graph
     fn to_dot_string(&self, graph_name: &str) -> String {
-        let mut s = String::from("digraph ") + graph_name + &String::from(" {\n");
-        let nodes = self.nodes.borrow();
-        for n in nodes.iter() {
-            s = s + &String::from("    ") + &n.elem.to_string();
-            for m in n.neighbors.borrow().iter() {
-                s = s + &String::from(" -> ") + &m.elem.to_string();
-            }
-
-            s = s + &String::from(";\n");
-        }
-        s = s + &String::from("}\n");
-        return s;
+        self.to_dot_string_with(graph_name, &DotStyle::new())
     }
 
     fn is_empty(&self) -> bool {
-        return self.nodes.borrow().is_empty();
+        return self.nodes.is_empty();
     }
 
     fn count_nodes(&self) -> usize {
-        return self.nodes.borrow().len();
+        return self.nodes.len();
     }
 
     fn get_nodes(&self) -> Vec<T> {
         let mut ret = Vec::<T>::new();
-        for n in self.nodes.borrow().iter() {
+        for n in self.nodes.iter() {
             ret.push(n.elem.clone());
         }
         return ret;
     }
 }
 
+impl<T> Default for DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> IDiGraph<T> for DiGraph<T>
 where
     T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
@@ -236,25 +499,52 @@ where
             return;
         }
 
-        let nodes = self.nodes.borrow_mut();
-
-        let idx_from = nodes.iter().position(|r| r.elem == from).unwrap();
-        let idx_to = nodes.iter().position(|r| r.elem == to).unwrap();
-
-        let n = &nodes[idx_from];
-        let m = nodes[idx_to].clone();
-
-        n.neighbors.borrow_mut().push(m);
+        self.invalidate_caches(true);
+        let idx_from = self.get_index_by_node_id(from).unwrap();
+        let idx_to = self.get_index_by_node_id(to).unwrap();
+
+        self.nodes[idx_from].neighbors.push(idx_to);
+        self.observers.notify(|| {
+            GraphEvent::AddEdge(
+                self.nodes[idx_from].elem.clone(),
+                self.nodes[idx_to].elem.clone(),
+                (),
+            )
+        });
     }
 
     fn all_simple_paths(&self, from: T, to: T) -> Vec<Vec<T>> {
+        self.all_simple_paths_with_cutoff(from, to, usize::MAX)
+    }
+
+    fn all_simple_paths_limited(
+        &self,
+        from: T,
+        to: T,
+        max_len: usize,
+        limit: usize,
+    ) -> LimitedPaths<T> {
         let mut ret = Vec::<Vec<T>>::new();
+        let (Ok(idx_from), Ok(idx_to)) = (
+            self.get_index_by_node_id(from),
+            self.get_index_by_node_id(to),
+        ) else {
+            return (ret, false);
+        };
+        let bounds = PathBounds { max_len, limit };
         let mut current_path = Vec::<T>::new();
-        let mut visited = Vec::<T>::new();
-
-        self.dfs(from, to, &mut ret, &mut current_path, &mut visited);
+        let mut visited = VisitMap::new(self.nodes.len());
+
+        self.dfs(
+            idx_from,
+            idx_to,
+            &bounds,
+            &mut ret,
+            &mut current_path,
+            &mut visited,
+        );
 
-        return ret;
+        bounds.truncate(ret)
     }
 
     fn get_neighbors(&self, from: T) -> Vec<T> {
@@ -264,76 +554,31 @@ where
             return neighbors;
         }
 
-        let nodes = self.nodes.borrow();
+        let idx_from = self.get_index_by_node_id(from).unwrap();
 
-        let idx_from = nodes.iter().position(|r| r.elem == from).unwrap();
-
-        let n = &nodes[idx_from];
-
-        //n.neighbors
-        for e in n.neighbors.borrow().iter() {
-            neighbors.push(e.elem.clone());
+        for e in self.nodes[idx_from].neighbors.iter() {
+            neighbors.push(self.nodes[*e].elem.clone());
         }
 
         return neighbors;
     }
 }
 
-impl<T> Drop for DiGraph<T>
-where
-    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
-{
-    fn drop(&mut self) {
-        self.nodes.borrow_mut().clear();
-    }
-}
-
 /// Returns a directed string graph `DiGraph<String>` from a dot file content
 pub fn digraph_from_dot_string(content: &String) -> Result<DiGraph<String>, &'static str> {
-    let mut graph = DiGraph::<String>::new();
-    let idx1: usize;
-    let idx2: usize;
-    match content.chars().position(|c| c == '{') {
-        None => {
-            return Err("Dot file not correct. { not found.");
-        }
-        Some(i) => {
-            idx1 = i + 1;
-        }
-    }
-
-    match content.chars().position(|c| c == '}') {
-        None => {
-            return Err("Dot file not correct. } not found.");
-        }
-        Some(i) => {
-            idx2 = i - 1;
-        }
-    }
-
-    if idx2 < idx1 {
-        return Err("Dot file not correct. } before {");
-    }
-
-    let c = &content[idx1..idx2];
-    let v_c: Vec<&str> = c.split(';').collect();
+    digraph_from_dot_reader(content.as_bytes())
+}
 
-    for line in v_c.iter() {
-        let v_nodes: Vec<&str> = line.split("->").collect();
-        let mut prev_node = String::new();
-        for txt_node in v_nodes.iter() {
-            let txt_n = txt_node.replace(";", "");
-            let n = txt_n.trim().to_string();
-            if !n.is_empty() {
-                // println!("Adding node {}", n.clone());
-                graph.add_node(n.clone());
-            }
-            if !prev_node.is_empty() {
-                // println!("  |-> Edge {} to {}",prev_node, n);
-                graph.add_edge(prev_node.clone(), n.clone());
-            }
-            prev_node = n.clone();
-        }
+/// Returns a directed string graph `DiGraph<String>` from a dot file read
+/// incrementally from `reader`, so big files don't need to be loaded in memory
+pub fn digraph_from_dot_reader(reader: impl BufRead) -> Result<DiGraph<String>, &'static str> {
+    let mut graph = DiGraph::<String>::new();
+    let header = parse_dot_reader(reader, |stmt| match stmt {
+        DotStatement::Node(id) => graph.add_node(id),
+        DotStatement::Edge(from, to, _) => graph.add_edge(from, to),
+    })?;
+    if !header.directed {
+        return Err("Dot file not correct. digraph expected.");
     }
 
     Ok(graph)
@@ -345,6 +590,7 @@ mod tests {
     use crate::digraph::digraph_from_dot_string;
     use crate::rugraph::IDiGraph;
     use crate::rugraph::IGraph;
+    use crate::visit::VisitMap;
     use std::fs::File;
     #[test]
     fn digraph_it_works() {
@@ -437,7 +683,9 @@ mod tests {
         );
 
         let mut fd = File::create("test2.dot").expect("error creating file");
-        graph.to_dot_file(&mut fd, &String::from("paths_test"))
+        graph
+            .to_dot_file(&mut fd, &String::from("paths_test"))
+            .expect("error writing file");
     }
 
     #[test]
@@ -470,12 +718,29 @@ mod tests {
         graph.add_edge("b".to_string(), "c".to_string());
         graph.add_edge("c".to_string(), "d".to_string());
         graph.add_edge("a".to_string(), "d".to_string());
-        graph.to_dot_file(&mut fd, &String::from("to_dot_test"));
+        graph
+            .to_dot_file(&mut fd, &String::from("to_dot_test"))
+            .expect("error writing file");
         let s = graph.to_dot_string(&String::from("to_dot_test"));
         println!("Dot:\n{}", s);
         assert_eq!(s.is_empty(), false);
     }
 
+    #[test]
+    fn digraph_to_dot_writer() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_edge(1, 2);
+        let mut buffer = Vec::<u8>::new();
+        graph.to_dot_writer(&mut buffer, "g").unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), graph.to_dot_string("g"));
+
+        // Write errors are returned instead of panicking
+        let mut small = [0u8; 4];
+        assert!(graph.to_dot_writer(&mut &mut small[..], "g").is_err());
+    }
+
     #[test]
     fn digraph_from_dot_str() {
         let content =
@@ -494,4 +759,213 @@ mod tests {
         println!("{}", s);
         //assert_eq!(s,content);
     }
+
+    #[test]
+    fn digraph_from_dot_reader() {
+        // Small buffer so tokens and multi-byte characters cross buffer boundaries
+        let content = "digraph g {\n  \"ñandú\" -> b;\n  /* a\n comment */ b -> c;\n}\n";
+        let reader = std::io::BufReader::with_capacity(3, content.as_bytes());
+        let graph = super::digraph_from_dot_reader(reader).unwrap();
+        assert_eq!(graph.get_nodes(), vec!["ñandú", "b", "c"]);
+        assert_eq!(graph.get_neighbors(String::from("b")), vec!["c"]);
+
+        let invalid: &[u8] = b"digraph g { a -> \xff }";
+        assert!(super::digraph_from_dot_reader(invalid).is_err());
+    }
+
+    #[test]
+    fn digraph_reversed() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_node(3);
+        graph.add_edge(1, 2);
+        graph.add_edge(1, 3);
+        graph.add_edge(2, 3);
+        graph.edge_attrs_mut(1, 2).insert("color", "red");
+
+        let reversed = graph.reversed();
+        assert_eq!(reversed.get_nodes(), vec![1, 2, 3]);
+        assert_eq!(reversed.get_neighbors(3), vec![1, 2]);
+        assert_eq!(reversed.get_neighbors(1), Vec::<i32>::new());
+        assert!(reversed.is_connected(3, 1));
+        assert!(!reversed.is_connected(1, 3));
+        assert_eq!(
+            reversed.edge_attrs(&2, &1).and_then(|a| a.get_str("color")),
+            Some("red")
+        );
+    }
+
+    #[test]
+    fn graphs_are_send() {
+        fn assert_send<G: Send>(_: &G) {}
+        fn assert_sync<G: Sync>(_: &G) {}
+        let mut graph = DiGraph::<String>::new();
+        graph.add_node(String::from("a"));
+        assert_send(&graph);
+        assert_sync(&graph);
+        assert_send(&crate::graph::Graph::<i32>::new());
+        assert_send(&crate::multidigraph::MultiDiGraph::<i32, String>::new());
+        let handle = std::thread::spawn(move || graph.count_nodes());
+        assert_eq!(handle.join().unwrap(), 1);
+    }
+
+    #[test]
+    fn digraph_many_nodes() {
+        let mut graph = DiGraph::<u32>::new();
+        for n in 0..100_000 {
+            graph.add_node(n);
+        }
+        for n in 1..100_000 {
+            graph.add_edge(n - 1, n);
+        }
+        assert_eq!(graph.count_nodes(), 100_000);
+        assert!(graph.node_exists(99_999));
+        assert!(graph.is_directly_connected(50_000, 50_001));
+        assert_eq!(graph.get_neighbors(99_998), vec![99_999]);
+    }
+
+    #[test]
+    fn digraph_with_capacity() {
+        let mut graph = DiGraph::<u32>::with_capacity(1000, 3000);
+        assert!(graph.capacity() >= 1000);
+        assert!(graph.is_empty());
+        for n in 0..1000 {
+            graph.add_node(n);
+        }
+        for n in 0..1000 {
+            for m in 1..=3 {
+                graph.add_edge(n, (n + m) % 1000);
+            }
+        }
+        assert_eq!(graph.get_neighbors(999), vec![0, 1, 2]);
+
+        graph.reserve(500);
+        assert!(graph.capacity() >= 1500);
+    }
+
+    #[test]
+    fn digraph_paths_with_cutoff() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 1..=5 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 5);
+        graph.add_edge(1, 4);
+        graph.add_edge(4, 5);
+        graph.add_edge(1, 5);
+
+        assert_eq!(graph.all_simple_paths(1, 5).len(), 3);
+        assert_eq!(
+            graph.all_simple_paths_with_cutoff(1, 5, 2),
+            vec![vec![1, 4, 5], vec![1, 5]]
+        );
+        assert_eq!(graph.all_simple_paths_with_cutoff(1, 5, 1), vec![vec![1, 5]]);
+        assert_eq!(graph.all_simple_paths_with_cutoff(1, 1, 0), vec![vec![1]]);
+
+        let (paths, truncated) = graph.all_simple_paths_limited(1, 5, usize::MAX, 2);
+        assert_eq!(paths, vec![vec![1, 2, 3, 5], vec![1, 4, 5]]);
+        assert!(truncated);
+        let (paths, truncated) = graph.all_simple_paths_limited(1, 5, usize::MAX, 3);
+        assert_eq!(paths.len(), 3);
+        assert!(!truncated);
+        assert_eq!(graph.all_simple_paths_limited(1, 5, 2, 0), (vec![], true));
+    }
+
+    #[test]
+    fn digraph_is_connected_with() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 0..200 {
+            graph.add_node(n);
+        }
+        for n in 0..199 {
+            graph.add_edge(n, n + 1);
+        }
+        let mut visited = VisitMap::default();
+        assert!(graph.is_connected_with(0, 199, &mut visited));
+        assert_eq!(visited.len(), 200);
+        assert!(!graph.is_connected_with(199, 0, &mut visited));
+        assert!(graph.is_connected_with(100, 150, &mut visited));
+        assert!(!visited.is_visited(99));
+        assert!(!graph.is_connected_with(0, 300, &mut visited));
+    }
+
+    #[test]
+    fn digraph_neighbor_ids() {
+        let mut graph = DiGraph::<&str>::new();
+        for n in ["c", "a", "b"] {
+            graph.add_node(n);
+        }
+        graph.add_edge("c", "b");
+        graph.add_edge("c", "a");
+        let c = graph.index_of(&"c").unwrap();
+        assert_eq!(c, 0);
+        assert_eq!(graph.neighbor_ids(c), &[2, 1]);
+        let names: Vec<&str> = graph
+            .neighbor_ids(c)
+            .iter()
+            .map(|i| *graph.node(*i).unwrap())
+            .collect();
+        assert_eq!(names, graph.get_neighbors("c"));
+        assert!(graph.neighbor_ids(1).is_empty());
+        assert_eq!(graph.index_of(&"d"), None);
+        assert_eq!(graph.node(3), None);
+    }
+
+    #[test]
+    fn digraph_retain_edges() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3), (3, 1), (1, 3), (3, 3)]);
+        graph.edge_attrs_mut(2, 3).insert("w", 1);
+        assert!(graph.is_connected(2, 1));
+        graph.retain_edges(|from, to| from < to);
+        assert_eq!(graph.get_neighbors(1), vec![2, 3]);
+        assert!(graph.get_neighbors(3).is_empty());
+        assert!(!graph.is_connected(2, 1));
+        graph.retain_edges(|from, _| *from != 2);
+        assert!(!graph.is_directly_connected(2, 3));
+        assert!(graph.edge_attrs(&2, &3).is_none());
+    }
+
+    #[test]
+    fn digraph_split_node() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3), (4, 2), (2, 2)]);
+        graph.node_attrs_mut(2).insert("cap", 5);
+        graph.edge_attrs_mut(2, 3).insert("w", 1);
+        graph.edge_attrs_mut(4, 2).insert("w", 2);
+        assert_eq!(graph.split_node(9, 10, 11), Err("Element not found"));
+        assert_eq!(graph.split_node(2, 3, 20), Err("Element already exists"));
+        assert_eq!(graph.split_node(2, 20, 20), Err("Element already exists"));
+
+        graph.split_node(2, 2, 20).unwrap();
+        assert_eq!(graph.get_nodes(), vec![1, 2, 3, 4, 20]);
+        assert_eq!(graph.get_neighbors(2), vec![20]);
+        assert_eq!(graph.get_neighbors(20), vec![3, 2]);
+        assert_eq!(graph.get_neighbors(1), vec![2]);
+        assert!(!graph.is_directly_connected(2, 3));
+        assert!(graph.node_attrs(&2).is_some());
+        assert_eq!(graph.edge_attrs(&20, &3).unwrap().get_int("w"), Some(1));
+        assert_eq!(graph.edge_attrs(&4, &2).unwrap().get_int("w"), Some(2));
+        assert!(graph.edge_attrs(&2, &3).is_none());
+    }
+
+    #[test]
+    fn digraph_reverse() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (1, 3), (2, 3), (3, 3)]);
+        graph.edge_attrs_mut(1, 2).insert("color", "red");
+        assert!(graph.is_connected(1, 3));
+        let reversed = graph.reversed();
+        graph.reverse();
+        for n in 1..=3 {
+            assert_eq!(graph.get_neighbors(n), reversed.get_neighbors(n));
+        }
+        assert_eq!(graph.get_neighbors(3), vec![1, 2, 3]);
+        assert!(!graph.is_connected(1, 3));
+        assert!(graph.edge_attrs(&2, &1).is_some());
+        assert!(graph.edge_attrs(&1, &2).is_none());
+    }
 }
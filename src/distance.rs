@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use crate::adjacency::Adjacency;
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the eccentricity of every node: the largest number of hops
+    /// needed to reach another node following the edges.
+    /// Returns an error if some node can't reach every other node
+    pub fn eccentricity(&self) -> Result<BTreeMap<T, usize>, &'static str> {
+        eccentricity(&self.adjacency())
+    }
+
+    /// Returns the nodes with minimum eccentricity
+    pub fn center(&self) -> Result<Vec<T>, &'static str> {
+        extreme(&self.adjacency(), false)
+    }
+
+    /// Returns the nodes with maximum eccentricity
+    pub fn periphery(&self) -> Result<Vec<T>, &'static str> {
+        extreme(&self.adjacency(), true)
+    }
+
+    /// Returns a path with the fewest edges from node `from` to node `to`,
+    /// both included, `None` if `to` can't be reached
+    pub fn shortest_path(&self, from: T, to: T) -> Option<Vec<T>> {
+        shortest_path(&self.adjacency(), &from, &to)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the eccentricity of every node: the largest number of hops
+    /// needed to reach another node.
+    /// Returns an error if the graph is not connected
+    pub fn eccentricity(&self) -> Result<BTreeMap<T, usize>, &'static str> {
+        eccentricity(&self.adjacency())
+    }
+
+    /// Returns the nodes with minimum eccentricity
+    pub fn center(&self) -> Result<Vec<T>, &'static str> {
+        extreme(&self.adjacency(), false)
+    }
+
+    /// Returns the nodes with maximum eccentricity
+    pub fn periphery(&self) -> Result<Vec<T>, &'static str> {
+        extreme(&self.adjacency(), true)
+    }
+
+    /// Returns a path with the fewest edges between nodes `from` and `to`,
+    /// both included, `None` if they are not connected
+    pub fn shortest_path(&self, from: T, to: T) -> Option<Vec<T>> {
+        shortest_path(&self.adjacency(), &from, &to)
+    }
+}
+
+fn eccentricities<T>(adj: &Adjacency<T>) -> Result<Vec<usize>, &'static str>
+where
+    T: Ord + Clone,
+{
+    let mut ret = Vec::<usize>::with_capacity(adj.len());
+    for i in 0..adj.len() {
+        let mut e = 0;
+        for d in adj.bfs_distances(i) {
+            match d {
+                None => return Err("Graph is not connected. Infinite eccentricity."),
+                Some(d) => e = e.max(d),
+            }
+        }
+        ret.push(e);
+    }
+    Ok(ret)
+}
+
+fn eccentricity<T>(adj: &Adjacency<T>) -> Result<BTreeMap<T, usize>, &'static str>
+where
+    T: Ord + Clone,
+{
+    let e = eccentricities(adj)?;
+    Ok(adj.nodes.iter().cloned().zip(e).collect())
+}
+
+fn shortest_path<T>(adj: &Adjacency<T>, from: &T, to: &T) -> Option<Vec<T>>
+where
+    T: Ord + Clone,
+{
+    let from = adj.nodes.iter().position(|n| n == from)?;
+    let to = adj.nodes.iter().position(|n| n == to)?;
+    let path = adj.bfs_path(from, to)?;
+    Some(path.into_iter().map(|i| adj.nodes[i].clone()).collect())
+}
+
+/// Nodes with minimum (center) or maximum (`periphery`) eccentricity
+fn extreme<T>(adj: &Adjacency<T>, periphery: bool) -> Result<Vec<T>, &'static str>
+where
+    T: Ord + Clone,
+{
+    let e = eccentricities(adj)?;
+    let target = if periphery {
+        e.iter().max()
+    } else {
+        e.iter().min()
+    };
+    Ok(match target {
+        None => Vec::new(),
+        Some(t) => (0..adj.len())
+            .filter(|i| e[*i] == *t)
+            .map(|i| adj.nodes[i].clone())
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn center_periphery_path() {
+        let mut graph = Graph::<i32>::new();
+        for n in 1..=5 {
+            graph.add_node(n);
+        }
+        for n in 1..5 {
+            graph.add_edge(n, n + 1);
+        }
+
+        assert_eq!(graph.center().unwrap(), vec![3]);
+        assert_eq!(graph.periphery().unwrap(), vec![1, 5]);
+        assert_eq!(graph.eccentricity().unwrap()[&2], 3);
+
+        graph.add_node(6);
+        assert!(graph.center().is_err());
+    }
+
+    #[test]
+    fn center_periphery_digraph() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 1..=3 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        assert!(graph.center().is_err());
+
+        graph.add_edge(3, 1);
+        graph.add_edge(1, 3);
+        assert_eq!(graph.center().unwrap(), vec![1]);
+        assert_eq!(graph.periphery().unwrap(), vec![2, 3]);
+    }
+
+    #[test]
+    fn shortest_path() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 1..=5 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+        graph.add_edge(1, 3);
+        assert_eq!(graph.shortest_path(1, 4), Some(vec![1, 3, 4]));
+        assert_eq!(graph.shortest_path(1, 1), Some(vec![1]));
+        assert_eq!(graph.shortest_path(4, 1), None);
+        assert_eq!(graph.shortest_path(1, 5), None);
+        assert_eq!(graph.shortest_path(1, 9), None);
+
+        let mut graph = Graph::<i32>::new();
+        for n in 1..=3 {
+            graph.add_node(n);
+        }
+        graph.add_edge(2, 1);
+        graph.add_edge(3, 2);
+        assert_eq!(graph.shortest_path(1, 3), Some(vec![1, 2, 3]));
+    }
+}
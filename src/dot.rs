@@ -0,0 +1,1164 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io::BufRead;
+use std::iter::Peekable;
+use std::vec::Vec;
+
+use crate::attributes::undirected_key;
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::matrixgraph::MatrixGraph;
+use crate::multidigraph::MultiDiGraph;
+use crate::multigraph::MultiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+use crate::rugraph::IMultiGraph;
+use crate::weightedgraph::WeightedGraph;
+
+/// Attributes of a graph, a node or an edge as `(key, value)` pairs
+pub type Attrs = Vec<(String, String)>;
+
+/// Statement found while parsing a dot file
+pub(crate) enum DotStatement {
+    /// A node
+    Node(String),
+    /// An edge `from` -> `to` (or `from` -- `to`) and its attributes
+    Edge(String, String, Attrs),
+}
+
+/// Header of a dot file
+pub(crate) struct DotHeader {
+    pub directed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Identifier, number, quoted string or HTML string. `quoted` identifiers
+    /// are never keywords
+    Id {
+        value: String,
+        quoted: bool,
+    },
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Semicolon,
+    Comma,
+    Equal,
+    Colon,
+    /// `->`
+    Arrow,
+    /// `--`
+    Line,
+}
+
+/// Splits a dot file in tokens skipping whitespace and comments
+/// (`// ...`, `/* ... */` and `# ...` lines)
+struct Lexer<I>
+where
+    I: Iterator<Item = char>,
+{
+    chars: Peekable<I>,
+    line_start: bool,
+}
+
+impl<I> Lexer<I>
+where
+    I: Iterator<Item = char>,
+{
+    fn new(chars: I) -> Self {
+        Lexer {
+            chars: chars.peekable(),
+            line_start: true,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        self.line_start = c == Some('\n');
+        c
+    }
+
+    fn skip_line(&mut self) {
+        while let Some(c) = self.bump() {
+            if c == '\n' {
+                break;
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, &'static str> {
+        loop {
+            let c = match self.chars.peek() {
+                None => return Ok(None),
+                Some(c) => *c,
+            };
+            if c.is_whitespace() {
+                let at_start = self.line_start || c == '\n';
+                self.bump();
+                self.line_start = at_start;
+                continue;
+            }
+            if c == '#' && self.line_start {
+                self.skip_line();
+                continue;
+            }
+            self.line_start = false;
+            self.bump();
+
+            let token = match c {
+                '{' => Token::LBrace,
+                '}' => Token::RBrace,
+                '[' => Token::LBracket,
+                ']' => Token::RBracket,
+                ';' => Token::Semicolon,
+                ',' => Token::Comma,
+                '=' => Token::Equal,
+                ':' => Token::Colon,
+                '"' => self.quoted()?,
+                '<' => self.html()?,
+                '/' => match self.chars.peek() {
+                    Some('/') => {
+                        self.skip_line();
+                        continue;
+                    }
+                    Some('*') => {
+                        self.bump();
+                        self.block_comment()?;
+                        continue;
+                    }
+                    _ => return Err("Dot file not correct. Unexpected '/'."),
+                },
+                '-' => match self.chars.peek() {
+                    Some('>') => {
+                        self.bump();
+                        Token::Arrow
+                    }
+                    Some('-') => {
+                        self.bump();
+                        Token::Line
+                    }
+                    _ => self.word(c),
+                },
+                c if c.is_alphanumeric() || c == '_' || c == '.' || !c.is_ascii() => self.word(c),
+                _ => return Err("Dot file not correct. Unexpected character."),
+            };
+            return Ok(Some(token));
+        }
+    }
+
+    fn block_comment(&mut self) -> Result<(), &'static str> {
+        let mut prev = ' ';
+        while let Some(c) = self.bump() {
+            if prev == '*' && c == '/' {
+                return Ok(());
+            }
+            prev = c;
+        }
+        Err("Dot file not correct. Unterminated comment.")
+    }
+
+    /// Identifier or number
+    fn word(&mut self, first: char) -> Token {
+        let mut value = String::from(first);
+        while let Some(c) = self.chars.peek() {
+            if c.is_alphanumeric() || *c == '_' || *c == '.' || !c.is_ascii() {
+                value.push(*c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        Token::Id {
+            value,
+            quoted: false,
+        }
+    }
+
+    /// Double quoted string, the opening quote is already consumed. Strings
+    /// joined with `+` are concatenated
+    fn quoted(&mut self) -> Result<Token, &'static str> {
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                None => return Err("Dot file not correct. Unterminated string."),
+                Some('"') => break,
+                Some('\\') => match self.bump() {
+                    None => return Err("Dot file not correct. Unterminated string."),
+                    Some(c @ ('"' | '\\')) => value.push(c),
+                    Some('\n') => {}
+                    Some(c) => {
+                        value.push('\\');
+                        value.push(c);
+                    }
+                },
+                Some(c) => value.push(c),
+            }
+        }
+
+        // "a" + "b"
+        loop {
+            while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+                self.bump();
+            }
+            if self.chars.peek() != Some(&'+') {
+                break;
+            }
+            self.bump();
+            while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+                self.bump();
+            }
+            if self.bump() != Some('"') {
+                return Err("Dot file not correct. Expected string after '+'.");
+            }
+            match self.quoted()? {
+                Token::Id { value: v, .. } => value.push_str(&v),
+                _ => unreachable!(),
+            }
+        }
+        Ok(Token::Id {
+            value,
+            quoted: true,
+        })
+    }
+
+    /// HTML string `<...>` with balanced angle brackets, the first `<` is
+    /// already consumed. The outer brackets are kept
+    fn html(&mut self) -> Result<Token, &'static str> {
+        let mut value = String::from("<");
+        let mut depth = 1;
+        while depth > 0 {
+            match self.bump() {
+                None => return Err("Dot file not correct. Unterminated HTML string."),
+                Some(c) => {
+                    if c == '<' {
+                        depth += 1;
+                    } else if c == '>' {
+                        depth -= 1;
+                    }
+                    value.push(c);
+                }
+            }
+        }
+        Ok(Token::Id {
+            value,
+            quoted: true,
+        })
+    }
+}
+
+/// Recursive descent parser of the Graphviz dot language. Statements are
+/// sent to `sink` as soon as they are parsed
+struct Parser<I, F>
+where
+    I: Iterator<Item = char>,
+    F: FnMut(DotStatement),
+{
+    lexer: Lexer<I>,
+    peeked: Option<Token>,
+    directed: bool,
+    sink: F,
+}
+
+fn is_keyword(token: &Option<Token>, keyword: &str) -> bool {
+    match token {
+        Some(Token::Id {
+            value,
+            quoted: false,
+        }) => value.eq_ignore_ascii_case(keyword),
+        _ => false,
+    }
+}
+
+impl<I, F> Parser<I, F>
+where
+    I: Iterator<Item = char>,
+    F: FnMut(DotStatement),
+{
+    fn peek(&mut self) -> Result<&Option<Token>, &'static str> {
+        if self.peeked.is_none() {
+            self.peeked = self.lexer.next_token()?;
+        }
+        Ok(&self.peeked)
+    }
+
+    fn next(&mut self) -> Result<Option<Token>, &'static str> {
+        match self.peeked.take() {
+            Some(t) => Ok(Some(t)),
+            None => self.lexer.next_token(),
+        }
+    }
+
+    fn expect(&mut self, token: Token, err: &'static str) -> Result<(), &'static str> {
+        if self.next()? == Some(token) {
+            Ok(())
+        } else {
+            Err(err)
+        }
+    }
+
+    fn id(&mut self) -> Result<String, &'static str> {
+        match self.next()? {
+            Some(Token::Id { value, .. }) => Ok(value),
+            _ => Err("Dot file not correct. Identifier expected."),
+        }
+    }
+
+    /// `[strict] (graph | digraph) [ID] {`
+    fn header(&mut self) -> Result<DotHeader, &'static str> {
+        if is_keyword(self.peek()?, "strict") {
+            self.next()?;
+        }
+        let t = self.next()?;
+        if is_keyword(&t, "digraph") {
+            self.directed = true;
+        } else if is_keyword(&t, "graph") {
+            self.directed = false;
+        } else {
+            return Err("Dot file not correct. graph or digraph expected.");
+        }
+        if let Some(Token::Id { .. }) = self.peek()? {
+            self.next()?;
+        }
+        self.expect(Token::LBrace, "Dot file not correct. { not found.")?;
+        Ok(DotHeader {
+            directed: self.directed,
+        })
+    }
+
+    /// Statements until the closing `}`. Inside a subgraph, which may take part
+    /// in an edge, the nodes found are added to `members`
+    fn stmt_list(
+        &mut self,
+        edge_defaults: &Attrs,
+        mut members: Option<&mut BTreeSet<String>>,
+    ) -> Result<(), &'static str> {
+        let mut edge_defaults = edge_defaults.clone();
+        loop {
+            match self.peek()? {
+                None => return Err("Dot file not correct. } not found."),
+                Some(Token::RBrace) => {
+                    self.next()?;
+                    return Ok(());
+                }
+                Some(Token::Semicolon) => {
+                    self.next()?;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let t = self.peek()?.clone();
+            if is_keyword(&t, "node") || is_keyword(&t, "edge") || is_keyword(&t, "graph") {
+                self.next()?;
+                let attrs = self.attr_list()?;
+                if is_keyword(&t, "edge") {
+                    merge(&mut edge_defaults, &attrs);
+                }
+                continue;
+            }
+
+            let first = self.endpoint(&edge_defaults)?;
+            match (self.peek()?, &first) {
+                (Some(Token::Equal), Endpoint::Node(_)) => {
+                    // Graph attribute `ID = ID`
+                    self.next()?;
+                    self.id()?;
+                    continue;
+                }
+                (Some(Token::Arrow), _) | (Some(Token::Line), _) => {
+                    self.edge_stmt(first, &edge_defaults, members.as_deref_mut())?;
+                }
+                (_, Endpoint::Node(id)) => {
+                    let id = id.clone();
+                    self.attr_list()?;
+                    if let Some(members) = members.as_deref_mut() {
+                        members.insert(id.clone());
+                    }
+                    (self.sink)(DotStatement::Node(id));
+                }
+                (_, Endpoint::Subgraph(inner)) => {
+                    if let Some(members) = members.as_deref_mut() {
+                        members.extend(inner.iter().cloned());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Node id (ports are dropped) or subgraph. Nodes are not sent to the sink
+    /// here, subgraph statements are
+    fn endpoint(&mut self, edge_defaults: &Attrs) -> Result<Endpoint, &'static str> {
+        let t = self.peek()?.clone();
+        if is_keyword(&t, "subgraph") || t == Some(Token::LBrace) {
+            if is_keyword(&t, "subgraph") {
+                self.next()?;
+                if let Some(Token::Id { .. }) = self.peek()? {
+                    self.next()?;
+                }
+            }
+            self.expect(Token::LBrace, "Dot file not correct. { expected.")?;
+            let mut inner = BTreeSet::new();
+            self.stmt_list(edge_defaults, Some(&mut inner))?;
+            return Ok(Endpoint::Subgraph(inner));
+        }
+
+        let id = self.id()?;
+        // Port and compass point: `a:port:n`
+        while self.peek()? == &Some(Token::Colon) {
+            self.next()?;
+            self.id()?;
+        }
+        Ok(Endpoint::Node(id))
+    }
+
+    /// `endpoint (-> endpoint)+ [attr_list]`. Inside a subgraph the nodes of
+    /// every endpoint are added to `members`
+    fn edge_stmt(
+        &mut self,
+        first: Endpoint,
+        edge_defaults: &Attrs,
+        members: Option<&mut BTreeSet<String>>,
+    ) -> Result<(), &'static str> {
+        let directed = self.directed;
+        let mut chain = vec![first];
+        loop {
+            match self.peek()? {
+                Some(Token::Arrow) if directed => {}
+                Some(Token::Line) if !directed => {}
+                Some(Token::Arrow) => {
+                    return Err("Dot file not correct. -> used in an undirected graph.")
+                }
+                Some(Token::Line) => return Err("Dot file not correct. -- used in a digraph."),
+                _ => break,
+            }
+            self.next()?;
+            chain.push(self.endpoint(edge_defaults)?);
+        }
+
+        let mut attrs = edge_defaults.clone();
+        merge(&mut attrs, &self.attr_list()?);
+
+        if let Some(members) = members {
+            for e in chain.iter() {
+                members.extend(e.nodes());
+            }
+        }
+        for e in chain.iter() {
+            if let Endpoint::Node(id) = e {
+                (self.sink)(DotStatement::Node(id.clone()));
+            }
+        }
+        for pair in chain.windows(2) {
+            for from in pair[0].nodes() {
+                for to in pair[1].nodes() {
+                    (self.sink)(DotStatement::Edge(from.clone(), to.clone(), attrs.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Zero or more `[k = v, ...]` blocks
+    fn attr_list(&mut self) -> Result<Attrs, &'static str> {
+        let mut attrs = Attrs::new();
+        while self.peek()? == &Some(Token::LBracket) {
+            self.next()?;
+            loop {
+                match self.peek()? {
+                    Some(Token::RBracket) => {
+                        self.next()?;
+                        break;
+                    }
+                    Some(Token::Comma) | Some(Token::Semicolon) => {
+                        self.next()?;
+                    }
+                    _ => {
+                        let key = self.id()?;
+                        let mut value = String::from("true");
+                        if self.peek()? == &Some(Token::Equal) {
+                            self.next()?;
+                            value = self.id()?;
+                        }
+                        merge(&mut attrs, &vec![(key, value)]);
+                    }
+                }
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+enum Endpoint {
+    Node(String),
+    Subgraph(BTreeSet<String>),
+}
+
+impl Endpoint {
+    fn nodes(&self) -> Vec<String> {
+        match self {
+            Endpoint::Node(id) => vec![id.clone()],
+            Endpoint::Subgraph(nodes) => nodes.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Adds the attributes of `other` to `attrs` replacing the existing keys
+fn merge(attrs: &mut Attrs, other: &Attrs) {
+    for (k, v) in other.iter() {
+        match attrs.iter_mut().find(|(key, _)| key == k) {
+            Some(a) => a.1 = v.clone(),
+            None => attrs.push((k.clone(), v.clone())),
+        }
+    }
+}
+
+/// Parses the graph of a dot file read from `chars`, sending every node and
+/// edge statement to `sink`. Nodes are sent every time they appear. Fails if
+/// anything but comments or semicolons follows the graph
+pub(crate) fn parse_dot<I, F>(chars: I, sink: F) -> Result<DotHeader, &'static str>
+where
+    I: Iterator<Item = char>,
+    F: FnMut(DotStatement),
+{
+    let mut parser = Parser {
+        lexer: Lexer::new(chars),
+        peeked: None,
+        directed: true,
+        sink,
+    };
+    let header = parser.header()?;
+    parser.stmt_list(&Attrs::new(), None)?;
+    loop {
+        match parser.next()? {
+            None => break,
+            Some(Token::Semicolon) => {}
+            Some(_) => return Err("Dot file not correct. Content found after the graph."),
+        }
+    }
+    Ok(header)
+}
+
+/// Characters of `reader`, read one line at a time so the whole input is never
+/// held in memory. A read error ends the iteration and sets `failed`
+struct ReaderChars<R: BufRead> {
+    reader: R,
+    line: String,
+    pos: usize,
+    failed: bool,
+}
+
+impl<R: BufRead> Iterator for ReaderChars<R> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.pos >= self.line.len() {
+            self.line.clear();
+            self.pos = 0;
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(_) => {
+                    self.failed = true;
+                    return None;
+                }
+            }
+        }
+        let c = self.line[self.pos..].chars().next()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+}
+
+/// Same as `parse_dot` reading the file incrementally from `reader`
+pub(crate) fn parse_dot_reader<R, F>(reader: R, sink: F) -> Result<DotHeader, &'static str>
+where
+    R: BufRead,
+    F: FnMut(DotStatement),
+{
+    let mut chars = ReaderChars {
+        reader,
+        line: String::new(),
+        pos: 0,
+        failed: false,
+    };
+    let header = parse_dot(&mut chars, sink);
+    if chars.failed {
+        return Err("Dot file could not be read.");
+    }
+    header
+}
+
+/// Returns the value of attribute `key`
+pub(crate) fn attr<'a>(attrs: &'a Attrs, key: &str) -> Option<&'a String> {
+    attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+type NodeAttrsFn<'a, T> = Box<dyn Fn(&T) -> Attrs + 'a>;
+type EdgeAttrsFn<'a, T, E> = Box<dyn Fn(&T, &T, &E) -> Attrs + 'a>;
+
+/// Graph, node and edge attributes used by `to_dot_string_with`.
+/// Node and edge attributes are computed by closures, stored per element or
+/// both; stored values replace the computed ones with the same key
+pub struct DotStyle<'a, T, E = ()> {
+    graph: Attrs,
+    nodes: BTreeMap<T, Attrs>,
+    edges: BTreeMap<(T, T), Attrs>,
+    node_fn: Option<NodeAttrsFn<'a, T>>,
+    edge_fn: Option<EdgeAttrsFn<'a, T, E>>,
+}
+
+impl<'a, T, E> DotStyle<'a, T, E>
+where
+    T: Ord + Clone,
+{
+    pub fn new() -> Self {
+        DotStyle {
+            graph: Attrs::new(),
+            nodes: BTreeMap::new(),
+            edges: BTreeMap::new(),
+            node_fn: None,
+            edge_fn: None,
+        }
+    }
+
+    /// Adds a graph level attribute, e.g. `rankdir=LR`
+    pub fn graph_attr(mut self, key: &str, value: &str) -> Self {
+        merge(&mut self.graph, &vec![(key.to_string(), value.to_string())]);
+        self
+    }
+
+    /// Stores an attribute for `node`
+    pub fn node_attr(mut self, node: T, key: &str, value: &str) -> Self {
+        let attrs = self.nodes.entry(node).or_default();
+        merge(attrs, &vec![(key.to_string(), value.to_string())]);
+        self
+    }
+
+    /// Stores an attribute for every edge from `from` to `to`
+    pub fn edge_attr(mut self, from: T, to: T, key: &str, value: &str) -> Self {
+        let attrs = self.edges.entry((from, to)).or_default();
+        merge(attrs, &vec![(key.to_string(), value.to_string())]);
+        self
+    }
+
+    /// Computes the attributes of every node with `f`
+    pub fn node_attrs(mut self, f: impl Fn(&T) -> Attrs + 'a) -> Self {
+        self.node_fn = Some(Box::new(f));
+        self
+    }
+
+    /// Computes the attributes of every edge with `f`, called with the source,
+    /// the target and the edge value (`()` for graphs without edge values)
+    pub fn edge_attrs(mut self, f: impl Fn(&T, &T, &E) -> Attrs + 'a) -> Self {
+        self.edge_fn = Some(Box::new(f));
+        self
+    }
+
+    fn node_attrs_of(&self, node: &T) -> Attrs {
+        let mut attrs = match &self.node_fn {
+            Some(f) => f(node),
+            None => Attrs::new(),
+        };
+        if let Some(stored) = self.nodes.get(node) {
+            merge(&mut attrs, stored);
+        }
+        attrs
+    }
+
+    /// In an undirected graph stored attributes match both orientations
+    fn edge_attrs_of(&self, from: &T, to: &T, edge: &E, directed: bool) -> Attrs {
+        let mut attrs = match &self.edge_fn {
+            Some(f) => f(from, to, edge),
+            None => Attrs::new(),
+        };
+        if !directed {
+            if let Some(stored) = self.edges.get(&(to.clone(), from.clone())) {
+                merge(&mut attrs, stored);
+            }
+        }
+        if let Some(stored) = self.edges.get(&(from.clone(), to.clone())) {
+            merge(&mut attrs, stored);
+        }
+        attrs
+    }
+}
+
+impl<T, E> Default for DotStyle<'_, T, E>
+where
+    T: Ord + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a dot representation of the graph with the stored node and
+    /// edge attributes and the ones given by `style`, that take precedence.
+    /// Every node gets its own statement
+    pub fn to_dot_string_with(&self, graph_name: &str, style: &DotStyle<T>) -> String {
+        let adj = self.adjacency();
+        let mut edges = Vec::<(&T, &T, Attrs)>::new();
+        for (i, s) in adj.succ.iter().enumerate() {
+            for j in s.iter() {
+                let (from, to) = (&adj.nodes[i], &adj.nodes[*j]);
+                let mut attrs = self.attrs.edge(&(from.clone(), to.clone())).to_strings();
+                merge(&mut attrs, &style.edge_attrs_of(from, to, &(), true));
+                edges.push((from, to, attrs));
+            }
+        }
+        let stored = |n: &T| self.attrs.node(n).to_strings();
+        write_dot(true, graph_name, style, &adj.nodes, stored, &edges)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a dot representation of the graph with the stored node and
+    /// edge attributes and the ones given by `style`, that take precedence.
+    /// Every node gets its own statement and every edge is written once
+    pub fn to_dot_string_with(&self, graph_name: &str, style: &DotStyle<T>) -> String {
+        let adj = self.adjacency();
+        let attributes = &self.digraph.attrs;
+        let mut edges = Vec::<(&T, &T, Attrs)>::new();
+        for (i, s) in adj.succ.iter().enumerate() {
+            for j in s.iter().filter(|j| **j >= i) {
+                let (from, to) = (&adj.nodes[i], &adj.nodes[*j]);
+                let key = undirected_key(from.clone(), to.clone());
+                let mut attrs = attributes.edge(&key).to_strings();
+                merge(&mut attrs, &style.edge_attrs_of(from, to, &(), false));
+                edges.push((from, to, attrs));
+            }
+        }
+        let stored = |n: &T| attributes.node(n).to_strings();
+        write_dot(false, graph_name, style, &adj.nodes, stored, &edges)
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a dot representation of the graph with the stored node and
+    /// edge attributes and the ones given by `style`, that take precedence.
+    /// Every node gets its own statement and edges are labeled with their
+    /// value unless another `label` is set
+    pub fn to_dot_string_with(&self, graph_name: &str, style: &DotStyle<T, E>) -> String {
+        let nodes = self.get_nodes();
+        let mut neighbors = Vec::<(T, T, E)>::new();
+        for n in nodes.iter() {
+            for (m, e) in self.get_neighbors(n.clone()) {
+                neighbors.push((n.clone(), m, e));
+            }
+        }
+        let edges: Vec<(&T, &T, Attrs)> = neighbors
+            .iter()
+            .map(|(from, to, e)| {
+                let mut attrs = vec![(String::from("label"), e.to_string())];
+                let key = (from.clone(), to.clone(), e.clone());
+                merge(&mut attrs, &self.attrs.edge(&key).to_strings());
+                merge(&mut attrs, &style.edge_attrs_of(from, to, e, true));
+                (from, to, attrs)
+            })
+            .collect();
+        let stored = |n: &T| self.attrs.node(n).to_strings();
+        write_dot(true, graph_name, style, &nodes, stored, &edges)
+    }
+}
+
+impl<T, E> MultiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a dot representation of the graph with the attributes given
+    /// by `style`. Every node gets its own statement, every edge is written
+    /// once and labeled with its value unless `style` sets another `label`
+    pub fn to_dot_string_with(&self, graph_name: &str, style: &DotStyle<T, E>) -> String {
+        let nodes = self.get_nodes();
+        let mut neighbors = Vec::<(T, T, E)>::new();
+        for (i, n) in nodes.iter().enumerate() {
+            for (m, e) in self.get_neighbors(n.clone()) {
+                if nodes.iter().position(|x| *x == m).unwrap() >= i {
+                    neighbors.push((n.clone(), m, e));
+                }
+            }
+        }
+        let edges: Vec<(&T, &T, Attrs)> = neighbors
+            .iter()
+            .map(|(a, b, e)| {
+                let mut attrs = vec![(String::from("label"), e.to_string())];
+                merge(&mut attrs, &style.edge_attrs_of(a, b, e, false));
+                (a, b, attrs)
+            })
+            .collect();
+        write_dot(false, graph_name, style, &nodes, |_| Attrs::new(), &edges)
+    }
+}
+
+impl<T, W> WeightedGraph<T, W>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    W: Copy + PartialOrd + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a dot representation of the graph with the attributes given
+    /// by `style`. Every node gets its own statement and every edge is
+    /// written once with its `weight` attribute
+    pub fn to_dot_string_with(&self, graph_name: &str, style: &DotStyle<T, W>) -> String {
+        let nodes = self.get_nodes();
+        let weighted = self.get_edges();
+        let edges: Vec<(&T, &T, Attrs)> = weighted
+            .iter()
+            .map(|(a, b, w)| {
+                let mut attrs = vec![(String::from("weight"), w.to_string())];
+                merge(&mut attrs, &style.edge_attrs_of(a, b, w, false));
+                (a, b, attrs)
+            })
+            .collect();
+        write_dot(false, graph_name, style, &nodes, |_| Attrs::new(), &edges)
+    }
+}
+
+impl<T> MatrixGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a dot representation of the graph with the attributes given
+    /// by `style`. Every node gets its own statement
+    pub fn to_dot_string_with(&self, graph_name: &str, style: &DotStyle<T>) -> String {
+        let nodes = self.get_nodes();
+        let mut neighbors = Vec::<(T, T)>::new();
+        for n in nodes.iter() {
+            for m in self.get_neighbors(n.clone()) {
+                neighbors.push((n.clone(), m));
+            }
+        }
+        let edges: Vec<(&T, &T, Attrs)> = neighbors
+            .iter()
+            .map(|(from, to)| (from, to, style.edge_attrs_of(from, to, &(), true)))
+            .collect();
+        write_dot(true, graph_name, style, &nodes, |_| Attrs::new(), &edges)
+    }
+}
+
+/// Writes a whole dot file. Node attributes are the `stored` ones replaced
+/// by the ones of `style`
+fn write_dot<T, E>(
+    directed: bool,
+    graph_name: &str,
+    style: &DotStyle<T, E>,
+    nodes: &[T],
+    stored: impl Fn(&T) -> Attrs,
+    edges: &[(&T, &T, Attrs)],
+) -> String
+where
+    T: Ord + Clone + std::fmt::Display,
+{
+    let (kind, op) = if directed {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+    let mut s = format!("{} {} {{\n", kind, quote_id(graph_name));
+    for (k, v) in style.graph.iter() {
+        s += &format!("    {}={};\n", quote_id(k), quote_id(v));
+    }
+    for n in nodes.iter() {
+        let mut attrs = stored(n);
+        merge(&mut attrs, &style.node_attrs_of(n));
+        s += &format!("    {}{};\n", quote_id(&n.to_string()), attr_list(&attrs));
+    }
+    for (from, to, attrs) in edges.iter() {
+        s += &format!(
+            "    {} {} {}{};\n",
+            quote_id(&from.to_string()),
+            op,
+            quote_id(&to.to_string()),
+            attr_list(attrs)
+        );
+    }
+    s += "}\n";
+    s
+}
+
+/// ` [k="v", ...]`, or nothing when there are no attributes
+fn attr_list(attrs: &Attrs) -> String {
+    if attrs.is_empty() {
+        return String::new();
+    }
+    let list: Vec<String> = attrs
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", quote_id(k), escape(v)))
+        .collect();
+    format!(" [{}]", list.join(", "))
+}
+
+/// Returns `id` as is when it is a valid dot identifier or numeral and quoted
+/// otherwise
+pub(crate) fn quote_id(id: &str) -> String {
+    let mut chars = id.chars();
+    let plain = match chars.next() {
+        None => false,
+        Some(c) if c.is_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_alphanumeric() || c == '_')
+                && !["graph", "digraph", "subgraph", "node", "edge", "strict"]
+                    .contains(&id.to_lowercase().as_str())
+        }
+        Some(_) => is_numeral(id),
+    };
+    if plain {
+        id.to_string()
+    } else {
+        format!("\"{}\"", escape(id))
+    }
+}
+
+/// Escapes backslashes and double quotes so the quoted string reads back
+/// unchanged
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// `[-]?(.[0-9]+ | [0-9]+(.[0-9]*)?)`
+fn is_numeral(id: &str) -> bool {
+    let digits = id.strip_prefix('-').unwrap_or(id);
+    let mut parts = digits.splitn(2, '.');
+    let int = parts.next().unwrap_or("");
+    let frac = parts.next();
+    int.chars().all(|c| c.is_ascii_digit())
+        && frac.is_none_or(|f| f.chars().all(|c| c.is_ascii_digit()))
+        && (!int.is_empty() || frac.is_some_and(|f| !f.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_dot;
+    use super::DotStatement;
+
+    fn parse(content: &str) -> Result<(bool, Vec<String>, Vec<String>), &'static str> {
+        let mut nodes = Vec::<String>::new();
+        let mut edges = Vec::<String>::new();
+        let header = parse_dot(content.chars(), |s| match s {
+            DotStatement::Node(id) => {
+                if !nodes.contains(&id) {
+                    nodes.push(id)
+                }
+            }
+            DotStatement::Edge(a, b, attrs) => {
+                let label = super::attr(&attrs, "label").cloned().unwrap_or_default();
+                edges.push(format!("{}->{}:{}", a, b, label))
+            }
+        })?;
+        Ok((header.directed, nodes, edges))
+    }
+
+    #[test]
+    fn dot_grammar() {
+        let content = r#"
+# generated by some tool
+/* block
+   comment */
+strict digraph "my graph" {
+    graph [rankdir=LR];
+    rankdir = LR
+    node [shape=box, color="red"];
+    edge [label=default]
+    "hello world" -> b:port:n -> c [label="a \"quoted\" label", color=blue];
+    d [label=<<b>bold</b>>]; // comment
+    b -> { e f } [label=x]
+    subgraph cluster_0 { g; h -> g }
+    c -> "multi" + "part"
+}
+"#;
+        let (directed, nodes, edges) = parse(content).unwrap();
+        assert!(directed);
+        assert_eq!(
+            nodes,
+            vec![
+                "hello world",
+                "b",
+                "c",
+                "d",
+                "e",
+                "f",
+                "g",
+                "h",
+                "multipart"
+            ]
+        );
+        assert_eq!(
+            edges,
+            vec![
+                "hello world->b:a \"quoted\" label",
+                "b->c:a \"quoted\" label",
+                "b->e:x",
+                "b->f:x",
+                "h->g:default",
+                "c->multipart:default"
+            ]
+        );
+    }
+
+    #[test]
+    fn dot_errors() {
+        assert!(parse("digraph { a -- b }").is_err());
+        assert!(parse("graph { a -> b }").is_err());
+        assert!(parse("digraph { a -> b ").is_err());
+        assert!(parse("digraph { a -> \"b }").is_err());
+        assert!(parse("a -> b").is_err());
+        assert!(parse("digraph { a -> b } trailing").is_err());
+        assert!(parse("digraph { a } digraph { b }").is_err());
+        assert!(parse("digraph { a -> b }; // comment\n").is_ok());
+
+        let trailing = String::from("digraph { a -> b } trailing");
+        assert!(crate::digraph::digraph_from_dot_string(&trailing).is_err());
+        assert!(crate::multidigraph::multidigraph_from_dot_string(&trailing).is_err());
+
+        let (directed, nodes, edges) = parse("graph g { a -- b; c }").unwrap();
+        assert!(!directed);
+        assert_eq!(nodes, vec!["a", "b", "c"]);
+        assert_eq!(edges, vec!["a->b:"]);
+    }
+
+    #[test]
+    fn dot_subgraph_endpoints() {
+        let content = "digraph { a -> { rank = same; b; c -> d; { e }; b } -> f }";
+        let (_, nodes, edges) = parse(content).unwrap();
+        assert_eq!(nodes, vec!["b", "c", "d", "e", "a", "f"]);
+        assert_eq!(
+            edges,
+            vec!["c->d:", "a->b:", "a->c:", "a->d:", "a->e:", "b->f:", "c->f:", "d->f:", "e->f:"]
+        );
+    }
+
+    #[test]
+    fn dot_export_style() {
+        use super::DotStyle;
+        use crate::digraph::DiGraph;
+        use crate::graph::Graph;
+        use crate::multidigraph::MultiDiGraph;
+        use crate::rugraph::IDiGraph;
+        use crate::rugraph::IGraph;
+        use crate::rugraph::IMultiDiGraph;
+
+        let mut graph = DiGraph::<String>::new();
+        graph.add_node(String::from("a"));
+        graph.add_node(String::from("b c"));
+        graph.add_node(String::from("d"));
+        graph.add_edge(String::from("a"), String::from("b c"));
+        let style = DotStyle::new()
+            .graph_attr("rankdir", "LR")
+            .node_attrs(|n: &String| vec![(String::from("tooltip"), n.to_uppercase())])
+            .node_attr(String::from("a"), "shape", "box")
+            .edge_attr(String::from("a"), String::from("b c"), "color", "red");
+        let s = graph.to_dot_string_with("styled", &style);
+        assert_eq!(
+            s,
+            "digraph styled {\n    rankdir=LR;\n    a [tooltip=\"A\", shape=\"box\"];\n    \"b c\" [tooltip=\"B C\"];\n    d [tooltip=\"D\"];\n    a -> \"b c\" [color=\"red\"];\n}\n"
+        );
+        let (directed, nodes, edges) = parse(&s).unwrap();
+        assert!(directed);
+        assert_eq!(nodes, vec!["a", "b c", "d"]);
+        assert_eq!(edges, vec!["a->b c:"]);
+
+        let mut graph = Graph::<i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_edge(1, 2);
+        let style = DotStyle::new().edge_attr(2, 1, "style", "dashed");
+        assert_eq!(
+            graph.to_dot_string_with("g", &style),
+            "graph g {\n    1;\n    2;\n    1 -- 2 [style=\"dashed\"];\n}\n"
+        );
+
+        let mut graph = MultiDiGraph::<i32, String>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_edge(1, 2, String::from("x"));
+        graph.add_edge(1, 2, String::from("y"));
+        let style = DotStyle::new().edge_attrs(|_: &i32, _: &i32, e: &String| {
+            if e == "y" {
+                vec![(String::from("color"), String::from("blue"))]
+            } else {
+                vec![]
+            }
+        });
+        let (_, _, edges) = parse(&graph.to_dot_string_with("m", &style)).unwrap();
+        assert_eq!(edges, vec!["1->2:x", "1->2:y"]);
+        assert!(graph
+            .to_dot_string_with("m", &style)
+            .contains("1 -> 2 [label=\"y\", color=\"blue\"];"));
+    }
+
+    #[test]
+    fn dot_round_trip() {
+        use crate::digraph::digraph_from_dot_string;
+        use crate::digraph::DiGraph;
+        use crate::graph::graph_from_dot_string;
+        use crate::graph::Graph;
+        use crate::multidigraph::multidigraph_from_dot_string;
+        use crate::multidigraph::MultiDiGraph;
+        use crate::rugraph::IDiGraph;
+        use crate::rugraph::IGraph;
+        use crate::rugraph::IMultiDiGraph;
+
+        let names = [
+            "a",
+            "b c",
+            "say \"hi\"",
+            "back\\",
+            "node",
+            "1.5",
+            "",
+            "isolated",
+        ];
+        let mut graph = DiGraph::<String>::new();
+        for n in names.iter() {
+            graph.add_node(n.to_string());
+        }
+        graph.add_edge(String::from("a"), String::from("b c"));
+        graph.add_edge(String::from("a"), String::from("back\\"));
+        graph.add_edge(String::from("say \"hi\""), String::from("node"));
+        graph.add_edge(String::from(""), String::from(""));
+        let read = digraph_from_dot_string(&graph.to_dot_string("g")).unwrap();
+        assert_eq!(read.get_nodes(), graph.get_nodes());
+        for n in names.iter() {
+            assert_eq!(
+                read.get_neighbors(n.to_string()),
+                graph.get_neighbors(n.to_string())
+            );
+        }
+
+        let mut graph = Graph::<String>::new();
+        for n in names.iter() {
+            graph.add_node(n.to_string());
+        }
+        graph.add_edge(String::from("1.5"), String::from("a"));
+        let read = graph_from_dot_string(&graph.to_dot_string("g")).unwrap();
+        assert_eq!(read.get_nodes(), graph.get_nodes());
+        assert_eq!(read.get_neighbors(String::from("a")), vec!["1.5"]);
+        assert!(read.get_neighbors(String::from("isolated")).is_empty());
+
+        let mut graph = MultiDiGraph::<String, String>::new();
+        for n in names.iter() {
+            graph.add_node(n.to_string());
+        }
+        graph.add_edge(String::from("a"), String::from("a"), String::from("x"));
+        graph.add_edge(String::from("a"), String::from("a"), String::from("\"y\\"));
+        let read = multidigraph_from_dot_string(&graph.to_dot_string("g")).unwrap();
+        assert_eq!(read.get_nodes(), graph.get_nodes());
+        assert_eq!(
+            read.get_neighbors(String::from("a")),
+            graph.get_neighbors(String::from("a"))
+        );
+    }
+}
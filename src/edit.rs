@@ -0,0 +1,319 @@
+use std::collections::BTreeSet;
+use std::vec::Vec;
+
+use crate::adjacency::Adjacency;
+use crate::assignment::hungarian;
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+
+/// Graphs with at most this number of nodes get an exact edit distance
+const EXACT_LIMIT: usize = 8;
+
+/// Cost of every edit operation used by `edit_distance`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditCosts {
+    /// Cost of adding a node
+    pub node_insertion: f64,
+    /// Cost of removing a node
+    pub node_deletion: f64,
+    /// Cost of replacing a node by a node with a different value
+    pub node_substitution: f64,
+    /// Cost of adding an edge
+    pub edge_insertion: f64,
+    /// Cost of removing an edge
+    pub edge_deletion: f64,
+}
+
+impl Default for EditCosts {
+    /// Every operation costs `1`, so the distance is the number of edits
+    fn default() -> Self {
+        EditCosts {
+            node_insertion: 1.0,
+            node_deletion: 1.0,
+            node_substitution: 1.0,
+            edge_insertion: 1.0,
+            edge_deletion: 1.0,
+        }
+    }
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the minimum cost of the node and edge insertions, deletions and
+    /// substitutions needed to transform `self` into `other`.
+    /// The result is exact when both graphs have at most 8 nodes, otherwise it
+    /// is an upper bound computed from an optimal assignment of the nodes
+    pub fn edit_distance(&self, other: &Self, costs: &EditCosts) -> f64 {
+        edit_distance(&self.adjacency(), &other.adjacency(), true, costs)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the minimum cost of the node and edge insertions, deletions and
+    /// substitutions needed to transform `self` into `other`.
+    /// The result is exact when both graphs have at most 8 nodes, otherwise it
+    /// is an upper bound computed from an optimal assignment of the nodes
+    pub fn edit_distance(&self, other: &Self, costs: &EditCosts) -> f64 {
+        edit_distance(&self.adjacency(), &other.adjacency(), false, costs)
+    }
+}
+
+struct Problem<'a, T>
+where
+    T: Ord + Clone,
+{
+    g1: &'a Adjacency<T>,
+    g2: &'a Adjacency<T>,
+    e1: Vec<BTreeSet<usize>>,
+    e2: Vec<BTreeSet<usize>>,
+    directed: bool,
+    costs: &'a EditCosts,
+}
+
+fn edit_distance<T>(g1: &Adjacency<T>, g2: &Adjacency<T>, directed: bool, costs: &EditCosts) -> f64
+where
+    T: Ord + Clone,
+{
+    let edges = |g: &Adjacency<T>| -> Vec<BTreeSet<usize>> {
+        g.succ.iter().map(|s| s.iter().copied().collect()).collect()
+    };
+    let p = Problem {
+        g1,
+        g2,
+        e1: edges(g1),
+        e2: edges(g2),
+        directed,
+        costs,
+    };
+
+    if g1.len().max(g2.len()) <= EXACT_LIMIT {
+        let mut mapping = Vec::<Option<usize>>::with_capacity(g1.len());
+        let mut used = vec![false; g2.len()];
+        let mut best = f64::INFINITY;
+        p.search(&mut mapping, &mut used, 0.0, &mut best);
+        best
+    } else {
+        p.cost(&p.assignment())
+    }
+}
+
+impl<T> Problem<'_, T>
+where
+    T: Ord + Clone,
+{
+    /// Cost of the edges between node `u` and the already mapped nodes `w <= u`
+    /// of `g1`, compared with the edges between their images in `g2`
+    fn edge_cost(&self, mapping: &[Option<usize>], u: usize) -> f64 {
+        let mut c = 0.0;
+        for w in 0..=u {
+            let mut pairs = vec![(u, w)];
+            if self.directed && w != u {
+                pairs.push((w, u));
+            }
+            for (a, b) in pairs {
+                let in1 = self.e1[a].contains(&b);
+                let in2 = match (mapping[a], mapping[b]) {
+                    (Some(x), Some(y)) => self.e2[x].contains(&y),
+                    _ => false,
+                };
+                if in1 && !in2 {
+                    c += self.costs.edge_deletion;
+                } else if in2 && !in1 {
+                    c += self.costs.edge_insertion;
+                }
+            }
+        }
+        c
+    }
+
+    fn node_cost(&self, u: usize, v: Option<usize>) -> f64 {
+        match v {
+            None => self.costs.node_deletion,
+            Some(v) if self.g1.nodes[u] != self.g2.nodes[v] => self.costs.node_substitution,
+            Some(_) => 0.0,
+        }
+    }
+
+    /// Cost of inserting the nodes of `g2` without preimage and their edges
+    fn insertion_cost(&self, used: &[bool]) -> f64 {
+        let mut c = 0.0;
+        for (v, s) in self.e2.iter().enumerate() {
+            if !used[v] {
+                c += self.costs.node_insertion;
+            }
+            for x in s.iter() {
+                if (used[v] && used[*x]) || (!self.directed && *x > v) {
+                    continue;
+                }
+                c += self.costs.edge_insertion;
+            }
+        }
+        c
+    }
+
+    /// Branch and bound over all the mappings of the nodes of `g1` to distinct
+    /// nodes of `g2` or to `None` (deletion)
+    fn search(
+        &self,
+        mapping: &mut Vec<Option<usize>>,
+        used: &mut [bool],
+        cost: f64,
+        best: &mut f64,
+    ) {
+        if cost >= *best {
+            return;
+        }
+        let u = mapping.len();
+        if u == self.g1.len() {
+            let total = cost + self.insertion_cost(used);
+            if total < *best {
+                *best = total;
+            }
+            return;
+        }
+
+        let mut candidates: Vec<Option<usize>> =
+            (0..self.g2.len()).filter(|v| !used[*v]).map(Some).collect();
+        candidates.push(None);
+        for v in candidates {
+            mapping.push(v);
+            if let Some(v) = v {
+                used[v] = true;
+            }
+            let c = cost + self.node_cost(u, v) + self.edge_cost(mapping, u);
+            self.search(mapping, used, c, best);
+            if let Some(v) = v {
+                used[v] = false;
+            }
+            mapping.pop();
+        }
+    }
+
+    /// Total cost of transforming `g1` into `g2` following `mapping`
+    fn cost(&self, mapping: &[Option<usize>]) -> f64 {
+        let mut used = vec![false; self.g2.len()];
+        let mut c = 0.0;
+        for (u, v) in mapping.iter().enumerate() {
+            if let Some(v) = v {
+                used[*v] = true;
+            }
+            c += self.node_cost(u, *v) + self.edge_cost(mapping, u);
+        }
+        c + self.insertion_cost(&used)
+    }
+
+    /// Mapping given by an optimal assignment between the nodes of both graphs
+    /// where the cost of matching two nodes also estimates the cost of their edges
+    fn assignment(&self) -> Vec<Option<usize>> {
+        let n1 = self.g1.len();
+        let n2 = self.g2.len();
+        let pred1 = self.g1.predecessors();
+        let pred2 = self.g2.predecessors();
+        let degree1 = |u: usize| self.e1[u].len() + if self.directed { pred1[u].len() } else { 0 };
+        let degree2 = |v: usize| self.e2[v].len() + if self.directed { pred2[v].len() } else { 0 };
+
+        let size = n1 + n2;
+        let mut a = vec![vec![None; size]; size];
+        for (i, row) in a.iter_mut().enumerate() {
+            for (j, c) in row.iter_mut().enumerate() {
+                *c = if i < n1 && j < n2 {
+                    let (d1, d2) = (degree1(i) as f64, degree2(j) as f64);
+                    let edges = if d1 > d2 {
+                        (d1 - d2) * self.costs.edge_deletion
+                    } else {
+                        (d2 - d1) * self.costs.edge_insertion
+                    };
+                    Some(self.node_cost(i, Some(j)) + edges / 2.0)
+                } else if i < n1 {
+                    (j - n2 == i).then(|| {
+                        self.costs.node_deletion
+                            + degree1(i) as f64 * self.costs.edge_deletion / 2.0
+                    })
+                } else if j < n2 {
+                    (i - n1 == j).then(|| {
+                        self.costs.node_insertion
+                            + degree2(j) as f64 * self.costs.edge_insertion / 2.0
+                    })
+                } else {
+                    Some(0.0)
+                };
+            }
+        }
+
+        let p = hungarian(&a).unwrap();
+        (0..n1)
+            .map(|i| if p[i] < n2 { Some(p[i]) } else { None })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EditCosts;
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    fn path(nodes: &[i32]) -> Graph<i32> {
+        let mut graph = Graph::<i32>::new();
+        for n in nodes.iter() {
+            graph.add_node(*n);
+        }
+        for w in nodes.windows(2) {
+            graph.add_edge(w[0], w[1]);
+        }
+        graph
+    }
+
+    #[test]
+    fn edit_distance_small() {
+        let costs = EditCosts::default();
+        let g1 = path(&[1, 2, 3]);
+        assert_eq!(g1.edit_distance(&g1, &costs), 0.0);
+
+        // Close the triangle: one edge insertion
+        let mut g2 = path(&[1, 2, 3]);
+        g2.add_edge(3, 1);
+        assert_eq!(g1.edit_distance(&g2, &costs), 1.0);
+
+        // Extra node with one edge
+        let g3 = path(&[1, 2, 3, 4]);
+        assert_eq!(g1.edit_distance(&g3, &costs), 2.0);
+        assert_eq!(g3.edit_distance(&g1, &costs), 2.0);
+
+        // Relabel a node
+        let g4 = path(&[1, 2, 9]);
+        assert_eq!(g1.edit_distance(&g4, &costs), 1.0);
+
+        let mut d1 = DiGraph::<i32>::new();
+        let mut d2 = DiGraph::<i32>::new();
+        for n in 1..=2 {
+            d1.add_node(n);
+            d2.add_node(n);
+        }
+        d1.add_edge(1, 2);
+        d2.add_edge(2, 1);
+        assert_eq!(d1.edit_distance(&d2, &costs), 2.0);
+    }
+
+    #[test]
+    fn edit_distance_large_is_upper_bound() {
+        let costs = EditCosts::default();
+        let nodes: Vec<i32> = (0..12).collect();
+        let g1 = path(&nodes);
+        assert_eq!(g1.edit_distance(&g1, &costs), 0.0);
+
+        let mut g2 = path(&nodes);
+        g2.add_node(12);
+        g2.add_edge(11, 12);
+        let d = g1.edit_distance(&g2, &costs);
+        assert!(d >= 2.0);
+        assert!(d <= 4.0);
+    }
+}
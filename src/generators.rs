@@ -0,0 +1,552 @@
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rand")]
+use std::collections::BTreeSet;
+#[cfg(feature = "rand")]
+use std::collections::VecDeque;
+use std::vec::Vec;
+
+#[cfg(feature = "rand")]
+use crate::bipartite::BipartiteGraph;
+#[cfg(feature = "rand")]
+use crate::digraph::DiGraph;
+#[cfg(feature = "rand")]
+use crate::graph::Graph;
+use crate::operators::Pair;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+#[cfg(feature = "rand")]
+use crate::tree::Tree;
+
+/// Returns the complete graph on nodes `0..n`, every node joined to every
+/// other one, as any graph type, e.g. `let k5: Graph<usize> =
+/// complete_graph(5)`. Directed graphs get both directions
+pub fn complete_graph<G>(n: usize) -> G
+where
+    G: IGraph<usize> + IDiGraph<usize> + Default,
+{
+    complete_graph_from(0..n)
+}
+
+/// Same as `complete_graph` on nodes `nodes`, in that order
+pub fn complete_graph_from<T, G>(nodes: impl IntoIterator<Item = T>) -> G
+where
+    T: PartialEq + Clone,
+    G: IGraph<T> + IDiGraph<T> + Default,
+{
+    let nodes: Vec<T> = nodes.into_iter().collect();
+    let mut graph = G::default();
+    for n in nodes.iter() {
+        graph.add_node(n.clone());
+    }
+    for a in nodes.iter() {
+        for b in nodes.iter().filter(|b| *b != a) {
+            graph.add_edge(a.clone(), b.clone());
+        }
+    }
+    graph
+}
+
+/// Returns the graph on nodes `0..n` and `edges`
+fn from_edges<G>(n: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> G
+where
+    G: IGraph<usize> + IDiGraph<usize> + Default,
+{
+    let mut graph = G::default();
+    for i in 0..n {
+        graph.add_node(i);
+    }
+    graph.extend_with_edges(edges);
+    graph
+}
+
+/// Returns the path `0 - 1 - ... - n-1` as any graph type, see
+/// `complete_graph`. Directed graphs point from each node to the next one
+pub fn path_graph<G>(n: usize) -> G
+where
+    G: IGraph<usize> + IDiGraph<usize> + Default,
+{
+    from_edges(n, (1..n).map(|i| (i - 1, i)))
+}
+
+/// Same as `path_graph` with an edge from `n-1` back to `0`
+pub fn cycle_graph<G>(n: usize) -> G
+where
+    G: IGraph<usize> + IDiGraph<usize> + Default,
+{
+    from_edges(n, (0..n).map(|i| (i, (i + 1) % n)))
+}
+
+/// Returns the star with center `0` and leaves `1..=n` as any graph type,
+/// see `complete_graph`. Directed graphs point from the center to the
+/// leaves
+pub fn star_graph<G>(n: usize) -> G
+where
+    G: IGraph<usize> + IDiGraph<usize> + Default,
+{
+    from_edges(n + 1, (1..=n).map(|i| (0, i)))
+}
+
+/// Returns the `rows` x `cols` lattice with nodes `Pair(row, col)`, row by
+/// row, each joined to the nodes above, below, left and right of it and, if
+/// `diagonal`, to the 4 nodes diagonally next to it. Directed graphs get
+/// both directions
+pub fn grid_graph<G>(rows: usize, cols: usize, diagonal: bool) -> G
+where
+    G: IGraph<Pair<usize, usize>> + IDiGraph<Pair<usize, usize>> + Default,
+{
+    let mut graph = G::default();
+    for r in 0..rows {
+        for c in 0..cols {
+            graph.add_node(Pair(r, c));
+        }
+    }
+    let mut join = |a: Pair<usize, usize>, b: Pair<usize, usize>| {
+        graph.add_edge(a, b);
+        graph.add_edge(b, a);
+    };
+    for r in 0..rows {
+        for c in 0..cols {
+            if c + 1 < cols {
+                join(Pair(r, c), Pair(r, c + 1));
+            }
+            if r + 1 < rows {
+                join(Pair(r, c), Pair(r + 1, c));
+                if diagonal && c + 1 < cols {
+                    join(Pair(r, c), Pair(r + 1, c + 1));
+                }
+                if diagonal && c > 0 {
+                    join(Pair(r, c), Pair(r + 1, c - 1));
+                }
+            }
+        }
+    }
+    graph
+}
+
+/// Returns a scale-free graph on nodes `0..n` grown by preferential
+/// attachment: starting from a star on `0..=m`, every other node is joined
+/// to `m` different earlier nodes picked with probability proportional to
+/// their degree. Directed graphs point from the new nodes to the earlier
+/// ones. Fails unless `1 <= m < n`
+#[cfg(feature = "rand")]
+pub fn barabasi_albert<G>(n: usize, m: usize, mut rng: impl Rng) -> Result<G, &'static str>
+where
+    G: IGraph<usize> + IDiGraph<usize> + Default,
+{
+    if m < 1 || m >= n {
+        return Err("Parameters not correct. Expected 1 <= m < n.");
+    }
+    let mut graph: G = star_graph(m);
+    // every node once per edge it has, so picking uniformly from it picks
+    // proportionally to the degree
+    let mut repeated: Vec<usize> = (1..=m).flat_map(|i| [0, i]).collect();
+    for source in m + 1..n {
+        graph.add_node(source);
+        let mut targets = BTreeSet::new();
+        while targets.len() < m {
+            targets.insert(repeated[rng.random_range(0..repeated.len())]);
+        }
+        for t in targets {
+            graph.add_edge(source, t);
+            repeated.extend([source, t]);
+        }
+    }
+    Ok(graph)
+}
+
+/// Returns a directed acyclic graph on nodes `0..n` with an edge from `i`
+/// to every `j > i` with probability `edge_prob`, so `0..n` is a
+/// topological order. Fails unless `0 <= edge_prob <= 1`
+#[cfg(feature = "rand")]
+pub fn random_dag(
+    n: usize,
+    edge_prob: f64,
+    mut rng: impl Rng,
+) -> Result<DiGraph<usize>, &'static str> {
+    if !(0.0..=1.0).contains(&edge_prob) {
+        return Err("Parameters not correct. Expected 0 <= edge_prob <= 1.");
+    }
+    let mut graph = DiGraph::new();
+    for i in 0..n {
+        graph.add_node(i);
+    }
+    for i in 0..n {
+        for j in i + 1..n {
+            if rng.random_bool(edge_prob) {
+                graph.add_edge(i, j);
+            }
+        }
+    }
+    Ok(graph)
+}
+
+/// Returns a bipartite graph with left nodes `0..n_left` and right nodes
+/// `0..n_right` where every left node is joined to every right node with
+/// probability `p`. Fails unless `0 <= p <= 1`
+#[cfg(feature = "rand")]
+pub fn random_bipartite(
+    n_left: usize,
+    n_right: usize,
+    p: f64,
+    mut rng: impl Rng,
+) -> Result<BipartiteGraph<usize, usize>, &'static str> {
+    if !(0.0..=1.0).contains(&p) {
+        return Err("Parameters not correct. Expected 0 <= p <= 1.");
+    }
+    let mut graph = BipartiteGraph::new();
+    for l in 0..n_left {
+        graph.add_left(l);
+    }
+    for r in 0..n_right {
+        graph.add_right(r);
+    }
+    for l in 0..n_left {
+        for r in 0..n_right {
+            if rng.random_bool(p) {
+                graph.add_edge(l, r)?;
+            }
+        }
+    }
+    Ok(graph)
+}
+
+/// Returns a graph with planted communities: nodes `0..sizes[0]` form
+/// block `0`, the next `sizes[1]` ones block `1` and so on, and two nodes
+/// in blocks `a` and `b` are joined with probability `p_matrix[a][b]`.
+/// The block of every node is its `block` attribute. Fails unless
+/// `p_matrix` is a symmetric matrix of probabilities with a row per block
+#[cfg(feature = "rand")]
+pub fn stochastic_block_model(
+    sizes: &[usize],
+    p_matrix: &[Vec<f64>],
+    mut rng: impl Rng,
+) -> Result<Graph<usize>, &'static str> {
+    let k = sizes.len();
+    if p_matrix.len() != k || p_matrix.iter().any(|row| row.len() != k) {
+        return Err("Parameters not correct. Expected a row and a column per block.");
+    }
+    if p_matrix.iter().flatten().any(|p| !(0.0..=1.0).contains(p)) {
+        return Err("Parameters not correct. Expected 0 <= p <= 1.");
+    }
+    if (0..k).any(|a| (0..a).any(|b| p_matrix[a][b] != p_matrix[b][a])) {
+        return Err("Parameters not correct. Expected a symmetric matrix.");
+    }
+    let blocks: Vec<usize> = sizes
+        .iter()
+        .enumerate()
+        .flat_map(|(b, size)| std::iter::repeat_n(b, *size))
+        .collect();
+    let mut graph = Graph::new();
+    for (n, b) in blocks.iter().enumerate() {
+        graph.add_node(n);
+        graph.node_attrs_mut(n).insert("block", *b as i64);
+    }
+    for i in 0..blocks.len() {
+        for j in i + 1..blocks.len() {
+            if rng.random_bool(p_matrix[blocks[i]][blocks[j]]) {
+                graph.add_edge(i, j);
+            }
+        }
+    }
+    Ok(graph)
+}
+
+/// Returns the edges of the tree on nodes `0..n` with a random Prüfer
+/// sequence, so every labeled tree is equally likely
+#[cfg(feature = "rand")]
+fn prufer_edges(n: usize, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+    let seq: Vec<usize> = (0..n.saturating_sub(2))
+        .map(|_| rng.random_range(0..n))
+        .collect();
+    let mut degree = vec![1; n];
+    for x in seq.iter() {
+        degree[*x] += 1;
+    }
+    let mut leaves: BTreeSet<usize> = (0..n).filter(|i| degree[*i] == 1).collect();
+    let mut edges = Vec::with_capacity(n.saturating_sub(1));
+    for x in seq {
+        let leaf = leaves.pop_first().unwrap();
+        edges.push((leaf, x));
+        degree[x] -= 1;
+        if degree[x] == 1 {
+            leaves.insert(x);
+        }
+    }
+    if let (Some(a), Some(b)) = (leaves.pop_first(), leaves.pop_first()) {
+        edges.push((a, b));
+    }
+    edges
+}
+
+/// Returns a uniformly random tree on nodes `0..n`
+#[cfg(feature = "rand")]
+pub fn random_tree(n: usize, mut rng: impl Rng) -> Graph<usize> {
+    from_edges(n, prufer_edges(n, &mut rng))
+}
+
+/// Same as `random_tree` rooted at node `0`
+#[cfg(feature = "rand")]
+pub fn random_rooted_tree(n: usize, rng: impl Rng) -> Tree<usize> {
+    let graph = random_tree(n, rng);
+    let mut tree = Tree::new();
+    if n == 0 {
+        return tree;
+    }
+    tree.add_node(0);
+    let mut queue = VecDeque::from([0]);
+    while let Some(parent) = queue.pop_front() {
+        for child in graph.get_neighbors(parent) {
+            if tree.parent(&parent) != Some(child) {
+                // every node but the root is reached once, from its parent
+                let _ = tree.add_child(parent, child);
+                queue.push_back(child);
+            }
+        }
+    }
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "rand")]
+    use super::barabasi_albert;
+    use super::complete_graph;
+    use super::complete_graph_from;
+    use super::cycle_graph;
+    use super::grid_graph;
+    use super::path_graph;
+    #[cfg(feature = "rand")]
+    use super::random_bipartite;
+    #[cfg(feature = "rand")]
+    use super::random_dag;
+    #[cfg(feature = "rand")]
+    use super::random_rooted_tree;
+    #[cfg(feature = "rand")]
+    use super::random_tree;
+    use super::star_graph;
+    #[cfg(feature = "rand")]
+    use super::stochastic_block_model;
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::operators::Pair;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    #[cfg(feature = "rand")]
+    use rand::rngs::StdRng;
+    #[cfg(feature = "rand")]
+    use rand::SeedableRng;
+
+    #[test]
+    fn complete_graphs() {
+        let k4: Graph<usize> = complete_graph(4);
+        assert_eq!(k4.get_nodes(), vec![0, 1, 2, 3]);
+        assert_eq!(k4.get_neighbors(2), vec![0, 1, 3]);
+        assert!(!k4.is_directly_connected(1, 1));
+
+        let k3: DiGraph<&str> = complete_graph_from(["a", "b", "c"]);
+        assert_eq!(k3.get_neighbors("a"), vec!["b", "c"]);
+        assert_eq!(k3.get_neighbors("c"), vec!["a", "b"]);
+
+        let empty: DiGraph<usize> = complete_graph(0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn path_cycle_star() {
+        let path: DiGraph<usize> = path_graph(4);
+        assert_eq!(path.get_nodes(), vec![0, 1, 2, 3]);
+        assert_eq!(path.get_neighbors(1), vec![2]);
+        assert!(path.get_neighbors(3).is_empty());
+        let path: Graph<usize> = path_graph(4);
+        assert_eq!(path.get_neighbors(1), vec![0, 2]);
+        assert_eq!(path_graph::<Graph<usize>>(1).count_nodes(), 1);
+
+        let cycle: DiGraph<usize> = cycle_graph(4);
+        assert_eq!(cycle.get_neighbors(3), vec![0]);
+        assert!(cycle.is_connected(2, 1));
+        let cycle: Graph<usize> = cycle_graph(4);
+        assert_eq!(cycle.get_neighbors(0), vec![1, 3]);
+
+        let star: DiGraph<usize> = star_graph(3);
+        assert_eq!(star.get_neighbors(0), vec![1, 2, 3]);
+        assert!(star.get_neighbors(2).is_empty());
+        let star: Graph<usize> = star_graph(3);
+        assert_eq!(star.count_nodes(), 4);
+        assert_eq!(star.get_neighbors(2), vec![0]);
+    }
+
+    #[test]
+    fn grid() {
+        let grid: Graph<Pair<usize, usize>> = grid_graph(2, 3, false);
+        assert_eq!(grid.count_nodes(), 6);
+        assert_eq!(grid.get_nodes()[..3], [Pair(0, 0), Pair(0, 1), Pair(0, 2)]);
+        assert_eq!(
+            grid.get_neighbors(Pair(0, 1)),
+            vec![Pair(0, 0), Pair(0, 2), Pair(1, 1)]
+        );
+        assert_eq!(grid.get_neighbors(Pair(1, 2)), vec![Pair(0, 2), Pair(1, 1)]);
+
+        let grid: DiGraph<Pair<usize, usize>> = grid_graph(3, 3, true);
+        assert_eq!(grid.get_neighbors(Pair(1, 1)).len(), 8);
+        assert_eq!(
+            grid.get_neighbors(Pair(0, 0)),
+            vec![Pair(0, 1), Pair(1, 0), Pair(1, 1)]
+        );
+        assert!(grid.is_directly_connected(Pair(2, 0), Pair(1, 1)));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn barabasi_albert_graph() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let graph: Graph<usize> = barabasi_albert(200, 2, &mut rng).unwrap();
+        assert_eq!(graph.count_nodes(), 200);
+        let degrees: Vec<usize> = (0..200).map(|n| graph.get_neighbors(n).len()).collect();
+        // the star has 2 edges and every other node adds 2
+        assert_eq!(degrees.iter().sum::<usize>(), 2 * (2 + 2 * 197));
+        assert!(degrees.iter().all(|d| *d >= 2));
+        assert!(*degrees.iter().max().unwrap() > 10);
+
+        let mut again = StdRng::seed_from_u64(7);
+        let same: Graph<usize> = barabasi_albert(200, 2, &mut again).unwrap();
+        assert_eq!(same.get_neighbors(150), graph.get_neighbors(150));
+
+        let directed: DiGraph<usize> = barabasi_albert(10, 3, &mut rng).unwrap();
+        assert!((4..10).all(|n| directed.get_neighbors(n).iter().all(|t| *t < n)));
+        assert!(barabasi_albert::<Graph<usize>>(3, 3, &mut rng).is_err());
+        assert!(barabasi_albert::<Graph<usize>>(3, 0, &mut rng).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn random_dag_graph() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let dag = random_dag(50, 0.2, &mut rng).unwrap();
+        assert_eq!(dag.count_nodes(), 50);
+        assert!((0..50).all(|n| dag.get_neighbors(n).iter().all(|m| *m > n)));
+        assert!(dag.topological_generations().is_ok());
+        let edges: usize = (0..50).map(|n| dag.get_neighbors(n).len()).sum();
+        assert!(edges > 150 && edges < 350);
+
+        let full = random_dag(5, 1.0, &mut rng).unwrap();
+        assert_eq!(full.get_neighbors(0), vec![1, 2, 3, 4]);
+        assert!(random_dag(5, 0.0, &mut rng)
+            .unwrap()
+            .get_neighbors(0)
+            .is_empty());
+        assert!(random_dag(5, 1.5, &mut rng).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn random_trees() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for n in 0..30 {
+            let tree = random_tree(n, &mut rng);
+            assert_eq!(tree.count_nodes(), n);
+            let degrees: usize = (0..n).map(|i| tree.get_neighbors(i).len()).sum();
+            assert_eq!(degrees, 2 * n.saturating_sub(1));
+            assert!((1..n).all(|i| tree.is_connected(0, i)));
+        }
+
+        // the 3 labeled trees on 3 nodes, one per center, are equally likely
+        let mut centers = [0; 3];
+        for _ in 0..3000 {
+            let tree = random_tree(3, &mut rng);
+            centers[(0..3).find(|i| tree.get_neighbors(*i).len() == 2).unwrap()] += 1;
+        }
+        assert!(centers.iter().all(|c| *c > 850 && *c < 1150));
+
+        let tree = random_rooted_tree(40, &mut rng);
+        assert_eq!(tree.roots(), vec![0]);
+        assert_eq!(tree.count_nodes(), 40);
+        assert!((1..40).all(|i| tree.ancestors(&i).last() == Some(&0)));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn random_bipartite_graph() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let graph = random_bipartite(20, 30, 0.5, &mut rng).unwrap();
+        assert_eq!(graph.left_nodes().len(), 20);
+        assert_eq!(graph.right_nodes(), (0..30).collect::<Vec<usize>>());
+        assert!(graph.count_edges() > 200 && graph.count_edges() < 400);
+
+        let full = random_bipartite(3, 4, 1.0, &mut rng).unwrap();
+        assert_eq!(full.count_edges(), 12);
+        assert_eq!(full.right_neighbors(&2), vec![0, 1, 2]);
+        assert_eq!(
+            random_bipartite(3, 4, 0.0, &mut rng).unwrap().count_edges(),
+            0
+        );
+        assert!(random_bipartite(3, 4, -0.1, &mut rng).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn stochastic_block_model_graph() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let p = vec![vec![0.9, 0.02], vec![0.02, 0.8]];
+        let graph = stochastic_block_model(&[30, 20], &p, &mut rng).unwrap();
+        assert_eq!(graph.count_nodes(), 50);
+        let block = |n: usize| graph.node_attrs(&n).unwrap().get_int("block").unwrap();
+        assert_eq!((block(29), block(30)), (0, 1));
+        let (mut inside, mut across) = (0, 0);
+        for n in 0..50 {
+            for m in graph.get_neighbors(n) {
+                if block(n) == block(m) {
+                    inside += 1;
+                } else {
+                    across += 1;
+                }
+            }
+        }
+        assert!(inside > 10 * across);
+
+        let p = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        let cliques = stochastic_block_model(&[3, 3], &p, &mut rng).unwrap();
+        assert_eq!(cliques.get_neighbors(0), vec![1, 2]);
+        assert_eq!(cliques.get_neighbors(4), vec![3, 5]);
+
+        let asymmetric = vec![vec![0.5, 0.1], vec![0.2, 0.5]];
+        assert!(stochastic_block_model(&[2, 2], &asymmetric, &mut rng).is_err());
+        assert!(stochastic_block_model(&[2, 2], &[vec![0.5]], &mut rng).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn seeded_generators_are_reproducible() {
+        let seeded = || StdRng::seed_from_u64(42);
+        let edges = |graph: &Graph<usize>| -> Vec<Vec<usize>> {
+            graph
+                .get_nodes()
+                .into_iter()
+                .map(|n| graph.get_neighbors(n))
+                .collect()
+        };
+        let ba = |rng| barabasi_albert::<Graph<usize>>(60, 3, rng).unwrap();
+        assert_eq!(edges(&ba(seeded())), edges(&ba(seeded())));
+        let tree = |rng| random_tree(60, rng);
+        assert_eq!(edges(&tree(seeded())), edges(&tree(seeded())));
+        let p = vec![vec![0.5, 0.1], vec![0.1, 0.5]];
+        let sbm = |rng| stochastic_block_model(&[30, 30], &p, rng).unwrap();
+        assert_eq!(edges(&sbm(seeded())), edges(&sbm(seeded())));
+
+        let dag = |rng| random_dag(60, 0.1, rng).unwrap();
+        let (a, b) = (dag(seeded()), dag(seeded()));
+        assert!((0..60).all(|n| a.get_neighbors(n) == b.get_neighbors(n)));
+        let bip = |rng| random_bipartite(30, 30, 0.2, rng).unwrap();
+        let (a, b) = (bip(seeded()), bip(seeded()));
+        assert!((0..30).all(|l| a.left_neighbors(&l) == b.left_neighbors(&l)));
+        let rooted = |rng| random_rooted_tree(60, rng);
+        let (a, b) = (rooted(seeded()), rooted(seeded()));
+        assert!((0..60).all(|n| a.parent(&n) == b.parent(&n)));
+
+        // a borrowed generator keeps its state between calls
+        let mut rng = seeded();
+        let first = random_tree(60, &mut rng);
+        let second = random_tree(60, &mut rng);
+        assert_ne!(edges(&first), edges(&second));
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a GML representation of the graph. Nodes get the ids `0`, `1`...
+    /// and their value is stored as `label`
+    pub fn to_gml_string(&self) -> String {
+        let adj = self.adjacency();
+        write_gml(true, &adj.nodes, &adj.edges(true))
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a GML representation of the graph. Nodes get the ids `0`, `1`...
+    /// and their value is stored as `label`
+    pub fn to_gml_string(&self) -> String {
+        let adj = self.adjacency();
+        write_gml(false, &adj.nodes, &adj.edges(false))
+    }
+}
+
+/// Reads a directed graph (`directed 1`) from a GML document. Node values
+/// are taken from `label`, or from `id` when there is none, and parsed as `T`
+pub fn digraph_from_gml_str<T>(content: &str) -> Result<DiGraph<T>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let doc = read_gml(content)?;
+    if !doc.directed {
+        return Err("GML not correct. directed graph expected.");
+    }
+    let mut graph = DiGraph::<T>::new();
+    for n in doc.nodes.iter() {
+        graph.add_node(parse_value(n)?);
+    }
+    for (from, to) in doc.edges.iter() {
+        graph.add_edge(parse_value(from)?, parse_value(to)?);
+    }
+    Ok(graph)
+}
+
+/// Reads an undirected graph from a GML document. Node values are taken from
+/// `label`, or from `id` when there is none, and parsed as `T`
+pub fn graph_from_gml_str<T>(content: &str) -> Result<Graph<T>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let doc = read_gml(content)?;
+    if doc.directed {
+        return Err("GML not correct. undirected graph expected.");
+    }
+    let mut graph = Graph::<T>::new();
+    for n in doc.nodes.iter() {
+        graph.add_node(parse_value(n)?);
+    }
+    for (from, to) in doc.edges.iter() {
+        graph.add_edge(parse_value(from)?, parse_value(to)?);
+    }
+    Ok(graph)
+}
+
+fn parse_value<V: FromStr>(s: &str) -> Result<V, &'static str> {
+    V::from_str(s).map_err(|_| "GML not correct. Value can't be parsed.")
+}
+
+fn write_gml<T>(directed: bool, nodes: &[T], edges: &[(usize, usize)]) -> String
+where
+    T: std::fmt::Display,
+{
+    let mut s = String::from("graph [\n");
+    s += &format!("  directed {}\n", if directed { 1 } else { 0 });
+    for (i, n) in nodes.iter().enumerate() {
+        let label = n.to_string().replace('&', "&amp;").replace('"', "&quot;");
+        s += &format!("  node [\n    id {}\n    label \"{}\"\n  ]\n", i, label);
+    }
+    for (i, j) in edges.iter() {
+        s += &format!("  edge [\n    source {}\n    target {}\n  ]\n", i, j);
+    }
+    s += "]\n";
+    s
+}
+
+/// Value of a GML key: a number or string, or a list of key value pairs
+enum Gml {
+    Scalar(String),
+    List(Vec<(String, Gml)>),
+}
+
+impl Gml {
+    fn get(&self, key: &str) -> Option<&Gml> {
+        match self {
+            Gml::List(l) => l.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            Gml::Scalar(_) => None,
+        }
+    }
+
+    fn scalar(&self, key: &str) -> Option<&String> {
+        match self.get(key) {
+            Some(Gml::Scalar(s)) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Key(String),
+    Scalar(String),
+    Open,
+    Close,
+}
+
+fn tokens(content: &str) -> Result<Vec<Token>, &'static str> {
+    let mut ret = Vec::<Token>::new();
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '#' => while chars.next_if(|c| *c != '\n').is_some() {},
+            '[' => ret.push(Token::Open),
+            ']' => ret.push(Token::Close),
+            '"' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        None => return Err("GML not correct. Unterminated string."),
+                        Some('"') => break,
+                        Some(c) => s.push(c),
+                    }
+                }
+                let s = s
+                    .replace("&quot;", "\"")
+                    .replace("&lt;", "<")
+                    .replace("&gt;", ">")
+                    .replace("&amp;", "&");
+                ret.push(Token::Scalar(s));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut s = String::from(c);
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    s.push(c);
+                }
+                ret.push(Token::Key(s));
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => {
+                let mut s = String::from(c);
+                while let Some(c) = chars
+                    .next_if(|c| c.is_ascii_digit() || matches!(*c, '.' | 'e' | 'E' | '-' | '+'))
+                {
+                    s.push(c);
+                }
+                ret.push(Token::Scalar(s));
+            }
+            _ => return Err("GML not correct. Unexpected character."),
+        }
+    }
+    Ok(ret)
+}
+
+/// Key value pairs until the end of the input or the `]` closing the list
+fn parse_list(
+    tokens: &mut std::vec::IntoIter<Token>,
+    nested: bool,
+) -> Result<Vec<(String, Gml)>, &'static str> {
+    let mut ret = Vec::<(String, Gml)>::new();
+    loop {
+        let key = match tokens.next() {
+            None if nested => return Err("GML not correct. ] not found."),
+            None => return Ok(ret),
+            Some(Token::Close) if nested => return Ok(ret),
+            Some(Token::Key(k)) => k,
+            Some(_) => return Err("GML not correct. Key expected."),
+        };
+        let value = match tokens.next() {
+            Some(Token::Scalar(s)) => Gml::Scalar(s),
+            Some(Token::Key(s)) => Gml::Scalar(s),
+            Some(Token::Open) => Gml::List(parse_list(tokens, true)?),
+            _ => return Err("GML not correct. Value expected."),
+        };
+        ret.push((key, value));
+    }
+}
+
+/// Node values and edges (as pairs of node values) of a GML document
+struct GmlGraph {
+    directed: bool,
+    nodes: Vec<String>,
+    edges: Vec<(String, String)>,
+}
+
+/// Reads the first `graph` of a GML document
+fn read_gml(content: &str) -> Result<GmlGraph, &'static str> {
+    let mut tokens = tokens(content)?.into_iter();
+    let top = Gml::List(parse_list(&mut tokens, false)?);
+    let graph = top
+        .get("graph")
+        .ok_or("GML not correct. graph not found.")?;
+    let Gml::List(items) = graph else {
+        return Err("GML not correct. graph not found.");
+    };
+    let directed = graph.scalar("directed").is_some_and(|d| d == "1");
+
+    let mut values = BTreeMap::<String, String>::new();
+    let mut nodes = Vec::<String>::new();
+    let mut edges = Vec::<(String, String)>::new();
+    for (key, item) in items.iter() {
+        if key == "node" {
+            let id = item
+                .scalar("id")
+                .ok_or("GML not correct. node without id.")?;
+            let value = item.scalar("label").unwrap_or(id);
+            values.insert(id.clone(), value.clone());
+            nodes.push(value.clone());
+        } else if key == "edge" {
+            let source = item
+                .scalar("source")
+                .ok_or("GML not correct. edge without source.")?;
+            let target = item
+                .scalar("target")
+                .ok_or("GML not correct. edge without target.")?;
+            edges.push((source.clone(), target.clone()));
+        }
+    }
+    let edges = edges
+        .into_iter()
+        .map(|(s, t)| match (values.get(&s), values.get(&t)) {
+            (Some(s), Some(t)) => Ok((s.clone(), t.clone())),
+            _ => Err("GML not correct. edge with unknown node."),
+        })
+        .collect::<Result<Vec<(String, String)>, &'static str>>()?;
+    Ok(GmlGraph {
+        directed,
+        nodes,
+        edges,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::digraph_from_gml_str;
+    use super::graph_from_gml_str;
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn gml_round_trip() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 1..=4 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 1);
+        let s = graph.to_gml_string();
+        let read = digraph_from_gml_str::<i32>(&s).unwrap();
+        assert_eq!(read.get_nodes(), vec![1, 2, 3, 4]);
+        assert_eq!(read.get_neighbors(3), vec![1]);
+        assert!(graph_from_gml_str::<i32>(&s).is_err());
+
+        let mut graph = Graph::<String>::new();
+        graph.add_node(String::from("say \"hi\""));
+        graph.add_node(String::from("b"));
+        graph.add_edge(String::from("say \"hi\""), String::from("b"));
+        let s = graph.to_gml_string();
+        assert_eq!(s.matches("edge [").count(), 1);
+        let read = graph_from_gml_str::<String>(&s).unwrap();
+        assert_eq!(read.get_neighbors(String::from("b")), vec!["say \"hi\""]);
+    }
+
+    #[test]
+    fn gml_dataset() {
+        // Header and layout of the classic datasets (karate, netscience)
+        let content = r#"Creator "Mark Newman on Fri Jul 21 12:39:27 2006"
+# comment
+graph
+[
+  node
+  [
+    id 1
+  ]
+  node [ id 2 graphics [ x 1.5 y -2.0e1 ] ]
+  node [ id 34 label "last" value 0.25 ]
+  edge [ source 2 target 1 ]
+  edge [ source 34 target 2 weight 3 ]
+]"#;
+        let graph = graph_from_gml_str::<String>(content).unwrap();
+        assert_eq!(graph.get_nodes(), vec!["1", "2", "last"]);
+        assert_eq!(graph.get_neighbors(String::from("2")), vec!["1", "last"]);
+        assert!(digraph_from_gml_str::<String>(content).is_err());
+        assert!(graph_from_gml_str::<i32>(content).is_err());
+        assert!(graph_from_gml_str::<String>("graph [ node [ id 1 ]").is_err());
+        assert!(graph_from_gml_str::<String>("graph [ edge [ source 1 target 2 ] ]").is_err());
+    }
+}
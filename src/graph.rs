@@ -1,8 +1,13 @@
+use crate::attributes::undirected_key;
 use crate::digraph::DiGraph;
+use crate::dot::parse_dot_reader;
+use crate::dot::DotStatement;
+use crate::dot::DotStyle;
 use crate::rugraph::IDiGraph;
 use crate::rugraph::IGraph;
-use std::fs::File;
-use std::io::Write;
+use crate::rugraph::LimitedPaths;
+use std::collections::BTreeMap;
+use std::io::BufRead;
 use std::vec::Vec;
 
 /// `Graph` is a `generic` undirected graph where each node of type `T`
@@ -11,7 +16,7 @@ pub struct Graph<T>
 where
     T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
 {
-    digraph: DiGraph<T>,
+    pub(crate) digraph: DiGraph<T>,
 }
 
 impl<T> Graph<T>
@@ -23,6 +28,34 @@ where
             digraph: DiGraph::<T>::new(),
         }
     }
+
+    /// Returns the id of node `node`, see `DiGraph::index_of`
+    pub fn index_of(&self, node: &T) -> Option<usize> {
+        self.digraph.index_of(node)
+    }
+
+    /// Returns the value of the node with id `id`
+    pub fn node(&self, id: usize) -> Option<&T> {
+        self.digraph.node(id)
+    }
+
+    /// Removes, with their attributes, the edges for which `keep(a, b)`
+    /// returns `false`. `keep` is called once per edge, with its nodes in
+    /// either order
+    pub fn retain_edges(&mut self, mut keep: impl FnMut(&T, &T) -> bool) {
+        let mut decided = BTreeMap::new();
+        self.digraph.retain_edges(|a, b| {
+            *decided
+                .entry(undirected_key(a.clone(), b.clone()))
+                .or_insert_with(|| keep(a, b))
+        });
+    }
+
+    /// Returns the ids of the neighbors of the node with id `id` without
+    /// copying them. Panics if `id` is not a node id
+    pub fn neighbor_ids(&self, id: usize) -> &[usize] {
+        self.digraph.neighbor_ids(id)
+    }
 }
 
 impl<T> IGraph<T> for Graph<T>
@@ -47,18 +80,10 @@ where
             | self.digraph.is_directly_connected(to.clone(), from.clone());
     }
 
-    /// TODO: not implemented yet
-    fn to_dot_file(&self, file: &mut File, graph_name: &str) {
-        let s = self.to_dot_string(&graph_name);
-        file.write_all(s.as_bytes()).expect("Error writing file!");
-    }
-
-    /// TODO: not implemented yet
+    /// Returns a dot representation of the graph with a statement for every
+    /// node and every edge (written once), so it parses back into an equal graph
     fn to_dot_string(&self, graph_name: &str) -> String {
-        let mut s = self.digraph.to_dot_string(&graph_name);
-        s = s.replace("digraph", "graph").replace("->", "--");
-        //TODO detect a -- b .. b -- a cases
-        return s;
+        self.to_dot_string_with(graph_name, &DotStyle::new())
     }
 
     fn is_empty(&self) -> bool {
@@ -74,6 +99,15 @@ where
     }
 }
 
+impl<T> Default for Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> IDiGraph<T> for Graph<T>
 where
     T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
@@ -87,64 +121,36 @@ where
         return self.digraph.all_simple_paths(from, to);
     }
 
+    fn all_simple_paths_limited(
+        &self,
+        from: T,
+        to: T,
+        max_len: usize,
+        limit: usize,
+    ) -> LimitedPaths<T> {
+        self.digraph.all_simple_paths_limited(from, to, max_len, limit)
+    }
+
     fn get_neighbors(&self, from: T) -> Vec<T> {
         return self.digraph.get_neighbors(from);
     }
 }
 
-impl<T> Drop for Graph<T>
-where
-    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
-{
-    fn drop(&mut self) {}
+/// Returns an undirected string graph `Graph<String>` from a dot file content
+pub fn graph_from_dot_string(content: &String) -> Result<Graph<String>, &'static str> {
+    graph_from_dot_reader(content.as_bytes())
 }
 
-/// Returns a directed string graph `Graph<String>` from a dot file content
-pub fn graph_from_dot_string(content: &String) -> Result<Graph<String>, &'static str> {
+/// Returns an undirected string graph `Graph<String>` from a dot file read
+/// incrementally from `reader`, so big files don't need to be loaded in memory
+pub fn graph_from_dot_reader(reader: impl BufRead) -> Result<Graph<String>, &'static str> {
     let mut graph = Graph::<String>::new();
-    let idx1: usize;
-    let idx2: usize;
-    match content.chars().position(|c| c == '{') {
-        None => {
-            return Err("Dot file not correct. { not found.");
-        }
-        Some(i) => {
-            idx1 = i + 1;
-        }
-    }
-
-    match content.chars().position(|c| c == '}') {
-        None => {
-            return Err("Dot file not correct. } not found.");
-        }
-        Some(i) => {
-            idx2 = i - 1;
-        }
-    }
-
-    if idx2 < idx1 {
-        return Err("Dot file not correct. } before {");
-    }
-
-    let c = &content[idx1..idx2];
-    let v_c: Vec<&str> = c.split(';').collect();
-
-    for line in v_c.iter() {
-        let v_nodes: Vec<&str> = line.split("->").collect();
-        let mut prev_node = String::new();
-        for txt_node in v_nodes.iter() {
-            let txt_n = txt_node.replace(";", "");
-            let n = txt_n.trim().to_string();
-            if !n.is_empty() {
-                // println!("Adding node {}", n.clone());
-                graph.add_node(n.clone());
-            }
-            if !prev_node.is_empty() {
-                // println!("  |-> Edge {} to {}",prev_node, n);
-                graph.add_edge(prev_node.clone(), n.clone());
-            }
-            prev_node = n.clone();
-        }
+    let header = parse_dot_reader(reader, |stmt| match stmt {
+        DotStatement::Node(id) => graph.add_node(id),
+        DotStatement::Edge(from, to, _) => graph.add_edge(from, to),
+    })?;
+    if header.directed {
+        return Err("Dot file not correct. graph expected.");
     }
 
     Ok(graph)
@@ -153,7 +159,7 @@ pub fn graph_from_dot_string(content: &String) -> Result<Graph<String>, &'static
 #[cfg(test)]
 mod tests {
     use super::Graph;
-    //use crate::graph::graph_from_dot_string;
+    use crate::graph::graph_from_dot_string;
     use crate::rugraph::IDiGraph;
     use crate::rugraph::IGraph;
     use std::fs::File;
@@ -286,7 +292,9 @@ mod tests {
         graph.add_edge("b".to_string(), "c".to_string());
         graph.add_edge("c".to_string(), "d".to_string());
         graph.add_edge("a".to_string(), "d".to_string());
-        graph.to_dot_file(&mut fd, &String::from("to_dot_test"));
+        graph
+            .to_dot_file(&mut fd, &String::from("to_dot_test"))
+            .expect("error writing file");
         let s = graph.to_dot_string(&String::from("to_dot_test"));
         println!("Dot:\n{}", s);
         assert_eq!(s.is_empty(), false);
@@ -294,8 +302,8 @@ mod tests {
 
     #[test]
     fn graph_from_dot_str() {
-        /*let content =
-            String::from("digraph from_dot_str{\na -> b -> d;\nb -> c;\nc -> d;\nd;\n};\n");
+        let content =
+            String::from("graph from_dot_str{\na -- b -- d;\nb -- c;\nc -- d;\nd;\n};\n");
 
         let graph = match graph_from_dot_string(&content) {
             Ok(v) => v,
@@ -306,8 +314,30 @@ mod tests {
         };
 
         assert_eq!(graph.count_nodes(), 4);
+        assert_eq!(graph.get_neighbors(String::from("d")), vec!["b", "c"]);
         let s = graph.to_dot_string(&String::from("from_dot_str"));
         println!("{}", s);
-        //assert_eq!(s,content);*/
+        //assert_eq!(s,content);
+
+        let directed = String::from("digraph g { a -> b }");
+        assert!(graph_from_dot_string(&directed).is_err());
+    }
+
+    #[test]
+    fn graph_retain_edges() {
+        let mut graph = Graph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3), (3, 4)]);
+        graph.edge_attrs_mut(3, 2).insert("w", 1);
+        let mut calls = 0;
+        graph.retain_edges(|a, b| {
+            calls += 1;
+            a.min(b) != &2
+        });
+        assert_eq!(calls, 3);
+        assert!(graph.is_directly_connected(2, 1));
+        assert!(!graph.is_directly_connected(3, 2));
+        assert!(!graph.is_directly_connected(2, 3));
+        assert!(graph.is_directly_connected(4, 3));
+        assert!(graph.edge_attrs(&2, &3).is_none());
     }
 }
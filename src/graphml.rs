@@ -0,0 +1,634 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::vec::Vec;
+
+use crate::attributes::undirected_key;
+use crate::attributes::AttrMap;
+use crate::attributes::AttrValue;
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a GraphML representation of the graph. Nodes get the ids
+    /// `n0`, `n1`... and their value is stored in the `label` data
+    pub fn to_graphml_string(&self) -> String {
+        let adj = self.adjacency();
+        let edges: Vec<OutEdge> = adj
+            .edges(true)
+            .into_iter()
+            .map(|(i, j)| {
+                let key = (adj.nodes[i].clone(), adj.nodes[j].clone());
+                (i, j, None, self.attrs.edge(&key))
+            })
+            .collect();
+        let node_attrs: Vec<AttrMap> = adj.nodes.iter().map(|n| self.attrs.node(n)).collect();
+        write_graphml(true, &adj.nodes, &node_attrs, &edges)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a GraphML representation of the graph. Nodes get the ids
+    /// `n0`, `n1`... and their value is stored in the `label` data
+    pub fn to_graphml_string(&self) -> String {
+        let adj = self.adjacency();
+        let attributes = &self.digraph.attrs;
+        let edges: Vec<OutEdge> = adj
+            .edges(false)
+            .into_iter()
+            .map(|(i, j)| {
+                let key = undirected_key(adj.nodes[i].clone(), adj.nodes[j].clone());
+                (i, j, None, attributes.edge(&key))
+            })
+            .collect();
+        let node_attrs: Vec<AttrMap> = adj.nodes.iter().map(|n| attributes.node(n)).collect();
+        write_graphml(false, &adj.nodes, &node_attrs, &edges)
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a GraphML representation of the graph. Nodes get the ids
+    /// `n0`, `n1`... and their value is stored in the `label` data, edge
+    /// values are stored in the `label` data of the edges
+    pub fn to_graphml_string(&self) -> String {
+        let nodes = self.get_nodes();
+        let mut edges = Vec::<OutEdge>::new();
+        for (i, n) in nodes.iter().enumerate() {
+            for (m, e) in self.get_neighbors(n.clone()) {
+                let j = nodes.iter().position(|x| *x == m).unwrap();
+                let attrs = self.attrs.edge(&(n.clone(), m, e.clone()));
+                edges.push((i, j, Some(e.to_string()), attrs));
+            }
+        }
+        let node_attrs: Vec<AttrMap> = nodes.iter().map(|n| self.attrs.node(n)).collect();
+        write_graphml(true, &nodes, &node_attrs, &edges)
+    }
+}
+
+/// Reads a directed graph from a GraphML document. Node values are taken from
+/// the `label` data, or from the node id when there is none, and parsed as `T`
+pub fn digraph_from_graphml_str<T>(content: &str) -> Result<DiGraph<T>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let doc = read_graphml(content)?;
+    if doc.edges.iter().any(|e| !e.directed) {
+        return Err("GraphML not correct. directed edges expected.");
+    }
+    let mut graph = DiGraph::<T>::new();
+    for n in doc.nodes.iter() {
+        graph.add_node(parse_value(n)?);
+    }
+    for e in doc.edges.iter() {
+        graph.add_edge(parse_value(&e.source)?, parse_value(&e.target)?);
+    }
+    Ok(graph)
+}
+
+/// Reads an undirected graph from a GraphML document. Node values are taken
+/// from the `label` data, or from the node id when there is none, and parsed as `T`
+pub fn graph_from_graphml_str<T>(content: &str) -> Result<Graph<T>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let doc = read_graphml(content)?;
+    if doc.edges.iter().any(|e| e.directed) {
+        return Err("GraphML not correct. undirected edges expected.");
+    }
+    let mut graph = Graph::<T>::new();
+    for n in doc.nodes.iter() {
+        graph.add_node(parse_value(n)?);
+    }
+    for e in doc.edges.iter() {
+        graph.add_edge(parse_value(&e.source)?, parse_value(&e.target)?);
+    }
+    Ok(graph)
+}
+
+/// Reads a multi directed graph from a GraphML document. Node values are taken
+/// from the `label` data, or from the node id when there is none, and parsed
+/// as `T`. Edge values are taken from the `label` data (empty if missing) and
+/// parsed as `E`
+pub fn multidigraph_from_graphml_str<T, E>(
+    content: &str,
+) -> Result<MultiDiGraph<T, E>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let doc = read_graphml(content)?;
+    if doc.edges.iter().any(|e| !e.directed) {
+        return Err("GraphML not correct. directed edges expected.");
+    }
+    let mut graph = MultiDiGraph::<T, E>::new();
+    for n in doc.nodes.iter() {
+        graph.add_node(parse_value(n)?);
+    }
+    for e in doc.edges.iter() {
+        let label = e.label.clone().unwrap_or_default();
+        graph.add_edge(
+            parse_value(&e.source)?,
+            parse_value(&e.target)?,
+            parse_value(&label)?,
+        );
+    }
+    Ok(graph)
+}
+
+fn parse_value<V: FromStr>(s: &str) -> Result<V, &'static str> {
+    V::from_str(s).map_err(|_| "GraphML not correct. Value can't be parsed.")
+}
+
+/// GraphML `attr.type` that fits all of `values`
+fn attr_type<'a>(values: impl Iterator<Item = &'a String> + Clone) -> &'static str {
+    if values.clone().all(|v| v.parse::<i64>().is_ok()) {
+        "long"
+    } else if values.clone().all(|v| v.parse::<f64>().is_ok()) {
+        "double"
+    } else if values.clone().all(|v| v == "true" || v == "false") {
+        "boolean"
+    } else {
+        "string"
+    }
+}
+
+/// GraphML `attr.type` that fits all the values of a stored attribute
+fn attr_value_type<'a>(values: impl Iterator<Item = &'a AttrValue> + Clone) -> &'static str {
+    if values.clone().all(|v| matches!(v, AttrValue::Int(_))) {
+        "long"
+    } else if values
+        .clone()
+        .all(|v| matches!(v, AttrValue::Int(_) | AttrValue::Float(_)))
+    {
+        "double"
+    } else if values.clone().all(|v| matches!(v, AttrValue::Bool(_))) {
+        "boolean"
+    } else {
+        "string"
+    }
+}
+
+/// Source index, target index, label and stored attributes of an edge
+type OutEdge = (usize, usize, Option<String>, AttrMap);
+
+/// Ids of the `<key>` elements of the stored attributes of one domain, the
+/// `label` attribute is always written by the exporter itself
+fn attr_keys<'a>(
+    s: &mut String,
+    domain: &str,
+    maps: impl Iterator<Item = &'a AttrMap> + Clone,
+    next_id: &mut usize,
+) -> BTreeMap<String, String> {
+    let mut names = BTreeMap::<String, Vec<&AttrValue>>::new();
+    for (k, v) in maps.flat_map(|m| m.iter()) {
+        if k != "label" {
+            names.entry(k.clone()).or_default().push(v);
+        }
+    }
+    let mut ids = BTreeMap::new();
+    for (name, values) in names.iter() {
+        let id = format!("d{}", next_id);
+        *next_id += 1;
+        *s += &format!(
+            "  <key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+            id,
+            domain,
+            escape(name),
+            attr_value_type(values.iter().copied())
+        );
+        ids.insert(name.clone(), id);
+    }
+    ids
+}
+
+/// `<data>` elements of the stored attributes of a node or edge
+fn attr_data(attrs: &AttrMap, ids: &BTreeMap<String, String>) -> String {
+    attrs
+        .iter()
+        .filter_map(|(k, v)| ids.get(k).map(|id| (id, v)))
+        .map(|(id, v)| format!("<data key=\"{}\">{}</data>", id, escape(&v.to_string())))
+        .collect()
+}
+
+fn write_graphml<T>(
+    directed: bool,
+    nodes: &[T],
+    node_attrs: &[AttrMap],
+    edges: &[OutEdge],
+) -> String
+where
+    T: std::fmt::Display,
+{
+    let values: Vec<String> = nodes.iter().map(|n| n.to_string()).collect();
+    let labels: Vec<String> = edges.iter().filter_map(|e| e.2.clone()).collect();
+
+    let mut s = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    s += "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n";
+    s += &format!(
+        "  <key id=\"d0\" for=\"node\" attr.name=\"label\" attr.type=\"{}\"/>\n",
+        attr_type(values.iter())
+    );
+    if edges.iter().any(|e| e.2.is_some()) {
+        s += &format!(
+            "  <key id=\"d1\" for=\"edge\" attr.name=\"label\" attr.type=\"{}\"/>\n",
+            attr_type(labels.iter())
+        );
+    }
+    let mut next_id = 2;
+    let node_ids = attr_keys(&mut s, "node", node_attrs.iter(), &mut next_id);
+    let edge_ids = attr_keys(&mut s, "edge", edges.iter().map(|e| &e.3), &mut next_id);
+    s += &format!(
+        "  <graph id=\"G\" edgedefault=\"{}\">\n",
+        if directed { "directed" } else { "undirected" }
+    );
+    for (i, v) in values.iter().enumerate() {
+        s += &format!(
+            "    <node id=\"n{}\"><data key=\"d0\">{}</data>{}</node>\n",
+            i,
+            escape(v),
+            attr_data(&node_attrs[i], &node_ids)
+        );
+    }
+    for (i, j, label, attrs) in edges.iter() {
+        let mut data = match label {
+            None => String::new(),
+            Some(l) => format!("<data key=\"d1\">{}</data>", escape(l)),
+        };
+        data += &attr_data(attrs, &edge_ids);
+        if data.is_empty() {
+            s += &format!("    <edge source=\"n{}\" target=\"n{}\"/>\n", i, j);
+        } else {
+            s += &format!("    <edge source=\"n{}\" target=\"n{}\">", i, j);
+            s += &format!("{}</edge>\n", data);
+        }
+    }
+    s += "  </graph>\n</graphml>\n";
+    s
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Replaces the predefined entities and character references
+fn unescape(s: &str) -> Result<String, &'static str> {
+    let mut ret = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('&') {
+        ret.push_str(&rest[..i]);
+        let end = rest[i..]
+            .find(';')
+            .ok_or("GraphML not correct. Unterminated entity.")?;
+        let entity = &rest[i + 1..i + end];
+        let c = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                let code = if let Some(hex) = entity.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(dec) = entity.strip_prefix('#') {
+                    dec.parse::<u32>().ok()
+                } else {
+                    None
+                };
+                code.and_then(char::from_u32)
+            }
+        };
+        ret.push(c.ok_or("GraphML not correct. Unknown entity.")?);
+        rest = &rest[i + end + 1..];
+    }
+    ret.push_str(rest);
+    Ok(ret)
+}
+
+/// Markup found in an XML document. Names have their namespace prefix removed
+#[derive(Debug, PartialEq)]
+enum Xml {
+    Open {
+        name: String,
+        attrs: Vec<(String, String)>,
+        empty: bool,
+    },
+    Close(String),
+    Text(String),
+}
+
+fn local_name(name: &str) -> String {
+    match name.rfind(':') {
+        Some(i) => name[i + 1..].to_string(),
+        None => name.to_string(),
+    }
+}
+
+/// Splits an XML document into tags and text. Declarations, processing
+/// instructions, comments and doctypes are skipped
+fn xml_events(content: &str) -> Result<Vec<Xml>, &'static str> {
+    let mut ret = Vec::<Xml>::new();
+    let mut rest = content;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            ret.push(Xml::Text(unescape(rest)?));
+            break;
+        };
+        if start > 0 {
+            ret.push(Xml::Text(unescape(&rest[..start])?));
+        }
+        rest = &rest[start..];
+
+        let skip = [("<?", "?>"), ("<!--", "-->"), ("<!DOCTYPE", ">")];
+        if let Some((_, close)) = skip.iter().find(|(open, _)| rest.starts_with(open)) {
+            let end = rest
+                .find(close)
+                .ok_or("GraphML not correct. Unterminated markup.")?;
+            rest = &rest[end + close.len()..];
+            continue;
+        }
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata
+                .find("]]>")
+                .ok_or("GraphML not correct. Unterminated CDATA.")?;
+            ret.push(Xml::Text(cdata[..end].to_string()));
+            rest = &cdata[end + 3..];
+            continue;
+        }
+
+        let end = tag_end(rest).ok_or("GraphML not correct. Unterminated tag.")?;
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        if let Some(name) = tag.strip_prefix('/') {
+            ret.push(Xml::Close(local_name(name.trim())));
+            continue;
+        }
+        let (tag, empty) = match tag.strip_suffix('/') {
+            Some(t) => (t, true),
+            None => (tag, false),
+        };
+        let tag = tag.trim();
+        let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        ret.push(Xml::Open {
+            name: local_name(&tag[..name_end]),
+            attrs: xml_attrs(&tag[name_end..])?,
+            empty,
+        });
+    }
+    Ok(ret)
+}
+
+/// Position of the `>` closing the tag at the start of `s`, skipping quoted values
+fn tag_end(s: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `name="value"` pairs of a tag
+fn xml_attrs(s: &str) -> Result<Vec<(String, String)>, &'static str> {
+    let mut ret = Vec::<(String, String)>::new();
+    let mut rest = s.trim_start();
+    while !rest.is_empty() {
+        let eq = rest
+            .find('=')
+            .ok_or("GraphML not correct. Attribute without value.")?;
+        let name = local_name(rest[..eq].trim());
+        rest = rest[eq + 1..].trim_start();
+        let q = rest
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or("GraphML not correct. Attribute value not quoted.")?;
+        let end = rest[1..]
+            .find(q)
+            .ok_or("GraphML not correct. Unterminated attribute value.")?;
+        ret.push((name, unescape(&rest[1..end + 1])?));
+        rest = rest[end + 2..].trim_start();
+    }
+    Ok(ret)
+}
+
+fn xml_attr<'a>(attrs: &'a [(String, String)], key: &str) -> Option<&'a String> {
+    attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+struct GraphMlEdge {
+    source: String,
+    target: String,
+    directed: bool,
+    label: Option<String>,
+}
+
+/// Nodes and edges of a GraphML document, nodes given by their value
+struct GraphMlDoc {
+    nodes: Vec<String>,
+    edges: Vec<GraphMlEdge>,
+}
+
+/// Reads the nodes and edges of the first graph of a GraphML document
+fn read_graphml(content: &str) -> Result<GraphMlDoc, &'static str> {
+    let events = xml_events(content)?;
+    // Key id -> attr.name
+    let mut keys = BTreeMap::<String, String>::new();
+    let mut ids = Vec::<String>::new();
+    let mut labels = BTreeMap::<String, String>::new();
+    let mut edges = Vec::<GraphMlEdge>::new();
+    let mut directed = None;
+    let mut node = None;
+    let mut edge: Option<GraphMlEdge> = None;
+    let mut data: Option<(String, String)> = None;
+
+    for ev in events.into_iter() {
+        match ev {
+            Xml::Open { name, attrs, empty } => match name.as_str() {
+                "key" => {
+                    let id =
+                        xml_attr(&attrs, "id").ok_or("GraphML not correct. key without id.")?;
+                    let attr_name = xml_attr(&attrs, "attr.name").unwrap_or(id);
+                    keys.insert(id.clone(), attr_name.clone());
+                }
+                "graph" if directed.is_none() => {
+                    directed = Some(
+                        xml_attr(&attrs, "edgedefault").map(|e| e.as_str()) != Some("undirected"),
+                    );
+                }
+                "node" => {
+                    let id =
+                        xml_attr(&attrs, "id").ok_or("GraphML not correct. node without id.")?;
+                    if !ids.contains(id) {
+                        ids.push(id.clone());
+                    }
+                    if !empty {
+                        node = Some(id.clone());
+                    }
+                }
+                "edge" => {
+                    let source = xml_attr(&attrs, "source")
+                        .ok_or("GraphML not correct. edge without source.")?;
+                    let target = xml_attr(&attrs, "target")
+                        .ok_or("GraphML not correct. edge without target.")?;
+                    let e = GraphMlEdge {
+                        source: source.clone(),
+                        target: target.clone(),
+                        directed: match xml_attr(&attrs, "directed") {
+                            Some(d) => d == "true",
+                            None => directed.unwrap_or(true),
+                        },
+                        label: None,
+                    };
+                    if empty {
+                        edges.push(e);
+                    } else {
+                        edge = Some(e);
+                    }
+                }
+                "data" if !empty => {
+                    let key =
+                        xml_attr(&attrs, "key").ok_or("GraphML not correct. data without key.")?;
+                    data = Some((key.clone(), String::new()));
+                }
+                _ => {}
+            },
+            Xml::Text(t) => {
+                if let Some((_, text)) = data.as_mut() {
+                    text.push_str(&t);
+                }
+            }
+            Xml::Close(name) => match name.as_str() {
+                "data" => {
+                    if let Some((key, text)) = data.take() {
+                        let is_label = keys.get(&key).unwrap_or(&key) == "label";
+                        if let Some(e) = edge.as_mut() {
+                            if is_label {
+                                e.label = Some(text);
+                            }
+                        } else if let Some(n) = node.as_ref() {
+                            if is_label {
+                                labels.insert(n.clone(), text);
+                            }
+                        }
+                    }
+                }
+                "node" => node = None,
+                "edge" => edges.extend(edge.take()),
+                _ => {}
+            },
+        }
+    }
+    if directed.is_none() {
+        return Err("GraphML not correct. graph not found.");
+    }
+
+    let value = |id: &String| labels.get(id).unwrap_or(id).clone();
+    Ok(GraphMlDoc {
+        nodes: ids.iter().map(value).collect(),
+        edges: edges
+            .into_iter()
+            .map(|e| GraphMlEdge {
+                source: value(&e.source),
+                target: value(&e.target),
+                ..e
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::digraph_from_graphml_str;
+    use super::graph_from_graphml_str;
+    use super::multidigraph_from_graphml_str;
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn graphml_round_trip() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 1..=4 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 1);
+        let s = graph.to_graphml_string();
+        assert!(s.contains("attr.type=\"long\""));
+        let read = digraph_from_graphml_str::<i32>(&s).unwrap();
+        assert_eq!(read.get_nodes(), vec![1, 2, 3, 4]);
+        assert_eq!(read.get_neighbors(3), vec![1]);
+        assert!(graph_from_graphml_str::<i32>(&s).is_err());
+
+        let mut graph = Graph::<String>::new();
+        graph.add_node(String::from("a<b"));
+        graph.add_node(String::from("c"));
+        graph.add_edge(String::from("a<b"), String::from("c"));
+        let s = graph.to_graphml_string();
+        assert_eq!(s.matches("<edge ").count(), 1);
+        let read = graph_from_graphml_str::<String>(&s).unwrap();
+        assert_eq!(read.get_neighbors(String::from("c")), vec!["a<b"]);
+
+        let mut graph = MultiDiGraph::<i32, i64>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_edge(1, 2, -5);
+        graph.add_edge(1, 2, 7);
+        let s = graph.to_graphml_string();
+        assert!(s.contains("for=\"edge\" attr.name=\"label\" attr.type=\"long\""));
+        let read = multidigraph_from_graphml_str::<i32, i64>(&s).unwrap();
+        assert_eq!(read.get_neighbors(1), vec![(2, -5), (2, 7)]);
+    }
+
+    #[test]
+    fn graphml_external() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- exported by some tool -->
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns"
+    xmlns:y="http://www.yworks.com/xml/graphml">
+  <key id="name" for="node" attr.name="label" attr.type="string"/>
+  <key id="w" for="edge" attr.name="weight" attr.type="double"/>
+  <graph id="G" edgedefault="undirected">
+    <node id="n0"><data key="name">first &amp; only</data></node>
+    <node id="n1"><data key="name"><![CDATA[second]]></data></node>
+    <node id="n2"/>
+    <edge id="e0" source="n0" target="n1"><data key="w">1.0</data></edge>
+    <edge source='n1' target='n2'/>
+  </graph>
+</graphml>"#;
+        let graph = graph_from_graphml_str::<String>(content).unwrap();
+        assert_eq!(graph.get_nodes(), vec!["first & only", "second", "n2"]);
+        assert_eq!(
+            graph.get_neighbors(String::from("second")),
+            vec!["first & only", "n2"]
+        );
+        assert!(digraph_from_graphml_str::<String>(content).is_err());
+        assert!(graph_from_graphml_str::<i32>(content).is_err());
+        assert!(graph_from_graphml_str::<String>("<graphml></graphml>").is_err());
+        assert!(graph_from_graphml_str::<String>("<graphml><graph").is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+
+/// `HyperGraph` is a `generic` hypergraph where every edge connects a set of
+/// nodes of any size. Edges are identified by the index returned when they
+/// are added. Each node of type `T` must implement:
+/// `T: Ord + Clone + std::fmt::Display + std::fmt::Debug`
+pub struct HyperGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    nodes: Vec<T>,
+    index: BTreeMap<T, usize>,
+    /// Member nodes of every edge, as sorted indexes
+    edges: Vec<Vec<usize>>,
+    /// Edges every node belongs to
+    incidence: Vec<Vec<usize>>,
+}
+
+/// Node of the incidence graph of a `HyperGraph`: either one of its nodes or
+/// one of its edges
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Incidence<T> {
+    Node(T),
+    Edge(usize),
+}
+
+impl<T: fmt::Display> fmt::Display for Incidence<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Incidence::Node(n) => write!(f, "{}", n),
+            Incidence::Edge(e) => write!(f, "e{}", e),
+        }
+    }
+}
+
+impl<T> HyperGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        HyperGraph::<T> {
+            nodes: Vec::new(),
+            index: BTreeMap::new(),
+            edges: Vec::new(),
+            incidence: Vec::new(),
+        }
+    }
+
+    /// Adds a new node `elem`
+    pub fn add_node(&mut self, elem: T) {
+        if !self.index.contains_key(&elem) {
+            self.index.insert(elem.clone(), self.nodes.len());
+            self.nodes.push(elem);
+            self.incidence.push(Vec::new());
+        }
+    }
+
+    /// Returns if `node` is in the graph
+    pub fn node_exists(&self, node: &T) -> bool {
+        self.index.contains_key(node)
+    }
+
+    /// Adds a new edge connecting `members`, repeated members count once.
+    /// Returns the index of the edge, or an error if a member was not added
+    /// or there are no members
+    pub fn add_edge(&mut self, members: &[T]) -> Result<usize, &'static str> {
+        let mut set = BTreeSet::new();
+        for m in members.iter() {
+            set.insert(*self.index.get(m).ok_or("Node not found")?);
+        }
+        if set.is_empty() {
+            return Err("Edge without nodes");
+        }
+        let e = self.edges.len();
+        for i in set.iter() {
+            self.incidence[*i].push(e);
+        }
+        self.edges.push(set.into_iter().collect());
+        Ok(e)
+    }
+
+    /// Returns the member nodes of edge `edge`, `None` if there is no such edge
+    pub fn edge(&self, edge: usize) -> Option<Vec<T>> {
+        self.edges
+            .get(edge)
+            .map(|m| m.iter().map(|i| self.nodes[*i].clone()).collect())
+    }
+
+    /// Returns the member nodes of every edge, in order of addition
+    pub fn get_edges(&self) -> Vec<Vec<T>> {
+        (0..self.edges.len()).filter_map(|e| self.edge(e)).collect()
+    }
+
+    /// Returns the nodes
+    pub fn get_nodes(&self) -> Vec<T> {
+        self.nodes.clone()
+    }
+
+    /// Returns how many nodes are in the graph
+    pub fn count_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns how many edges are in the graph
+    pub fn count_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns the indexes of the edges `node` belongs to
+    pub fn incident_edges(&self, node: &T) -> Vec<usize> {
+        match self.index.get(node) {
+            Some(i) => self.incidence[*i].clone(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns how many edges `node` belongs to
+    pub fn degree(&self, node: &T) -> usize {
+        self.index.get(node).map_or(0, |i| self.incidence[*i].len())
+    }
+
+    /// Returns the nodes sharing at least one edge with `node`, sorted
+    pub fn get_neighbors(&self, node: &T) -> Vec<T> {
+        let Some(i) = self.index.get(node) else {
+            return Vec::new();
+        };
+        let set: BTreeSet<&T> = self.incidence[*i]
+            .iter()
+            .flat_map(|e| self.edges[*e].iter())
+            .filter(|j| *j != i)
+            .map(|j| &self.nodes[*j])
+            .collect();
+        set.into_iter().cloned().collect()
+    }
+
+    /// Returns if `a` and `b` share at least one edge
+    pub fn is_directly_connected(&self, a: &T, b: &T) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(i), Some(j)) => self.incidence[*i]
+                .iter()
+                .any(|e| self.edges[*e].binary_search(j).is_ok()),
+            _ => false,
+        }
+    }
+
+    /// Returns the incidence graph: a bipartite `DiGraph` with a node for
+    /// every node and every edge of the hypergraph, and arcs both ways
+    /// between each edge and its members, so reachability is kept
+    pub fn to_incidence_digraph(&self) -> DiGraph<Incidence<T>> {
+        let mut graph = DiGraph::<Incidence<T>>::new();
+        for n in self.nodes.iter() {
+            graph.add_node(Incidence::Node(n.clone()));
+        }
+        for (e, members) in self.edges.iter().enumerate() {
+            graph.add_node(Incidence::Edge(e));
+            for i in members.iter() {
+                let node = Incidence::Node(self.nodes[*i].clone());
+                graph.add_edge(node.clone(), Incidence::Edge(e));
+                graph.add_edge(Incidence::Edge(e), node);
+            }
+        }
+        graph
+    }
+}
+
+impl<T> Default for HyperGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HyperGraph;
+    use super::Incidence;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn hypergraph() {
+        let mut graph = HyperGraph::<&str>::new();
+        for n in ["ana", "bob", "eve", "joe"] {
+            graph.add_node(n);
+        }
+        assert_eq!(graph.add_edge(&["ana", "bob", "eve", "bob"]), Ok(0));
+        assert_eq!(graph.add_edge(&["eve", "joe"]), Ok(1));
+        assert!(graph.add_edge(&["ana", "max"]).is_err());
+        assert!(graph.add_edge(&[]).is_err());
+
+        assert_eq!(graph.count_edges(), 2);
+        assert_eq!(graph.edge(0), Some(vec!["ana", "bob", "eve"]));
+        assert_eq!(graph.edge(2), None);
+        assert_eq!(graph.incident_edges(&"eve"), vec![0, 1]);
+        assert_eq!(graph.degree(&"joe"), 1);
+        assert_eq!(graph.get_neighbors(&"eve"), vec!["ana", "bob", "joe"]);
+        assert!(graph.is_directly_connected(&"ana", &"eve"));
+        assert!(!graph.is_directly_connected(&"ana", &"joe"));
+
+        let incidence = graph.to_incidence_digraph();
+        assert_eq!(incidence.count_nodes(), 6);
+        assert_eq!(
+            incidence.get_neighbors(Incidence::Edge(1)),
+            vec![Incidence::Node("eve"), Incidence::Node("joe")]
+        );
+        assert!(incidence.is_connected(Incidence::Node("ana"), Incidence::Node("joe")));
+        assert_eq!(Incidence::<&str>::Edge(1).to_string(), "e1");
+    }
+}
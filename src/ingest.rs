@@ -0,0 +1,170 @@
+use futures_core::Stream;
+use std::future::poll_fn;
+use std::pin::pin;
+
+pub use crate::log::EdgeEvent;
+use crate::multidigraph::MultiDiGraph;
+
+/// `GraphIngestor` builds a `MultiDiGraph` from a stream of `EdgeEvent`s.
+/// Events are pulled one at a time and the next one is only requested once
+/// the previous one is applied, so a producer writing to a bounded channel
+/// is slowed down to the pace of the ingestor
+pub struct GraphIngestor<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    graph: MultiDiGraph<T, E>,
+    applied: usize,
+}
+
+impl<T, E> GraphIngestor<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        Self::from_graph(MultiDiGraph::new())
+    }
+
+    /// Returns an ingestor applying the events to `graph`
+    pub fn from_graph(graph: MultiDiGraph<T, E>) -> Self {
+        GraphIngestor { graph, applied: 0 }
+    }
+
+    /// Returns the graph built so far
+    pub fn graph(&self) -> &MultiDiGraph<T, E> {
+        &self.graph
+    }
+
+    /// Returns the graph built
+    pub fn into_graph(self) -> MultiDiGraph<T, E> {
+        self.graph
+    }
+
+    /// Returns how many events were applied
+    pub fn applied(&self) -> usize {
+        self.applied
+    }
+
+    /// Applies `event` to the graph, see `EdgeEvent::apply_to`
+    pub fn apply(&mut self, event: EdgeEvent<T, E>) -> Result<(), &'static str> {
+        event.apply_to(&mut self.graph)?;
+        self.applied += 1;
+        Ok(())
+    }
+
+    /// Applies every event of `stream` until it ends, returning how many were
+    /// applied. Stops at the first event that can't be applied
+    pub async fn ingest<S>(&mut self, stream: S) -> Result<usize, &'static str>
+    where
+        S: Stream<Item = EdgeEvent<T, E>>,
+    {
+        let mut stream = pin!(stream);
+        let mut count = 0;
+        while let Some(event) = poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            self.apply(event)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+impl<T, E> Default for GraphIngestor<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EdgeEvent;
+    use super::GraphIngestor;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+    use futures_core::Stream;
+    use std::future::Future;
+    use std::pin::pin;
+    use std::pin::Pin;
+    use std::task::Context;
+    use std::task::Poll;
+    use std::task::Waker;
+    use std::vec::IntoIter;
+
+    /// Stream of the events of a vector, not ready every other poll
+    struct Feed {
+        events: IntoIter<EdgeEvent<i32, char>>,
+        ready: bool,
+    }
+
+    impl Stream for Feed {
+        type Item = EdgeEvent<i32, char>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.ready = !self.ready;
+            if !self.ready {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            Poll::Ready(self.events.next())
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    fn feed(events: Vec<EdgeEvent<i32, char>>) -> Feed {
+        Feed {
+            events: events.into_iter(),
+            ready: false,
+        }
+    }
+
+    #[test]
+    fn graph_ingestor() {
+        let mut ingestor = GraphIngestor::<i32, char>::new();
+        let events = vec![
+            EdgeEvent::AddNode(1),
+            EdgeEvent::AddNode(2),
+            EdgeEvent::AddNode(3),
+            EdgeEvent::AddEdge(1, 2, 'a'),
+            EdgeEvent::AddEdge(2, 3, 'b'),
+            EdgeEvent::AddEdge(1, 3, 'c'),
+            EdgeEvent::RemoveEdge(1, 3, 'c'),
+        ];
+        assert_eq!(block_on(ingestor.ingest(feed(events))), Ok(7));
+        assert!(ingestor.graph().is_connected(1, 3));
+        assert!(!ingestor.graph().is_directly_connected(1, 3));
+
+        let events = vec![
+            EdgeEvent::RemoveNode(2),
+            EdgeEvent::AddEdge(1, 4, 'd'),
+            EdgeEvent::AddNode(5),
+        ];
+        assert_eq!(
+            block_on(ingestor.ingest(feed(events))),
+            Err("Element not found")
+        );
+        assert_eq!(ingestor.applied(), 8);
+        assert!(!ingestor.graph().node_exists(5));
+        assert_eq!(
+            ingestor.apply(EdgeEvent::RemoveEdge(1, 2, 'a')),
+            Err("Edge not found")
+        );
+
+        let graph = ingestor.into_graph();
+        assert_eq!(graph.get_nodes(), vec![1, 3]);
+        assert!(graph.get_neighbors(1).is_empty());
+    }
+}
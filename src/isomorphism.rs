@@ -0,0 +1,367 @@
+use std::collections::BTreeSet;
+use std::vec::Vec;
+
+use crate::adjacency::Adjacency;
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns `true` if both graphs have the same structure, ignoring the
+    /// node values and the order in which nodes and edges were added
+    pub fn is_isomorphic(&self, other: &Self) -> bool {
+        is_isomorphic(&self.adjacency(), &other.adjacency(), |_, _| true)
+    }
+
+    /// Like `is_isomorphic` but node `a` of `self` may only be matched with
+    /// node `b` of `other` if `node_match(a, b)` returns `true`
+    pub fn is_isomorphic_by(&self, other: &Self, node_match: impl Fn(&T, &T) -> bool) -> bool {
+        is_isomorphic(&self.adjacency(), &other.adjacency(), node_match)
+    }
+
+    /// Returns a Weisfeiler-Lehman hash of the structure of the graph.
+    /// Isomorphic graphs always get the same hash, so it can be used to bucket
+    /// graphs before running the exact `is_isomorphic` check
+    pub fn structural_hash(&self) -> u64 {
+        structural_hash(&self.adjacency())
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns `true` if both graphs have the same structure, ignoring the
+    /// node values and the order in which nodes and edges were added
+    pub fn is_isomorphic(&self, other: &Self) -> bool {
+        is_isomorphic(&self.adjacency(), &other.adjacency(), |_, _| true)
+    }
+
+    /// Like `is_isomorphic` but node `a` of `self` may only be matched with
+    /// node `b` of `other` if `node_match(a, b)` returns `true`
+    pub fn is_isomorphic_by(&self, other: &Self, node_match: impl Fn(&T, &T) -> bool) -> bool {
+        is_isomorphic(&self.adjacency(), &other.adjacency(), node_match)
+    }
+
+    /// Returns a Weisfeiler-Lehman hash of the structure of the graph.
+    /// Isomorphic graphs always get the same hash, so it can be used to bucket
+    /// graphs before running the exact `is_isomorphic` check
+    pub fn structural_hash(&self) -> u64 {
+        structural_hash(&self.adjacency())
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a Weisfeiler-Lehman hash of the structure of the graph, taking
+    /// parallel edges into account but ignoring the edge values.
+    /// Isomorphic graphs always get the same hash
+    pub fn structural_hash(&self) -> u64 {
+        structural_hash(&self.adjacency())
+    }
+}
+
+/// Successor and predecessor sets of a graph
+struct Sides {
+    succ: Vec<BTreeSet<usize>>,
+    pred: Vec<BTreeSet<usize>>,
+}
+
+impl Sides {
+    fn new<T: Ord + Clone>(adj: &Adjacency<T>) -> Self {
+        let n = adj.len();
+        let mut succ = vec![BTreeSet::<usize>::new(); n];
+        let mut pred = vec![BTreeSet::<usize>::new(); n];
+        for (i, s) in adj.succ.iter().enumerate() {
+            for j in s.iter() {
+                succ[i].insert(*j);
+                pred[*j].insert(i);
+            }
+        }
+        Sides { succ, pred }
+    }
+
+    fn degree(&self, i: usize) -> (usize, usize) {
+        (self.succ[i].len(), self.pred[i].len())
+    }
+}
+
+/// VF2 style matcher: nodes of the first graph are mapped one by one, following
+/// a connectivity preserving order, to compatible nodes of the second graph and
+/// the partial mapping is checked against the edges of the already mapped nodes
+fn is_isomorphic<T>(
+    g1: &Adjacency<T>,
+    g2: &Adjacency<T>,
+    node_match: impl Fn(&T, &T) -> bool,
+) -> bool
+where
+    T: Ord + Clone,
+{
+    let n = g1.len();
+    if n != g2.len() {
+        return false;
+    }
+    let s1 = Sides::new(g1);
+    let s2 = Sides::new(g2);
+
+    let mut d1: Vec<(usize, usize)> = (0..n).map(|i| s1.degree(i)).collect();
+    let mut d2: Vec<(usize, usize)> = (0..n).map(|i| s2.degree(i)).collect();
+    d1.sort();
+    d2.sort();
+    if d1 != d2 {
+        return false;
+    }
+
+    let order = matching_order(&s1);
+    let mut core1 = vec![usize::MAX; n];
+    let mut core2 = vec![usize::MAX; n];
+    let state = State {
+        g1,
+        g2,
+        s1: &s1,
+        s2: &s2,
+        order: &order,
+        node_match: &node_match,
+    };
+    state.extend(0, &mut core1, &mut core2)
+}
+
+/// Returns the nodes sorted so that every node (after the first one of its
+/// component) is adjacent to an earlier one, starting from the highest degree
+fn matching_order(s: &Sides) -> Vec<usize> {
+    let n = s.succ.len();
+    let mut by_degree: Vec<usize> = (0..n).collect();
+    by_degree.sort_by_key(|i| {
+        let (o, i) = s.degree(*i);
+        std::cmp::Reverse(o + i)
+    });
+
+    let mut seen = vec![false; n];
+    let mut order = Vec::<usize>::with_capacity(n);
+    for start in by_degree {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut idx = order.len();
+        order.push(start);
+        while idx < order.len() {
+            let i = order[idx];
+            idx += 1;
+            for j in s.succ[i].iter().chain(s.pred[i].iter()) {
+                if !seen[*j] {
+                    seen[*j] = true;
+                    order.push(*j);
+                }
+            }
+        }
+    }
+    order
+}
+
+struct State<'a, T, F>
+where
+    T: Ord + Clone,
+    F: Fn(&T, &T) -> bool,
+{
+    g1: &'a Adjacency<T>,
+    g2: &'a Adjacency<T>,
+    s1: &'a Sides,
+    s2: &'a Sides,
+    order: &'a [usize],
+    node_match: &'a F,
+}
+
+impl<T, F> State<'_, T, F>
+where
+    T: Ord + Clone,
+    F: Fn(&T, &T) -> bool,
+{
+    fn extend(&self, depth: usize, core1: &mut [usize], core2: &mut [usize]) -> bool {
+        if depth == self.order.len() {
+            return true;
+        }
+        let u = self.order[depth];
+        for v in 0..core2.len() {
+            if core2[v] != usize::MAX || !self.feasible(u, v, core1) {
+                continue;
+            }
+            core1[u] = v;
+            core2[v] = u;
+            if self.extend(depth + 1, core1, core2) {
+                return true;
+            }
+            core1[u] = usize::MAX;
+            core2[v] = usize::MAX;
+        }
+        false
+    }
+
+    fn feasible(&self, u: usize, v: usize, core1: &[usize]) -> bool {
+        if self.s1.degree(u) != self.s2.degree(v)
+            || self.s1.succ[u].contains(&u) != self.s2.succ[v].contains(&v)
+            || !(self.node_match)(&self.g1.nodes[u], &self.g2.nodes[v])
+        {
+            return false;
+        }
+        for (m1, m2) in core1.iter().enumerate() {
+            if *m2 == usize::MAX || m1 == u {
+                continue;
+            }
+            if self.s1.succ[u].contains(&m1) != self.s2.succ[v].contains(m2)
+                || self.s1.pred[u].contains(&m1) != self.s2.pred[v].contains(m2)
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// FNV-1a over a sequence of words, stable across platforms and releases
+fn fnv1a(words: &[u64]) -> u64 {
+    let mut h: u64 = 0xcbf29ce484222325;
+    for w in words.iter() {
+        for b in w.to_le_bytes() {
+            h ^= b as u64;
+            h = h.wrapping_mul(0x100000001b3);
+        }
+    }
+    h
+}
+
+/// Weisfeiler-Lehman refinement: every round the label of a node is replaced by
+/// the hash of its label and the sorted labels of its successors and predecessors.
+/// The histograms of labels of all the rounds are combined into the final hash
+fn structural_hash<T>(adj: &Adjacency<T>) -> u64
+where
+    T: Ord + Clone,
+{
+    let n = adj.len();
+    let pred = adj.predecessors();
+    let mut labels: Vec<u64> = (0..n)
+        .map(|i| fnv1a(&[adj.succ[i].len() as u64, pred[i].len() as u64]))
+        .collect();
+
+    let edges: usize = adj.succ.iter().map(|s| s.len()).sum();
+    let mut words = vec![n as u64, edges as u64];
+    let mut distinct = 0;
+    for _ in 0..=n {
+        let mut histogram = labels.clone();
+        histogram.sort_unstable();
+        words.push(fnv1a(&histogram));
+
+        let mut count = histogram.clone();
+        count.dedup();
+        if count.len() == distinct {
+            break;
+        }
+        distinct = count.len();
+
+        labels = (0..n)
+            .map(|i| {
+                let mut s: Vec<u64> = adj.succ[i].iter().map(|j| labels[*j]).collect();
+                let mut p: Vec<u64> = pred[i].iter().map(|j| labels[*j]).collect();
+                s.sort_unstable();
+                p.sort_unstable();
+                let mut w = vec![labels[i], s.len() as u64];
+                w.extend(s);
+                w.extend(p);
+                fnv1a(&w)
+            })
+            .collect();
+    }
+    fnv1a(&words)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    fn digraph(edges: &[(i32, i32)], nodes: &[i32]) -> DiGraph<i32> {
+        let mut graph = DiGraph::<i32>::new();
+        for n in nodes.iter() {
+            graph.add_node(*n);
+        }
+        for (a, b) in edges.iter() {
+            graph.add_edge(*a, *b);
+        }
+        graph
+    }
+
+    #[test]
+    fn isomorphic_digraphs() {
+        let g1 = digraph(&[(1, 2), (2, 3), (3, 1), (3, 4)], &[1, 2, 3, 4]);
+        let g2 = digraph(&[(30, 40), (20, 30), (10, 20), (30, 10)], &[40, 30, 20, 10]);
+        assert!(g1.is_isomorphic(&g2));
+
+        // Same shape with the tail edge reversed
+        let g3 = digraph(&[(1, 2), (2, 3), (3, 1), (4, 3)], &[1, 2, 3, 4]);
+        assert!(!g1.is_isomorphic(&g3));
+
+        assert!(g1.is_isomorphic_by(&g2, |a, b| a * 10 == *b));
+        assert!(!g1.is_isomorphic_by(&g2, |a, b| (5 - a) * 10 == *b));
+    }
+
+    #[test]
+    fn isomorphic_graphs() {
+        // Cycle C6 vs two triangles: same degrees, different structure
+        let mut g1 = Graph::<i32>::new();
+        let mut g2 = Graph::<i32>::new();
+        for n in 0..6 {
+            g1.add_node(n);
+            g2.add_node(n);
+        }
+        for n in 0..6 {
+            g1.add_edge(n, (n + 1) % 6);
+        }
+        g2.add_edge(0, 1);
+        g2.add_edge(1, 2);
+        g2.add_edge(2, 0);
+        g2.add_edge(3, 4);
+        g2.add_edge(4, 5);
+        g2.add_edge(5, 3);
+        assert!(!g1.is_isomorphic(&g2));
+
+        let mut g3 = Graph::<i32>::new();
+        for n in [5, 3, 1, 0, 2, 4] {
+            g3.add_node(n);
+        }
+        for (a, b) in [(0, 2), (2, 4), (4, 5), (5, 3), (3, 1), (1, 0)] {
+            g3.add_edge(a, b);
+        }
+        assert!(g1.is_isomorphic(&g3));
+    }
+
+    #[test]
+    fn structural_hash_invariant() {
+        let g1 = digraph(&[(1, 2), (2, 3), (3, 1), (3, 4)], &[1, 2, 3, 4]);
+        let g2 = digraph(&[(30, 40), (20, 30), (10, 20), (30, 10)], &[40, 30, 20, 10]);
+        let g3 = digraph(&[(1, 2), (2, 3), (3, 1), (4, 3)], &[1, 2, 3, 4]);
+        assert_eq!(g1.structural_hash(), g2.structural_hash());
+        assert_ne!(g1.structural_hash(), g3.structural_hash());
+
+        let mut c6 = Graph::<i32>::new();
+        let mut path = Graph::<i32>::new();
+        for n in 0..6 {
+            c6.add_node(n);
+            path.add_node(n);
+        }
+        for n in 0..6 {
+            c6.add_edge(n, (n + 1) % 6);
+        }
+        for n in 0..5 {
+            path.add_edge(n, n + 1);
+        }
+        assert_ne!(c6.structural_hash(), path.structural_hash());
+    }
+}
@@ -0,0 +1,268 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::json;
+use serde_json::Value;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + Serialize,
+{
+    /// Returns the node-link JSON representation of the graph:
+    /// `{"directed": true, "nodes": [{"id": ..}], "links": [{"source": .., "target": ..}]}`
+    pub fn to_json_string(&self) -> String {
+        let adj = self.adjacency();
+        let links: Vec<Value> = adj
+            .edges(true)
+            .into_iter()
+            .map(|(i, j)| json!({"source": adj.nodes[i], "target": adj.nodes[j]}))
+            .collect();
+        write_json(true, false, &adj.nodes, links)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + Serialize,
+{
+    /// Returns the node-link JSON representation of the graph. Every edge is
+    /// written once
+    pub fn to_json_string(&self) -> String {
+        let adj = self.adjacency();
+        let links: Vec<Value> = adj
+            .edges(false)
+            .into_iter()
+            .map(|(i, j)| json!({"source": adj.nodes[i], "target": adj.nodes[j]}))
+            .collect();
+        write_json(false, false, &adj.nodes, links)
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + Serialize,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug + Serialize,
+{
+    /// Returns the node-link JSON representation of the graph. Edge values are
+    /// written as the `label` of the links
+    pub fn to_json_string(&self) -> String {
+        let nodes = self.get_nodes();
+        let mut links = Vec::<Value>::new();
+        for n in nodes.iter() {
+            for (m, e) in self.get_neighbors(n.clone()) {
+                links.push(json!({"source": n, "target": m, "label": e}));
+            }
+        }
+        write_json(true, true, &nodes, links)
+    }
+}
+
+/// Reads a directed graph from node-link JSON
+pub fn digraph_from_json_str<T>(content: &str) -> Result<DiGraph<T>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + DeserializeOwned,
+{
+    let doc = read_json(content)?;
+    if doc.directed == Some(false) {
+        return Err("JSON not correct. directed graph expected.");
+    }
+    let mut graph = DiGraph::<T>::new();
+    for n in doc.nodes.into_iter() {
+        graph.add_node(parse_value(n)?);
+    }
+    for l in doc.links.into_iter() {
+        graph.add_edge(parse_value(l.source)?, parse_value(l.target)?);
+    }
+    Ok(graph)
+}
+
+/// Reads an undirected graph from node-link JSON
+pub fn graph_from_json_str<T>(content: &str) -> Result<Graph<T>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + DeserializeOwned,
+{
+    let doc = read_json(content)?;
+    if doc.directed == Some(true) {
+        return Err("JSON not correct. undirected graph expected.");
+    }
+    let mut graph = Graph::<T>::new();
+    for n in doc.nodes.into_iter() {
+        graph.add_node(parse_value(n)?);
+    }
+    for l in doc.links.into_iter() {
+        graph.add_edge(parse_value(l.source)?, parse_value(l.target)?);
+    }
+    Ok(graph)
+}
+
+/// Reads a multi directed graph from node-link JSON. Edge values are taken
+/// from the `label` of the links
+pub fn multidigraph_from_json_str<T, E>(content: &str) -> Result<MultiDiGraph<T, E>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + DeserializeOwned,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug + DeserializeOwned,
+{
+    let doc = read_json(content)?;
+    if doc.directed == Some(false) {
+        return Err("JSON not correct. directed graph expected.");
+    }
+    let mut graph = MultiDiGraph::<T, E>::new();
+    for n in doc.nodes.into_iter() {
+        graph.add_node(parse_value(n)?);
+    }
+    for l in doc.links.into_iter() {
+        let label = l.label.ok_or("JSON not correct. link without label.")?;
+        graph.add_edge(
+            parse_value(l.source)?,
+            parse_value(l.target)?,
+            parse_value(label)?,
+        );
+    }
+    Ok(graph)
+}
+
+fn parse_value<V: DeserializeOwned>(v: Value) -> Result<V, &'static str> {
+    serde_json::from_value(v).map_err(|_| "JSON not correct. Value can't be parsed.")
+}
+
+fn write_json<T: Serialize>(
+    directed: bool,
+    multigraph: bool,
+    nodes: &[T],
+    links: Vec<Value>,
+) -> String {
+    let nodes: Vec<Value> = nodes.iter().map(|n| json!({ "id": n })).collect();
+    json!({
+        "directed": directed,
+        "multigraph": multigraph,
+        "graph": {},
+        "nodes": nodes,
+        "links": links,
+    })
+    .to_string()
+}
+
+struct JsonLink {
+    source: Value,
+    target: Value,
+    label: Option<Value>,
+}
+
+/// Nodes and links of a node-link document, `directed` if present
+struct JsonDoc {
+    directed: Option<bool>,
+    nodes: Vec<Value>,
+    links: Vec<JsonLink>,
+}
+
+fn read_json(content: &str) -> Result<JsonDoc, &'static str> {
+    let mut v: Value = serde_json::from_str(content).map_err(|_| "JSON not correct.")?;
+    let directed = v.get("directed").and_then(|d| d.as_bool());
+    let nodes = match v.get_mut("nodes").map(Value::take) {
+        Some(Value::Array(nodes)) => nodes,
+        _ => return Err("JSON not correct. nodes not found."),
+    };
+    let links = match v.get_mut("links").map(Value::take) {
+        Some(Value::Array(links)) => links,
+        None => Vec::new(),
+        _ => return Err("JSON not correct. links is not a list."),
+    };
+
+    let nodes = nodes
+        .into_iter()
+        .map(|mut n| match n.get_mut("id").map(Value::take) {
+            Some(id) => Ok(id),
+            None => Err("JSON not correct. node without id."),
+        })
+        .collect::<Result<Vec<Value>, &'static str>>()?;
+    let links = links
+        .into_iter()
+        .map(|mut l| {
+            let source = l.get_mut("source").map(Value::take);
+            let target = l.get_mut("target").map(Value::take);
+            match (source, target) {
+                (Some(source), Some(target)) => Ok(JsonLink {
+                    source,
+                    target,
+                    label: l.get_mut("label").map(Value::take),
+                }),
+                _ => Err("JSON not correct. link without source or target."),
+            }
+        })
+        .collect::<Result<Vec<JsonLink>, &'static str>>()?;
+    Ok(JsonDoc {
+        directed,
+        nodes,
+        links,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::digraph_from_json_str;
+    use super::graph_from_json_str;
+    use super::multidigraph_from_json_str;
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn json_round_trip() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 1..=3 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        let s = graph.to_json_string();
+        assert_eq!(
+            s,
+            r#"{"directed":true,"graph":{},"links":[{"source":1,"target":2},{"source":2,"target":3}],"multigraph":false,"nodes":[{"id":1},{"id":2},{"id":3}]}"#
+        );
+        let read = digraph_from_json_str::<i32>(&s).unwrap();
+        assert_eq!(read.get_nodes(), vec![1, 2, 3]);
+        assert_eq!(read.get_neighbors(2), vec![3]);
+        assert!(graph_from_json_str::<i32>(&s).is_err());
+        assert!(digraph_from_json_str::<String>(&s).is_err());
+
+        let mut graph = Graph::<String>::new();
+        graph.add_node(String::from("a"));
+        graph.add_node(String::from("b"));
+        graph.add_edge(String::from("a"), String::from("b"));
+        let read = graph_from_json_str::<String>(&graph.to_json_string()).unwrap();
+        assert_eq!(read.get_neighbors(String::from("b")), vec!["a"]);
+
+        let mut graph = MultiDiGraph::<String, i32>::new();
+        graph.add_node(String::from("a"));
+        graph.add_node(String::from("b"));
+        graph.add_edge(String::from("a"), String::from("b"), 1);
+        graph.add_edge(String::from("a"), String::from("b"), 2);
+        let read = multidigraph_from_json_str::<String, i32>(&graph.to_json_string()).unwrap();
+        assert_eq!(
+            read.get_neighbors(String::from("a")),
+            vec![(String::from("b"), 1), (String::from("b"), 2)]
+        );
+    }
+
+    #[test]
+    fn json_errors() {
+        assert!(graph_from_json_str::<i32>("{").is_err());
+        assert!(graph_from_json_str::<i32>(r#"{"links": []}"#).is_err());
+        assert!(graph_from_json_str::<i32>(r#"{"nodes": [{"name": 1}]}"#).is_err());
+        let graph = graph_from_json_str::<i32>(
+            r#"{"nodes": [{"id": 1, "group": 2}, {"id": 2}], "links": [{"source": 1, "target": 2, "value": 3}]}"#,
+        )
+        .unwrap();
+        assert_eq!(graph.get_neighbors(2), vec![1]);
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::vec::Vec;
+
+use crate::adjacency::Adjacency;
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+
+/// Built-in ways of placing the nodes on the plane. Positions are given in
+/// units of the typical distance between neighbor nodes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layout {
+    /// Nodes evenly spaced on a circle, in insertion order
+    Circular,
+    /// Nodes on a square grid, row by row in insertion order
+    Grid,
+    /// Force directed placement (Fruchterman-Reingold) starting from the
+    /// circular layout, with the given number of iterations
+    Spring(usize),
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the position `(x, y)` of every node following `layout`
+    pub fn node_positions(&self, layout: Layout) -> BTreeMap<T, (f64, f64)> {
+        let adj = self.adjacency();
+        let pos = positions(&adj, layout);
+        adj.nodes.into_iter().zip(pos).collect()
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the position `(x, y)` of every node following `layout`
+    pub fn node_positions(&self, layout: Layout) -> BTreeMap<T, (f64, f64)> {
+        let adj = self.adjacency();
+        let pos = positions(&adj, layout);
+        adj.nodes.into_iter().zip(pos).collect()
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the position `(x, y)` of every node following `layout`
+    pub fn node_positions(&self, layout: Layout) -> BTreeMap<T, (f64, f64)> {
+        let adj = self.adjacency();
+        let pos = positions(&adj, layout);
+        adj.nodes.into_iter().zip(pos).collect()
+    }
+}
+
+/// Position of every node of the snapshot, indexed as `adj.nodes`
+pub(crate) fn positions<T>(adj: &Adjacency<T>, layout: Layout) -> Vec<(f64, f64)>
+where
+    T: Ord + Clone,
+{
+    match layout {
+        Layout::Circular => circular(adj.len()),
+        Layout::Grid => grid(adj.len()),
+        Layout::Spring(iterations) => spring(adj, iterations),
+    }
+}
+
+fn circular(n: usize) -> Vec<(f64, f64)> {
+    if n == 1 {
+        return vec![(0.0, 0.0)];
+    }
+    // Neighbor nodes on the circle are one unit apart
+    let radius = (0.5 / (PI / n as f64).sin()).max(1.0);
+    (0..n)
+        .map(|i| {
+            let angle = PI / 2.0 - 2.0 * PI * i as f64 / n as f64;
+            (radius * angle.cos(), radius * angle.sin())
+        })
+        .collect()
+}
+
+fn grid(n: usize) -> Vec<(f64, f64)> {
+    let columns = (n as f64).sqrt().ceil().max(1.0) as usize;
+    (0..n)
+        .map(|i| ((i % columns) as f64, 0.0 - (i / columns) as f64))
+        .collect()
+}
+
+fn spring<T>(adj: &Adjacency<T>, iterations: usize) -> Vec<(f64, f64)>
+where
+    T: Ord + Clone,
+{
+    let n = adj.len();
+    let mut pos = circular(n);
+    // Ideal distance between nodes
+    let k = 1.0;
+    let mut temperature = (n as f64).sqrt();
+    let cooling = temperature / (iterations as f64 + 1.0);
+
+    for _ in 0..iterations {
+        let mut disp = vec![(0.0f64, 0.0f64); n];
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let (dx, dy) = (pos[i].0 - pos[j].0, pos[i].1 - pos[j].1);
+                let dist = (dx * dx + dy * dy).sqrt().max(0.01);
+                let f = k * k / dist;
+                disp[i].0 += dx / dist * f;
+                disp[i].1 += dy / dist * f;
+                disp[j].0 -= dx / dist * f;
+                disp[j].1 -= dy / dist * f;
+            }
+        }
+        for (i, j) in adj.edges(true).into_iter().filter(|(i, j)| i != j) {
+            let (dx, dy) = (pos[i].0 - pos[j].0, pos[i].1 - pos[j].1);
+            let dist = (dx * dx + dy * dy).sqrt().max(0.01);
+            let f = dist * dist / k;
+            disp[i].0 -= dx / dist * f;
+            disp[i].1 -= dy / dist * f;
+            disp[j].0 += dx / dist * f;
+            disp[j].1 += dy / dist * f;
+        }
+        for i in 0..n {
+            let len = (disp[i].0 * disp[i].0 + disp[i].1 * disp[i].1).sqrt();
+            if len > 0.0 {
+                let step = len.min(temperature);
+                pos[i].0 += disp[i].0 / len * step;
+                pos[i].1 += disp[i].1 / len * step;
+            }
+        }
+        temperature -= cooling;
+    }
+    pos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Layout;
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+        ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+    }
+
+    #[test]
+    fn layouts() {
+        let mut graph = Graph::<i32>::new();
+        for n in 0..6 {
+            graph.add_node(n);
+        }
+        for n in 0..5 {
+            graph.add_edge(n, n + 1);
+        }
+
+        let pos = graph.node_positions(Layout::Circular);
+        assert!((distance(pos[&0], pos[&1]) - 1.0).abs() < 1e-9);
+        assert!((distance(pos[&0], (0.0, 0.0)) - distance(pos[&3], (0.0, 0.0))).abs() < 1e-9);
+
+        let pos = graph.node_positions(Layout::Grid);
+        assert_eq!(pos[&0], (0.0, 0.0));
+        assert_eq!(pos[&4], (1.0, -1.0));
+
+        // The ends of the path move apart, neighbors stay close
+        let pos = graph.node_positions(Layout::Spring(100));
+        assert!(distance(pos[&0], pos[&5]) > 2.0 * distance(pos[&0], pos[&1]));
+        for n in 0..6 {
+            assert!(pos[&n].0.is_finite() && pos[&n].1.is_finite());
+        }
+    }
+}
@@ -3,4 +3,72 @@
 pub mod rugraph;
 pub mod graph;
 pub mod digraph;
-pub mod multidigraph;
\ No newline at end of file
+pub mod multidigraph;
+pub mod multigraph;
+pub mod weightedgraph;
+pub mod stable;
+pub mod matrixgraph;
+pub mod bipartite;
+pub mod tree;
+pub mod hypergraph;
+mod adjacency;
+mod assignment;
+pub mod attributes;
+pub mod binary;
+#[cfg(feature = "capi")]
+pub mod capi;
+mod centrality;
+mod community;
+pub mod concurrent;
+mod connectivity;
+pub mod convert;
+mod cover;
+pub mod csr;
+mod dag;
+mod distance;
+pub mod dot;
+pub mod edit;
+pub mod generators;
+pub mod gml;
+pub mod graphml;
+#[cfg(feature = "async")]
+pub mod ingest;
+mod isomorphism;
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod layout;
+pub mod log;
+pub mod matrixmarket;
+pub mod memory;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod observe;
+pub mod operators;
+pub mod pajek;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod pattern;
+#[cfg(feature = "persist")]
+pub mod persist;
+#[cfg(feature = "petgraph")]
+pub mod petgraph_interop;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod query;
+mod reachability;
+#[cfg(feature = "render")]
+pub mod render;
+pub mod search;
+pub mod snapshot;
+pub mod stats;
+mod subgraph;
+pub mod temporal;
+pub mod tgf;
+mod tikz;
+mod transform;
+mod tsp;
+pub mod unionfind;
+pub mod view;
+pub mod visit;
+#[cfg(feature = "wasm")]
+pub mod wasm;
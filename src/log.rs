@@ -0,0 +1,214 @@
+use std::vec::Vec;
+
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+/// A change to a graph, as received from an event feed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EdgeEvent<T, E> {
+    AddNode(T),
+    RemoveNode(T),
+    /// Edge `from`, `to` labeled `edge`
+    AddEdge(T, T, E),
+    RemoveEdge(T, T, E),
+}
+
+impl<T, E> EdgeEvent<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Applies the event to `graph`. Adding a node or an edge that already
+    /// exists does nothing. Fails if an edge refers to a missing node or a
+    /// removed node or edge doesn't exist, leaving the graph unchanged
+    pub fn apply_to(self, graph: &mut MultiDiGraph<T, E>) -> Result<(), &'static str> {
+        match self {
+            EdgeEvent::AddNode(node) => graph.add_node(node),
+            EdgeEvent::RemoveNode(node) => {
+                if !graph.remove_node(node) {
+                    return Err("Element not found");
+                }
+            }
+            EdgeEvent::AddEdge(from, to, edge) => {
+                if !graph.node_exists(from.clone()) || !graph.node_exists(to.clone()) {
+                    return Err("Element not found");
+                }
+                graph.add_edge(from, to, edge);
+            }
+            EdgeEvent::RemoveEdge(from, to, edge) => {
+                if !graph.remove_edge(from, to, edge) {
+                    return Err("Edge not found");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `GraphLog` is a `MultiDiGraph` that records every change made to it, so
+/// the graph can be rebuilt as it was after any number of changes, and a
+/// copy in another process can be kept up to date by sending it the log
+pub struct GraphLog<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    graph: MultiDiGraph<T, E>,
+    events: Vec<EdgeEvent<T, E>>,
+}
+
+impl<T, E> GraphLog<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        GraphLog {
+            graph: MultiDiGraph::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Returns a log with the changes `events`, failing at the first one
+    /// that can't be applied
+    pub fn from_events(
+        events: impl IntoIterator<Item = EdgeEvent<T, E>>,
+    ) -> Result<Self, &'static str> {
+        let mut log = Self::new();
+        for event in events {
+            log.record(event)?;
+        }
+        Ok(log)
+    }
+
+    /// Returns the current graph
+    pub fn graph(&self) -> &MultiDiGraph<T, E> {
+        &self.graph
+    }
+
+    /// Returns the changes recorded, oldest first
+    pub fn events(&self) -> &[EdgeEvent<T, E>] {
+        &self.events
+    }
+
+    /// Returns how many changes were recorded
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Applies `event` to the graph and records it. Events that fail are
+    /// not recorded, see `EdgeEvent::apply_to`
+    pub fn record(&mut self, event: EdgeEvent<T, E>) -> Result<(), &'static str> {
+        event.clone().apply_to(&mut self.graph)?;
+        self.events.push(event);
+        Ok(())
+    }
+
+    /// Adds node `node`
+    pub fn add_node(&mut self, node: T) {
+        // adding a node can't fail
+        let _ = self.record(EdgeEvent::AddNode(node));
+    }
+
+    /// Removes node `node` and its edges. Fails if it doesn't exist
+    pub fn remove_node(&mut self, node: T) -> Result<(), &'static str> {
+        self.record(EdgeEvent::RemoveNode(node))
+    }
+
+    /// Adds an edge labeled `edge` from `from` to `to`. Fails if a node
+    /// doesn't exist
+    pub fn add_edge(&mut self, from: T, to: T, edge: E) -> Result<(), &'static str> {
+        self.record(EdgeEvent::AddEdge(from, to, edge))
+    }
+
+    /// Removes the edge labeled `edge` from `from` to `to`. Fails if it
+    /// doesn't exist
+    pub fn remove_edge(&mut self, from: T, to: T, edge: E) -> Result<(), &'static str> {
+        self.record(EdgeEvent::RemoveEdge(from, to, edge))
+    }
+
+    /// Catches up with `log`, a log this one is a prefix of (e.g. received
+    /// from the process owning the graph), applying the changes missing here.
+    /// Returns how many were applied. Fails if the logs diverged
+    pub fn apply(&mut self, log: &GraphLog<T, E>) -> Result<usize, &'static str> {
+        self.apply_events(log.events())
+    }
+
+    /// Same as `apply` with the changes `events` of a log
+    pub fn apply_events(&mut self, events: &[EdgeEvent<T, E>]) -> Result<usize, &'static str> {
+        if events.len() < self.len() || events[..self.len()] != self.events[..] {
+            return Err("Logs diverged.");
+        }
+        let missing = &events[self.len()..];
+        for event in missing {
+            self.record(event.clone())?;
+        }
+        Ok(missing.len())
+    }
+
+    /// Returns the graph as it was after the first `n` changes, the current
+    /// graph if `n` is at least the number of changes
+    pub fn replay_until(&self, n: usize) -> MultiDiGraph<T, E> {
+        let mut graph = MultiDiGraph::new();
+        for event in self.events.iter().take(n) {
+            // recorded events were applied in this order before
+            let _ = event.clone().apply_to(&mut graph);
+        }
+        graph
+    }
+}
+
+impl<T, E> Default for GraphLog<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EdgeEvent;
+    use super::GraphLog;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn graph_log() {
+        let mut log = GraphLog::<i32, char>::new();
+        for n in 1..=3 {
+            log.add_node(n);
+        }
+        log.add_edge(1, 2, 'a').unwrap();
+        log.add_edge(2, 3, 'b').unwrap();
+        assert_eq!(log.add_edge(1, 4, 'c'), Err("Element not found"));
+        log.remove_edge(1, 2, 'a').unwrap();
+        log.remove_node(3).unwrap();
+        assert_eq!(log.remove_node(3), Err("Element not found"));
+        assert_eq!(log.len(), 7);
+        assert_eq!(log.graph().get_nodes(), vec![1, 2]);
+
+        let past = log.replay_until(5);
+        assert!(past.is_connected(1, 3));
+        assert_eq!(log.replay_until(0).count_nodes(), 0);
+        assert_eq!(log.replay_until(100).get_nodes(), vec![1, 2]);
+
+        let mut copy = GraphLog::from_events(log.events()[..4].to_vec()).unwrap();
+        assert!(copy.graph().is_directly_connected(1, 2));
+        assert_eq!(copy.apply(&log), Ok(3));
+        assert_eq!(copy.graph().get_nodes(), vec![1, 2]);
+        assert!(!copy.graph().is_directly_connected(1, 2));
+        assert_eq!(copy.apply(&log), Ok(0));
+
+        copy.add_node(9);
+        log.add_node(8);
+        assert_eq!(copy.apply(&log), Err("Logs diverged."));
+        assert!(GraphLog::<i32, char>::from_events([EdgeEvent::RemoveNode(1)]).is_err());
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use crate::dot::DotStyle;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::LimitedPaths;
+use crate::rugraph::PathBounds;
+use crate::visit::VisitMap;
+
+const WORD: usize = 64;
+
+/// `MatrixGraph` is a `generic` directed graph stored as an adjacency bit
+/// matrix, meant for dense small-to-medium graphs: `is_directly_connected`,
+/// `add_edge` and `remove_edge` don't depend on the node degrees, and
+/// `complement` and `transpose` work on whole words. Each node of type `T`
+/// must implement: `T: Ord + Clone + std::fmt::Display + std::fmt::Debug`
+#[derive(Clone)]
+pub struct MatrixGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    nodes: Vec<T>,
+    index: BTreeMap<T, usize>,
+    /// Row `i` has bit `j` set when there is an edge `i -> j`
+    rows: Vec<Vec<u64>>,
+}
+
+impl<T> MatrixGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        MatrixGraph::<T> {
+            nodes: Vec::new(),
+            index: BTreeMap::new(),
+            rows: Vec::new(),
+        }
+    }
+
+    fn words(&self) -> usize {
+        self.nodes.len().div_ceil(WORD)
+    }
+
+    fn has(&self, i: usize, j: usize) -> bool {
+        self.rows[i][j / WORD] & (1 << (j % WORD)) != 0
+    }
+
+    fn indexes(&self, from: &T, to: &T) -> Option<(usize, usize)> {
+        Some((*self.index.get(from)?, *self.index.get(to)?))
+    }
+
+    /// Successor indexes of node `i`, in index order
+    fn successors(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.nodes.len()).filter(move |j| self.has(i, *j))
+    }
+
+    /// Removes the edge from `from` to `to`. Returns `false` if there is no
+    /// such edge
+    pub fn remove_edge(&mut self, from: T, to: T) -> bool {
+        match self.indexes(&from, &to) {
+            Some((i, j)) if self.has(i, j) => {
+                self.rows[i][j / WORD] &= !(1 << (j % WORD));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns how many edges are in the graph
+    pub fn count_edges(&self) -> usize {
+        self.rows
+            .iter()
+            .flatten()
+            .map(|w| w.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns a graph with the same nodes and an edge `a -> b` for every
+    /// pair of distinct nodes not joined by an edge here
+    pub fn complement(&self) -> MatrixGraph<T> {
+        let mut ret = self.clone();
+        let n = self.nodes.len();
+        for (i, row) in ret.rows.iter_mut().enumerate() {
+            for (k, w) in row.iter_mut().enumerate() {
+                *w = !*w;
+                // Bits past the last node stay clear
+                let valid = n - k * WORD;
+                if valid < WORD {
+                    *w &= (1 << valid) - 1;
+                }
+            }
+            row[i / WORD] &= !(1 << (i % WORD));
+        }
+        ret
+    }
+
+    /// Returns a graph with the same nodes and every edge reversed
+    pub fn transpose(&self) -> MatrixGraph<T> {
+        let mut ret = self.clone();
+        for row in ret.rows.iter_mut() {
+            row.fill(0);
+        }
+        for i in 0..self.nodes.len() {
+            for j in self.successors(i) {
+                ret.rows[j][i / WORD] |= 1 << (i % WORD);
+            }
+        }
+        ret
+    }
+
+    fn dfs(
+        &self,
+        from: usize,
+        to: usize,
+        bounds: &PathBounds,
+        paths: &mut Vec<Vec<T>>,
+        path: &mut Vec<usize>,
+    ) {
+        if path.contains(&from) {
+            return;
+        }
+        path.push(from);
+        if from == to {
+            paths.push(path.iter().map(|i| self.nodes[*i].clone()).collect());
+        } else if path.len() <= bounds.max_len {
+            for j in self.successors(from) {
+                if bounds.done(paths) {
+                    break;
+                }
+                self.dfs(j, to, bounds, paths, path);
+            }
+        }
+        path.pop();
+    }
+}
+
+impl<T> Default for MatrixGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> IGraph<T> for MatrixGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn add_node(&mut self, elem: T) {
+        if self.index.contains_key(&elem) {
+            return;
+        }
+        let grow = self.nodes.len().is_multiple_of(WORD);
+        self.index.insert(elem.clone(), self.nodes.len());
+        self.nodes.push(elem);
+        if grow {
+            for row in self.rows.iter_mut() {
+                row.push(0);
+            }
+        }
+        self.rows.push(vec![0; self.words()]);
+    }
+
+    fn node_exists(&self, node: T) -> bool {
+        self.index.contains_key(&node)
+    }
+
+    fn is_connected(&self, from: T, to: T) -> bool {
+        let (i, j) = match self.indexes(&from, &to) {
+            Some(v) => v,
+            None => return false,
+        };
+        let mut seen = VisitMap::new(self.nodes.len());
+        let mut to_process = vec![i];
+        while let Some(k) = to_process.pop() {
+            for m in self.successors(k) {
+                if m == j {
+                    return true;
+                }
+                if seen.visit(m) {
+                    to_process.push(m);
+                }
+            }
+        }
+        false
+    }
+
+    fn is_directly_connected(&self, from: T, to: T) -> bool {
+        self.indexes(&from, &to)
+            .is_some_and(|(i, j)| self.has(i, j))
+    }
+
+    /// Returns a dot representation of the graph with a statement for every
+    /// node and every edge, so it parses back into an equal graph
+    fn to_dot_string(&self, graph_name: &str) -> String {
+        self.to_dot_string_with(graph_name, &DotStyle::new())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn count_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn get_nodes(&self) -> Vec<T> {
+        self.nodes.clone()
+    }
+}
+
+impl<T> IDiGraph<T> for MatrixGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn add_edge(&mut self, from: T, to: T) {
+        if let Some((i, j)) = self.indexes(&from, &to) {
+            self.rows[i][j / WORD] |= 1 << (j % WORD);
+        }
+    }
+
+    fn all_simple_paths(&self, from: T, to: T) -> Vec<Vec<T>> {
+        self.all_simple_paths_with_cutoff(from, to, usize::MAX)
+    }
+
+    fn all_simple_paths_limited(
+        &self,
+        from: T,
+        to: T,
+        max_len: usize,
+        limit: usize,
+    ) -> LimitedPaths<T> {
+        let mut ret = Vec::<Vec<T>>::new();
+        let bounds = PathBounds { max_len, limit };
+        if let Some((i, j)) = self.indexes(&from, &to) {
+            self.dfs(i, j, &bounds, &mut ret, &mut Vec::new());
+        }
+        bounds.truncate(ret)
+    }
+
+    /// Returns the successors of `from` in insertion order of the nodes
+    fn get_neighbors(&self, from: T) -> Vec<T> {
+        match self.index.get(&from) {
+            Some(i) => self.successors(*i).map(|j| self.nodes[j].clone()).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MatrixGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn matrix_graph() {
+        let mut graph = MatrixGraph::<i32>::new();
+        // More than one word per row
+        for n in 0..70 {
+            graph.add_node(n);
+        }
+        graph.add_node(3);
+        assert_eq!(graph.count_nodes(), 70);
+        graph.add_edge(0, 69);
+        graph.add_edge(69, 1);
+        graph.add_edge(0, 1);
+        graph.add_edge(5, 99);
+        assert!(graph.is_directly_connected(0, 69));
+        assert!(!graph.is_directly_connected(69, 0));
+        assert_eq!(graph.get_neighbors(0), vec![1, 69]);
+        assert!(graph.is_connected(69, 1));
+        assert!(!graph.is_connected(1, 0));
+        assert_eq!(
+            graph.all_simple_paths(0, 1),
+            vec![vec![0, 1], vec![0, 69, 1]]
+        );
+        assert_eq!(graph.count_edges(), 3);
+
+        let transposed = graph.transpose();
+        assert_eq!(transposed.get_neighbors(1), vec![0, 69]);
+        assert_eq!(transposed.count_edges(), 3);
+
+        let complement = graph.complement();
+        assert_eq!(complement.count_edges(), 70 * 69 - 3);
+        assert!(!complement.is_directly_connected(0, 0));
+        assert!(!complement.is_directly_connected(0, 69));
+        assert!(complement.is_directly_connected(69, 0));
+
+        assert!(graph.remove_edge(0, 69));
+        assert!(!graph.remove_edge(0, 69));
+        assert_eq!(graph.all_simple_paths(0, 1), vec![vec![0, 1]]);
+    }
+}
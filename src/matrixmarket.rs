@@ -0,0 +1,267 @@
+use std::io::BufRead;
+use std::str::FromStr;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+/// Reads a directed graph from a Matrix Market coordinate file. Entry `(i, j)`
+/// becomes the edge `i -> j` between nodes `1..=max(rows, columns)`; in
+/// symmetric matrices the edge `j -> i` is added too
+pub fn digraph_from_matrix_market<R: BufRead>(reader: R) -> Result<DiGraph<usize>, &'static str> {
+    let mm = read_matrix_market(reader)?;
+    let edges = mm.entries.len() * if mm.symmetric { 2 } else { 1 };
+    let mut graph = DiGraph::<usize>::with_capacity(mm.size, edges);
+    for n in 1..=mm.size {
+        graph.add_node(n);
+    }
+    for (i, j, _) in mm.entries.into_iter() {
+        graph.add_edge(i, j);
+        if mm.symmetric {
+            graph.add_edge(j, i);
+        }
+    }
+    Ok(graph)
+}
+
+/// Reads an undirected graph from a Matrix Market coordinate file. Entry
+/// `(i, j)` becomes the edge `i -- j` between nodes `1..=max(rows, columns)`
+pub fn graph_from_matrix_market<R: BufRead>(reader: R) -> Result<Graph<usize>, &'static str> {
+    let mm = read_matrix_market(reader)?;
+    let mut graph = Graph::<usize>::new();
+    for n in 1..=mm.size {
+        graph.add_node(n);
+    }
+    for (i, j, _) in mm.entries.into_iter() {
+        graph.add_edge(i, j);
+    }
+    Ok(graph)
+}
+
+/// Reads a weighted directed graph from a Matrix Market coordinate file.
+/// Entry `(i, j)` becomes an edge `i -> j` whose value is the entry parsed as
+/// `W` (`1` for `pattern` matrices, `"re im"` for `complex` ones); in
+/// symmetric matrices the edge `j -> i` is added too (with the value negated
+/// in skew-symmetric ones and conjugated in hermitian ones)
+pub fn multidigraph_from_matrix_market<R, W>(
+    reader: R,
+) -> Result<MultiDiGraph<usize, W>, &'static str>
+where
+    R: BufRead,
+    W: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let mm = read_matrix_market(reader)?;
+    let mut graph = MultiDiGraph::<usize, W>::new();
+    for n in 1..=mm.size {
+        graph.add_node(n);
+    }
+    for (i, j, value) in mm.entries.into_iter() {
+        if mm.symmetric && i != j {
+            let mirrored = if mm.skew {
+                negate(&value)
+            } else if mm.hermitian {
+                conjugate(&value)
+            } else {
+                value.clone()
+            };
+            graph.add_edge(j, i, parse_value(&mirrored)?);
+        }
+        graph.add_edge(i, j, parse_value(&value)?);
+    }
+    Ok(graph)
+}
+
+fn parse_value<V: FromStr>(s: &str) -> Result<V, &'static str> {
+    V::from_str(s).map_err(|_| "Matrix Market file not correct. Value can't be parsed.")
+}
+
+fn negate(value: &str) -> String {
+    value
+        .split_whitespace()
+        .map(|v| match v.strip_prefix('-') {
+            Some(positive) => positive.to_string(),
+            None => format!("-{}", v),
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// `"re im"` becomes `"re -im"`, real values are unchanged
+fn conjugate(value: &str) -> String {
+    match value.split_once(char::is_whitespace) {
+        Some((re, im)) => format!("{} {}", re, negate(im)),
+        None => value.to_string(),
+    }
+}
+
+/// Entries `(row, column, value)` of a sparse matrix, 1 based
+struct MatrixMarket {
+    size: usize,
+    symmetric: bool,
+    skew: bool,
+    hermitian: bool,
+    entries: Vec<(usize, usize, String)>,
+}
+
+fn read_matrix_market<R: BufRead>(reader: R) -> Result<MatrixMarket, &'static str> {
+    let mut lines = reader.lines();
+    let header = match lines.next() {
+        Some(Ok(h)) => h.to_lowercase(),
+        _ => return Err("Matrix Market file not correct. Header not found."),
+    };
+    let header: Vec<&str> = header.split_whitespace().collect();
+    if header.len() != 5 || header[0] != "%%matrixmarket" || header[1] != "matrix" {
+        return Err("Matrix Market file not correct. Header not found.");
+    }
+    if header[2] != "coordinate" {
+        return Err("Matrix Market file not correct. Only coordinate matrices are supported.");
+    }
+    let pattern = header[3] == "pattern";
+    let (symmetric, skew, hermitian) = match header[4] {
+        "general" => (false, false, false),
+        "symmetric" => (true, false, false),
+        "skew-symmetric" => (true, true, false),
+        "hermitian" => (true, false, true),
+        _ => return Err("Matrix Market file not correct. Unknown symmetry."),
+    };
+
+    let mut mm = MatrixMarket {
+        size: 0,
+        symmetric,
+        skew,
+        hermitian,
+        entries: Vec::new(),
+    };
+    let mut rows = 0;
+    let mut columns = 0;
+    let mut nonzeros = None;
+    for line in lines {
+        let line = line.map_err(|_| "Matrix Market file not correct. Read error.")?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let numbers: Vec<usize> = words
+            .iter()
+            .take(3)
+            .filter_map(|w| w.parse::<usize>().ok())
+            .collect();
+        if nonzeros.is_none() {
+            if words.len() != 3 || numbers.len() != 3 {
+                return Err("Matrix Market file not correct. Size line expected.");
+            }
+            (rows, columns) = (numbers[0], numbers[1]);
+            mm.size = rows.max(columns);
+            nonzeros = Some(numbers[2]);
+            continue;
+        }
+        if numbers.len() < 2 || (!pattern && words.len() < 3) {
+            return Err("Matrix Market file not correct. Entry expected.");
+        }
+        let (i, j) = (numbers[0], numbers[1]);
+        if i == 0 || j == 0 || i > rows || j > columns {
+            return Err("Matrix Market file not correct. Entry out of range.");
+        }
+        let value = if pattern {
+            String::from("1")
+        } else {
+            words[2..].join(" ")
+        };
+        mm.entries.push((i, j, value));
+    }
+    match nonzeros {
+        None => Err("Matrix Market file not correct. Size line expected."),
+        Some(n) if n != mm.entries.len() => {
+            Err("Matrix Market file not correct. Wrong number of entries.")
+        }
+        Some(_) => Ok(mm),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::digraph_from_matrix_market;
+    use super::graph_from_matrix_market;
+    use super::multidigraph_from_matrix_market;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn matrix_market_general() {
+        let content = "%%MatrixMarket matrix coordinate integer general
+% a comment
+3 4 3
+1 2 5
+3 1 -2
+2 4 7
+";
+        let graph = digraph_from_matrix_market(content.as_bytes()).unwrap();
+        assert_eq!(graph.get_nodes(), vec![1, 2, 3, 4]);
+        assert_eq!(graph.get_neighbors(3), vec![1]);
+        assert!(graph.get_neighbors(1).contains(&2));
+        assert!(!graph.get_neighbors(2).contains(&1));
+
+        let graph = multidigraph_from_matrix_market::<_, i64>(content.as_bytes()).unwrap();
+        assert_eq!(graph.get_neighbors(3), vec![(1, -2)]);
+        assert_eq!(graph.get_neighbors(2), vec![(4, 7)]);
+    }
+
+    #[test]
+    fn matrix_market_symmetric() {
+        let content = "%%MatrixMarket matrix coordinate pattern symmetric
+3 3 2
+2 1
+3 3
+";
+        let graph = digraph_from_matrix_market(content.as_bytes()).unwrap();
+        assert_eq!(graph.get_neighbors(1), vec![2]);
+        assert_eq!(graph.get_neighbors(2), vec![1]);
+        let graph = graph_from_matrix_market(content.as_bytes()).unwrap();
+        assert_eq!(graph.get_neighbors(3), vec![3]);
+
+        let content = "%%MatrixMarket matrix coordinate integer skew-symmetric
+2 2 1
+2 1 4
+";
+        let graph = multidigraph_from_matrix_market::<_, i32>(content.as_bytes()).unwrap();
+        assert_eq!(graph.get_neighbors(1), vec![(2, -4)]);
+        assert_eq!(graph.get_neighbors(2), vec![(1, 4)]);
+
+        let content = "%%MatrixMarket matrix coordinate complex hermitian
+3 3 3
+1 1 2.0 0.0
+2 1 1.5 -3.0
+3 2 0.5 2.0
+";
+        let graph = multidigraph_from_matrix_market::<_, String>(content.as_bytes()).unwrap();
+        assert_eq!(
+            graph.get_neighbors(1),
+            vec![(1, "2.0 0.0".to_string()), (2, "1.5 3.0".to_string())]
+        );
+        assert_eq!(
+            graph.get_neighbors(2),
+            vec![(1, "1.5 -3.0".to_string()), (3, "0.5 -2.0".to_string())]
+        );
+        assert_eq!(graph.get_neighbors(3), vec![(2, "0.5 2.0".to_string())]);
+    }
+
+    #[test]
+    fn matrix_market_errors() {
+        let bad = [
+            "",
+            "%%MatrixMarket matrix array real general\n2 2\n1.0\n",
+            "%%MatrixMarket matrix coordinate real general\n2 2 1\n",
+            "%%MatrixMarket matrix coordinate real general\n2 2 1\n3 1 1.0\n",
+            "%%MatrixMarket matrix coordinate real general\n2 2 1\n1 1\n",
+        ];
+        for content in bad.iter() {
+            assert!(digraph_from_matrix_market(content.as_bytes()).is_err());
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+use std::mem::size_of;
+
+use crate::graph::Graph;
+
+/// Estimated memory used by a graph returned by `memory_usage()`. Only the
+/// memory owned by the graph is counted: heap data owned by the node and edge
+/// values themselves (e.g. the contents of a `String`) is not
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryReport {
+    /// Bytes of the node arena, including the node values and the neighbor
+    /// lists short enough to be stored inline
+    pub nodes: usize,
+    /// Bytes of the neighbor lists stored out of line
+    pub adjacency: usize,
+    /// Bytes of the lookup index, with its copy of every node value
+    pub payloads: usize,
+}
+
+impl MemoryReport {
+    /// Returns the total number of bytes
+    pub fn total(&self) -> usize {
+        self.nodes + self.adjacency + self.payloads
+    }
+}
+
+impl fmt::Display for MemoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "nodes: {} B, adjacency: {} B, payloads: {} B, total: {} B",
+            self.nodes,
+            self.adjacency,
+            self.payloads,
+            self.total()
+        )
+    }
+}
+
+/// Estimated bytes of a `BTreeMap<K, V>` with `len` entries, assuming its
+/// nodes are two thirds full
+pub(crate) fn btree_map_bytes<K, V>(len: usize) -> usize {
+    len * (size_of::<K>() + size_of::<V>()) * 3 / 2
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns an estimate of the memory used by the graph
+    pub fn memory_usage(&self) -> MemoryReport {
+        self.digraph.memory_usage()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn memory_usage() {
+        let mut graph = DiGraph::<u64>::with_capacity(100, 0);
+        let empty = graph.memory_usage();
+        assert_eq!(empty.adjacency, 0);
+        assert_eq!(empty.payloads, 0);
+        assert!(empty.nodes > 0);
+
+        for n in 0..100 {
+            graph.add_node(n);
+        }
+        graph.add_edge(0, 1);
+        let small = graph.memory_usage();
+        assert_eq!(small.nodes, empty.nodes);
+        assert_eq!(small.adjacency, 0);
+        assert!(small.payloads >= 100 * 16);
+
+        for n in 1..100 {
+            graph.add_edge(0, n);
+        }
+        let big = graph.memory_usage();
+        assert!(big.adjacency >= 99 * 8);
+        assert_eq!(big.total(), big.nodes + big.adjacency + big.payloads);
+        assert!(big.to_string().starts_with("nodes: "));
+
+        let mut graph = Graph::<u64>::new();
+        graph.add_node(1);
+        assert!(graph.memory_usage().total() > 0);
+
+        let mut graph = MultiDiGraph::<u64, u64>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        let before = graph.memory_usage();
+        for e in 0..10 {
+            graph.add_edge(1, 2, e);
+        }
+        assert!(graph.memory_usage().adjacency >= before.adjacency + 10 * 16);
+    }
+}
@@ -0,0 +1,434 @@
+use memmap2::Mmap;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::vec::Vec;
+
+use crate::csr::CsrGraph;
+
+/// First bytes of every file
+const MAGIC: &[u8; 8] = b"RUGRCSR\0";
+/// Version of the layout, increased on incompatible changes
+const VERSION: u64 = 1;
+/// Bytes of the header: magic, version, node count, edge count, weighted
+/// flag and bytes of node text
+const HEADER: usize = 48;
+/// Panic message of the queries reading an inconsistent section
+const CORRUPTED: &str = "Graph file corrupted.";
+
+/// Position of every section of a file, all numbers are little endian:
+/// `offsets` holds `n + 1` `u64` (the successors of node `i` are the
+/// targets `offsets[i]..offsets[i + 1]`), `targets` `m` `u32`, `weights`
+/// `m` `f64` in weighted files, `labels` `n + 1` `u64` positions of the text
+/// of every node in `text`, and `sorted` the `n` node indexes as `u32`
+/// sorted by text, for lookups
+#[derive(Debug, Clone, Copy)]
+struct Layout {
+    n: usize,
+    m: usize,
+    offsets: usize,
+    targets: usize,
+    weights: Option<usize>,
+    labels: usize,
+    sorted: usize,
+    text: usize,
+    len: usize,
+}
+
+impl Layout {
+    fn new(n: usize, m: usize, weighted: bool, text_len: usize) -> Option<Self> {
+        let offsets = HEADER;
+        let targets = offsets.checked_add(n.checked_add(1)?.checked_mul(8)?)?;
+        let mut labels = targets.checked_add(m.checked_mul(4)?)?;
+        let weights = if weighted {
+            let w = labels;
+            labels = labels.checked_add(m.checked_mul(8)?)?;
+            Some(w)
+        } else {
+            None
+        };
+        let sorted = labels.checked_add((n + 1).checked_mul(8)?)?;
+        let text = sorted.checked_add(n.checked_mul(4)?)?;
+        Some(Layout {
+            n,
+            m,
+            offsets,
+            targets,
+            weights,
+            labels,
+            sorted,
+            text,
+            len: text.checked_add(text_len)?,
+        })
+    }
+}
+
+/// Read-only directed graph in compressed sparse row layout read straight
+/// from a memory mapped file written by `CsrGraph::save_mmap`, so opening
+/// it costs the same for any size: nothing is parsed or copied, the pages
+/// are loaded by the operating system as queries touch them. Nodes are
+/// addressed by their index and named by their text. Only the header is
+/// checked when opening, a query reading a corrupted section panics
+pub struct MmapCsrGraph {
+    map: Mmap,
+    layout: Layout,
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+impl MmapCsrGraph {
+    /// Maps the graph file `path`. Fails if it is not a graph file or its
+    /// sizes don't match its header; the sections themselves are checked as
+    /// queries read them. The file must not be modified while it is mapped
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only; the caller doesn't modify the file
+        let map = unsafe { Mmap::map(&file)? };
+        if map.len() < HEADER || &map[..8] != MAGIC {
+            return Err(invalid("Not a graph file."));
+        }
+        let header = |i: usize| u64::from_le_bytes(map[8 * i..8 * i + 8].try_into().unwrap());
+        if header(1) != VERSION {
+            return Err(invalid("Graph file version not supported."));
+        }
+        let layout = usize::try_from(header(2))
+            .ok()
+            .zip(usize::try_from(header(3)).ok())
+            .zip(usize::try_from(header(5)).ok())
+            .and_then(|((n, m), text)| Layout::new(n, m, header(4) != 0, text))
+            .filter(|l| l.len == map.len() && l.n <= u32::MAX as usize)
+            .ok_or_else(|| invalid("Graph file truncated."))?;
+        let graph = MmapCsrGraph { map, layout };
+        let (n, m) = (layout.n, layout.m);
+        if graph.u64_at(layout.offsets) != 0
+            || graph.u64_at(layout.offsets + 8 * n) != m
+            || graph.u64_at(layout.labels) != 0
+            || graph.u64_at(layout.labels + 8 * n) != layout.len - layout.text
+        {
+            return Err(invalid(CORRUPTED));
+        }
+        Ok(graph)
+    }
+
+    fn u64_at(&self, pos: usize) -> usize {
+        u64::from_le_bytes(self.map[pos..pos + 8].try_into().unwrap()) as usize
+    }
+
+    fn u32_at(&self, pos: usize) -> usize {
+        u32::from_le_bytes(self.map[pos..pos + 4].try_into().unwrap()) as usize
+    }
+
+    /// Returns how many nodes are in the graph
+    pub fn count_nodes(&self) -> usize {
+        self.layout.n
+    }
+
+    /// Returns how many edges are in the graph
+    pub fn count_edges(&self) -> usize {
+        self.layout.m
+    }
+
+    /// Returns if the edges have weights
+    pub fn is_weighted(&self) -> bool {
+        self.layout.weights.is_some()
+    }
+
+    /// Returns the text of node `i`
+    pub fn node(&self, i: usize) -> &str {
+        let start = self.u64_at(self.layout.labels + 8 * i);
+        let end = self.u64_at(self.layout.labels + 8 * (i + 1));
+        let text = self.layout.text;
+        assert!(
+            start <= end && end <= self.layout.len - text,
+            "{}",
+            CORRUPTED
+        );
+        std::str::from_utf8(&self.map[text + start..text + end]).expect(CORRUPTED)
+    }
+
+    /// Returns the index of the node with text `node`
+    pub fn index_of(&self, node: &str) -> Option<usize> {
+        let sorted = |k: usize| {
+            let i = self.u32_at(self.layout.sorted + 4 * k);
+            assert!(i < self.layout.n, "{}", CORRUPTED);
+            i
+        };
+        let (mut lo, mut hi) = (0, self.layout.n);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            match self.node(sorted(mid)).cmp(node) {
+                Ordering::Less => lo = mid + 1,
+                Ordering::Greater => hi = mid,
+                Ordering::Equal => return Some(sorted(mid)),
+            }
+        }
+        None
+    }
+
+    /// Returns the range of edge positions of the successors of node `i`
+    fn edge_range(&self, i: usize) -> std::ops::Range<usize> {
+        let range = self.u64_at(self.layout.offsets + 8 * i)
+            ..self.u64_at(self.layout.offsets + 8 * (i + 1));
+        assert!(
+            range.start <= range.end && range.end <= self.layout.m,
+            "{}",
+            CORRUPTED
+        );
+        range
+    }
+
+    /// Returns the target of the edge at position `e`
+    fn target(&self, e: usize) -> usize {
+        let j = self.u32_at(self.layout.targets + 4 * e);
+        assert!(j < self.layout.n, "{}", CORRUPTED);
+        j
+    }
+
+    /// Returns the number of successors of node `i`
+    pub fn out_degree(&self, i: usize) -> usize {
+        self.edge_range(i).len()
+    }
+
+    /// Returns the indexes of the successors of node `i`
+    pub fn neighbors(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edge_range(i).map(|e| self.target(e))
+    }
+
+    /// Returns the successors of node `i` with the weight of the edge leading
+    /// to them, `1.0` in unweighted graphs
+    pub fn weighted_neighbors(&self, i: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.edge_range(i).map(|e| {
+            let w = self.layout.weights.map_or(1.0, |w| {
+                f64::from_le_bytes(self.map[w + 8 * e..w + 8 * e + 8].try_into().unwrap())
+            });
+            (self.target(e), w)
+        })
+    }
+
+    /// Returns the number of hops from node `from` to every node, `None` if
+    /// the node is not reachable
+    pub fn bfs_distances(&self, from: usize) -> Vec<Option<usize>> {
+        let mut dist = vec![None; self.count_nodes()];
+        dist[from] = Some(0);
+        let mut queue = VecDeque::from([from]);
+        while let Some(i) = queue.pop_front() {
+            let d = dist[i].unwrap() + 1;
+            for j in self.neighbors(i) {
+                if dist[j].is_none() {
+                    dist[j] = Some(d);
+                    queue.push_back(j);
+                }
+            }
+        }
+        dist
+    }
+
+    /// Returns the length of the lightest path from node `from` to every
+    /// node (Dijkstra's algorithm), `None` if the node is not reachable.
+    /// Weights must not be negative
+    pub fn dijkstra(&self, from: usize) -> Vec<Option<f64>> {
+        let mut dist = vec![None; self.count_nodes()];
+        let mut heap = BinaryHeap::from([Closest(0.0, from)]);
+        while let Some(Closest(d, i)) = heap.pop() {
+            if dist[i].is_some() {
+                continue;
+            }
+            dist[i] = Some(d);
+            for (j, w) in self.weighted_neighbors(i) {
+                if dist[j].is_none() {
+                    heap.push(Closest(d + w, j));
+                }
+            }
+        }
+        dist
+    }
+}
+
+/// Node and its tentative distance, ordered so the closest node is the
+/// greatest
+struct Closest(f64, usize);
+
+impl PartialEq for Closest {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Closest {}
+
+impl PartialOrd for Closest {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Closest {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0).then(other.1.cmp(&self.1))
+    }
+}
+
+impl<T> CsrGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display,
+{
+    /// Writes the graph to the file `path` to be opened with
+    /// `MmapCsrGraph::open`. Nodes are stored as text
+    pub fn save_mmap(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_mmap(path.as_ref(), None::<fn(usize, usize) -> f64>)
+    }
+
+    /// Same as `save_mmap` storing `weight(i, j)` as the weight of the edge
+    /// from node `i` to node `j`
+    pub fn save_mmap_weighted(
+        &self,
+        path: impl AsRef<Path>,
+        weight: impl Fn(usize, usize) -> f64,
+    ) -> io::Result<()> {
+        self.write_mmap(path.as_ref(), Some(weight))
+    }
+
+    fn write_mmap(
+        &self,
+        path: &Path,
+        weight: Option<impl Fn(usize, usize) -> f64>,
+    ) -> io::Result<()> {
+        let n = self.count_nodes();
+        if n > u32::MAX as usize {
+            return Err(invalid("Too many nodes for a graph file."));
+        }
+        let labels: Vec<String> = self.nodes().iter().map(|n| n.to_string()).collect();
+        let mut sorted: Vec<usize> = (0..n).collect();
+        sorted.sort_by(|a, b| labels[*a].cmp(&labels[*b]));
+
+        let mut w = BufWriter::new(File::create(path)?);
+        let text_len: usize = labels.iter().map(|l| l.len()).sum();
+        w.write_all(MAGIC)?;
+        for v in [VERSION, n as u64, self.count_edges() as u64] {
+            w.write_all(&v.to_le_bytes())?;
+        }
+        w.write_all(&(weight.is_some() as u64).to_le_bytes())?;
+        w.write_all(&(text_len as u64).to_le_bytes())?;
+        let mut offset = 0u64;
+        w.write_all(&offset.to_le_bytes())?;
+        for i in 0..n {
+            offset += self.out_degree(i) as u64;
+            w.write_all(&offset.to_le_bytes())?;
+        }
+        for i in 0..n {
+            for j in self.neighbors(i) {
+                w.write_all(&(*j as u32).to_le_bytes())?;
+            }
+        }
+        if let Some(weight) = weight {
+            for i in 0..n {
+                for j in self.neighbors(i) {
+                    w.write_all(&weight(i, *j).to_le_bytes())?;
+                }
+            }
+        }
+        let mut offset = 0u64;
+        w.write_all(&offset.to_le_bytes())?;
+        for l in labels.iter() {
+            offset += l.len() as u64;
+            w.write_all(&offset.to_le_bytes())?;
+        }
+        for i in sorted {
+            w.write_all(&(i as u32).to_le_bytes())?;
+        }
+        for l in labels.iter() {
+            w.write_all(l.as_bytes())?;
+        }
+        w.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MmapCsrGraph;
+    use crate::digraph::DiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use std::panic::AssertUnwindSafe;
+
+    #[test]
+    fn mmap_csr_graph() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("rugraph_mmap_{}.csr", std::process::id()));
+        let mut graph = DiGraph::<&str>::new();
+        for n in ["d", "a", "c", "b", "e"] {
+            graph.add_node(n);
+        }
+        graph.add_edge("d", "a");
+        graph.add_edge("a", "c");
+        graph.add_edge("c", "b");
+        graph.add_edge("d", "b");
+        let csr = graph.freeze();
+        csr.save_mmap_weighted(&path, |i, j| if (i, j) == (0, 3) { 10.0 } else { 1.5 })
+            .unwrap();
+
+        let mapped = MmapCsrGraph::open(&path).unwrap();
+        assert!(mapped.is_weighted());
+        assert_eq!((mapped.count_nodes(), mapped.count_edges()), (5, 4));
+        assert_eq!(mapped.node(2), "c");
+        assert_eq!(mapped.index_of("b"), Some(3));
+        assert_eq!(mapped.index_of("z"), None);
+        assert_eq!(mapped.neighbors(0).collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(mapped.out_degree(4), 0);
+        assert_eq!(
+            mapped.bfs_distances(0),
+            vec![Some(0), Some(1), Some(2), Some(1), None]
+        );
+        assert_eq!(
+            mapped.dijkstra(0),
+            vec![Some(0.0), Some(1.5), Some(3.0), Some(4.5), None]
+        );
+
+        csr.save_mmap(&path).unwrap();
+        let mapped = MmapCsrGraph::open(&path).unwrap();
+        assert!(!mapped.is_weighted());
+        assert_eq!(mapped.dijkstra(0)[3], Some(1.0));
+        drop(mapped);
+
+        // the last offset must be the number of edges
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[48 + 8 * 5] = 3;
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(MmapCsrGraph::open(&path).is_err());
+
+        std::fs::write(&path, b"RUGRCSR\0").unwrap();
+        assert!(MmapCsrGraph::open(&path).is_err());
+        std::fs::write(&path, b"not a graph file, not a graph file, not a graph").unwrap();
+        assert!(MmapCsrGraph::open(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn mmap_corrupted_offset() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("rugraph_mmap_bad_{}.csr", std::process::id()));
+        let mut graph = DiGraph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3)]);
+        graph.freeze().save_mmap(&path).unwrap();
+        // successors of node 1 end past the last edge
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[48 + 8 * 2] = 9;
+        std::fs::write(&path, &bytes).unwrap();
+
+        let mapped = MmapCsrGraph::open(&path).unwrap();
+        assert_eq!(mapped.neighbors(0).collect::<Vec<_>>(), vec![1]);
+        let bfs = std::panic::catch_unwind(AssertUnwindSafe(|| mapped.bfs_distances(0)));
+        let msg = bfs.unwrap_err();
+        let msg = msg.downcast_ref::<String>().map(String::as_str);
+        assert_eq!(msg, Some("Graph file corrupted."));
+        drop(mapped);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
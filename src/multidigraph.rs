@@ -1,11 +1,22 @@
-use std::cell::RefCell;
-use std::fs::File;
-use std::io::Write;
-use std::rc::Rc;
+use smallvec::SmallVec;
+use std::collections::BTreeMap;
+use std::io::BufRead;
 use std::vec::Vec;
 
+use crate::attributes::Attributes;
+use crate::dot::attr;
+use crate::dot::parse_dot_reader;
+use crate::dot::DotStatement;
+use crate::dot::DotStyle;
+use crate::memory::btree_map_bytes;
+use crate::memory::MemoryReport;
+use crate::observe::GraphEvent;
+use crate::observe::Observers;
 use crate::rugraph::IGraph;
 use crate::rugraph::IMultiDiGraph;
+use crate::rugraph::LimitedPaths;
+use crate::rugraph::PathBounds;
+use crate::visit::VisitMap;
 
 /// `MultiDiGraph` is actually a `generic` multi directed graph where each node of type `T`
 ///  and edge of type `E`
@@ -16,26 +27,30 @@ where
     T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
     E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
 {
-    /// Nodes are stored in the heap
-    nodes: RefCell<Vec<Rc<MultiNode<T, E>>>>,
+    /// Nodes are stored in an arena, edges refer to them by their index
+    nodes: Vec<MultiNode<T, E>>,
+    /// Position of every node in the arena
+    index: BTreeMap<T, usize>,
+    pub(crate) attrs: Attributes<T, (T, T, E)>,
+    pub(crate) observers: Observers<T, E>,
 }
 
-/// A `Node` is represented as a generic `T` and a list of pointers to their neighbors (allocated in the heap)
+/// A `Node` is represented as a generic `T` and a list of edges to their neighbors
 struct MultiNode<T, E>
 where
     T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
     E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
 {
     elem: T,
-    neighbors: RefCell<Vec<Rc<Edge<T, E>>>>,
+    neighbors: SmallVec<[Edge<E>; 2]>,
 }
 
-struct Edge<T, E>
+/// An `Edge` is represented as the index of the target node in the arena and its value
+struct Edge<E>
 where
-    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
     E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
 {
-    node: Rc<MultiNode<T, E>>,
+    node: usize,
     edge: E,
 }
 
@@ -46,8 +61,8 @@ where
 {
     pub fn new(elem: T) -> Self {
         MultiNode::<T, E> {
-            elem: elem,
-            neighbors: RefCell::new(Vec::new()),
+            elem,
+            neighbors: SmallVec::new(),
         }
     }
 }
@@ -59,80 +74,369 @@ where
 {
     pub fn new() -> Self {
         MultiDiGraph::<T, E> {
-            nodes: RefCell::new(vec![]),
+            nodes: vec![],
+            index: BTreeMap::new(),
+            attrs: Attributes::new(),
+            observers: Observers::new(),
         }
     }
 
-    fn get_index_by_node_id(&self, from: T) -> Result<usize, &'static str> {
-        let nodes = self.nodes.borrow();
-        let idx_from = nodes.iter().position(|r| r.elem == from);
-        match idx_from {
-            None => Err("Element not found"),
-            Some(value) => Ok(value),
+    /// Returns an estimate of the memory used by the graph
+    pub fn memory_usage(&self) -> MemoryReport {
+        MemoryReport {
+            nodes: self.nodes.capacity() * std::mem::size_of::<MultiNode<T, E>>(),
+            adjacency: self
+                .nodes
+                .iter()
+                .filter(|n| n.neighbors.spilled())
+                .map(|n| n.neighbors.capacity() * std::mem::size_of::<Edge<E>>())
+                .sum(),
+            payloads: btree_map_bytes::<T, usize>(self.index.len()),
         }
     }
 
-    fn dfs(
-        &self,
-        previous_from: T,
+    /// Same as `is_connected` but marking the nodes in `visited`, which is
+    /// reset first, so the same map can be reused between queries
+    pub fn is_connected_with(&self, from: T, to: T, visited: &mut VisitMap) -> bool {
+        let (Ok(idx_from), Ok(idx_to)) = (
+            self.get_index_by_node_id(from),
+            self.get_index_by_node_id(to),
+        ) else {
+            return false;
+        };
+        visited.reset(self.nodes.len());
+        visited.visit(idx_from);
+        let mut to_process = vec![idx_from];
+
+        while let Some(i) = to_process.pop() {
+            for e in self.nodes[i].neighbors.iter() {
+                if e.node == idx_to {
+                    return true;
+                }
+                if visited.visit(e.node) {
+                    to_process.push(e.node);
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Removes node `node` with its incoming and outgoing edges and their
+    /// attributes. Returns `false` if the node doesn't exist. The last node
+    /// of the arena takes the place of the removed one
+    pub fn remove_node(&mut self, node: T) -> bool {
+        let Some(idx) = self.index.remove(&node) else {
+            return false;
+        };
+        if !self.observers.is_empty() {
+            let outgoing = self.nodes[idx].neighbors.iter().map(|e| (idx, e));
+            let incoming = self.nodes.iter().enumerate().filter(|(i, _)| *i != idx);
+            let incoming = incoming.flat_map(|(i, n)| {
+                n.neighbors.iter().filter(|e| e.node == idx).map(move |e| (i, e))
+            });
+            for (i, e) in outgoing.chain(incoming) {
+                self.observers.notify(|| {
+                    GraphEvent::RemoveEdge(
+                        self.nodes[i].elem.clone(),
+                        self.nodes[e.node].elem.clone(),
+                        e.edge.clone(),
+                    )
+                });
+            }
+        }
+        let last = self.nodes.len() - 1;
+        self.nodes.swap_remove(idx);
+        for n in self.nodes.iter_mut() {
+            n.neighbors.retain(|e| e.node != idx);
+            for e in n.neighbors.iter_mut().filter(|e| e.node == last) {
+                e.node = idx;
+            }
+        }
+        if idx < last {
+            self.index.insert(self.nodes[idx].elem.clone(), idx);
+        }
+        self.attrs.nodes.remove(&node);
+        self.attrs.edges.retain(|(from, to, _), _| *from != node && *to != node);
+        self.observers.notify(|| GraphEvent::RemoveNode(node));
+        true
+    }
+
+    /// Removes the edge from `from` to `to` labeled `edge` and its
+    /// attributes. Returns `false` if there is no such edge
+    pub fn remove_edge(&mut self, from: T, to: T, edge: E) -> bool {
+        let (Ok(idx_from), Ok(idx_to)) = (
+            self.get_index_by_node_id(from.clone()),
+            self.get_index_by_node_id(to.clone()),
+        ) else {
+            return false;
+        };
+        let neighbors = &mut self.nodes[idx_from].neighbors;
+        let Some(pos) = neighbors
+            .iter()
+            .position(|e| e.node == idx_to && e.edge == edge)
+        else {
+            return false;
+        };
+        neighbors.remove(pos);
+        self.attrs.edges.remove(&(from.clone(), to.clone(), edge.clone()));
+        self.observers.notify(|| GraphEvent::RemoveEdge(from, to, edge));
+        true
+    }
+
+    /// Changes the value of the edge from `from` to `to` labeled `old_edge`
+    /// to `new_edge`, keeping its position among the parallel edges and its
+    /// attributes. Returns an error if there is no such edge or there is
+    /// already one labeled `new_edge`
+    pub fn update_edge(
+        &mut self,
         from: T,
         to: T,
-        dst: T,
-        edge: E,
-        simple_path: &mut Vec<Vec<(T, T, E)>>,
-        current_path: &mut Vec<(T, T, E)>,
-        visited: &mut Vec<T>,
-    ) {
-        if visited.contains(&previous_from.clone()) {
+        old_edge: E,
+        new_edge: E,
+    ) -> Result<(), &'static str> {
+        let (Ok(idx_from), Ok(idx_to)) = (
+            self.get_index_by_node_id(from.clone()),
+            self.get_index_by_node_id(to.clone()),
+        ) else {
+            return Err("Element not found");
+        };
+        if old_edge == new_edge {
+            if self.is_directly_connected_by(from, to, old_edge) {
+                return Ok(());
+            }
+            return Err("Edge not found");
+        }
+        let neighbors = &mut self.nodes[idx_from].neighbors;
+        if neighbors.iter().any(|e| e.node == idx_to && e.edge == new_edge) {
+            return Err("Edge already exists");
+        }
+        let Some(e) = neighbors
+            .iter_mut()
+            .find(|e| e.node == idx_to && e.edge == old_edge)
+        else {
+            return Err("Edge not found");
+        };
+        e.edge = new_edge.clone();
+        let old_key = (from.clone(), to.clone(), old_edge.clone());
+        if let Some(attrs) = self.attrs.edges.remove(&old_key) {
+            self.attrs
+                .edges
+                .insert((from.clone(), to.clone(), new_edge.clone()), attrs);
+        }
+        self.observers
+            .notify(|| GraphEvent::RemoveEdge(from.clone(), to.clone(), old_edge));
+        self.observers
+            .notify(|| GraphEvent::AddEdge(from, to, new_edge));
+        Ok(())
+    }
+
+    /// Flips every edge in place, keeping its value and attributes
+    pub fn reverse(&mut self) {
+        self.notify_edges(GraphEvent::RemoveEdge);
+        let mut pred: Vec<SmallVec<[Edge<E>; 2]>> =
+            (0..self.nodes.len()).map(|_| SmallVec::new()).collect();
+        for i in 0..self.nodes.len() {
+            for e in std::mem::take(&mut self.nodes[i].neighbors) {
+                pred[e.node].push(Edge {
+                    node: i,
+                    edge: e.edge,
+                });
+            }
+        }
+        for (n, p) in self.nodes.iter_mut().zip(pred) {
+            n.neighbors = p;
+        }
+        let edges = std::mem::take(&mut self.attrs.edges);
+        self.attrs.edges = edges
+            .into_iter()
+            .map(|((from, to, e), a)| ((to, from, e), a))
+            .collect();
+        self.notify_edges(GraphEvent::AddEdge);
+    }
+
+    /// Notifies the observers of `event(from, to, edge)` for every edge
+    fn notify_edges(&self, event: impl Fn(T, T, E) -> GraphEvent<T, E>) {
+        if self.observers.is_empty() {
             return;
         }
-        visited.push(previous_from.clone());
-        current_path.push((previous_from.clone(), dst.clone(), edge.clone()));
-        if from == to {
-            simple_path.push(current_path.clone());
-            if visited.contains(&previous_from.clone()) {
-                let index = visited
-                    .iter()
-                    .position(|x| x.clone() == previous_from.clone())
-                    .unwrap();
-                visited.remove(index);
-                current_path.pop();
-                return;
+        for n in self.nodes.iter() {
+            for e in n.neighbors.iter() {
+                self.observers.notify(|| {
+                    event(n.elem.clone(), self.nodes[e.node].elem.clone(), e.edge.clone())
+                });
             }
         }
+    }
 
-        let neighbors = self.get_neighbors(dst.clone());
-        for n in neighbors.iter() {
-            self.dfs(
-                dst.clone(),
-                n.0.clone(),
-                to.clone(),
-                n.0.clone(),
-                n.1.clone(),
-                simple_path,
-                current_path,
-                visited,
-            );
+    /// Removes, with their attributes, the edges for which
+    /// `keep(from, to, edge)` returns `false`, in a single pass over the graph
+    pub fn retain_edges(&mut self, mut keep: impl FnMut(&T, &T, &E) -> bool) {
+        let mut removed = Vec::new();
+        for i in 0..self.nodes.len() {
+            let mut neighbors = std::mem::take(&mut self.nodes[i].neighbors);
+            neighbors.retain(|e| {
+                let kept = keep(&self.nodes[i].elem, &self.nodes[e.node].elem, &e.edge);
+                if !kept {
+                    removed.push((i, e.node, e.edge.clone()));
+                }
+                kept
+            });
+            self.nodes[i].neighbors = neighbors;
+        }
+        for (i, j, edge) in removed {
+            let key = (self.nodes[i].elem.clone(), self.nodes[j].elem.clone(), edge);
+            self.attrs.edges.remove(&key);
+            self.observers
+                .notify(|| GraphEvent::RemoveEdge(key.0, key.1, key.2));
         }
+    }
 
-        current_path.pop();
-        if visited.contains(&previous_from.clone()) {
-            let index = visited
-                .iter()
-                .position(|x| x.clone() == previous_from.clone())
-                .unwrap();
-            visited.remove(index);
+    /// Splits node `node` into `into_in`, which gets its incoming edges, and
+    /// `into_out`, which gets its outgoing ones, joined by an edge labeled
+    /// `edge` from `into_in` to `into_out`, see `DiGraph::split_node`
+    pub fn split_node(
+        &mut self,
+        node: T,
+        into_in: T,
+        into_out: T,
+        edge: E,
+    ) -> Result<(), &'static str> {
+        let idx = self.get_index_by_node_id(node.clone())?;
+        if into_in == into_out
+            || (into_in != node && self.node_exists(into_in.clone()))
+            || (into_out != node && self.node_exists(into_out.clone()))
+        {
+            return Err("Element already exists");
+        }
+        let incoming: Vec<(usize, E)> = (0..self.nodes.len())
+            .filter(|k| *k != idx)
+            .flat_map(|k| {
+                self.nodes[k]
+                    .neighbors
+                    .iter()
+                    .filter(|e| e.node == idx)
+                    .map(move |e| (k, e.edge.clone()))
+            })
+            .collect();
+        for e in self.nodes[idx].neighbors.iter() {
+            self.observers.notify(|| {
+                let to = self.nodes[e.node].elem.clone();
+                GraphEvent::RemoveEdge(node.clone(), to, e.edge.clone())
+            });
         }
+        for (k, e) in incoming.iter() {
+            self.observers.notify(|| {
+                GraphEvent::RemoveEdge(self.nodes[*k].elem.clone(), node.clone(), e.clone())
+            });
+        }
+        self.observers.notify(|| GraphEvent::RemoveNode(node.clone()));
+
+        let out = self.nodes.len();
+        let neighbors = std::mem::take(&mut self.nodes[idx].neighbors);
+        self.nodes[idx].neighbors.push(Edge {
+            node: out,
+            edge: edge.clone(),
+        });
+        self.nodes[idx].elem = into_in.clone();
+        self.nodes.push(MultiNode::<T, E> {
+            elem: into_out.clone(),
+            neighbors,
+        });
+        self.index.remove(&node);
+        self.index.insert(into_in.clone(), idx);
+        self.index.insert(into_out.clone(), out);
+
+        if let Some(a) = self.attrs.nodes.remove(&node) {
+            self.attrs.nodes.insert(into_in.clone(), a);
+        }
+        let moved: Vec<(T, T, E)> = self
+            .attrs
+            .edges
+            .keys()
+            .filter(|(from, to, _)| *from == node || *to == node)
+            .cloned()
+            .collect();
+        for (from, to, e) in moved {
+            let key = (from.clone(), to.clone(), e.clone());
+            let a = self.attrs.edges.remove(&key).unwrap();
+            let from = if from == node { into_out.clone() } else { from };
+            let to = if to == node { into_in.clone() } else { to };
+            self.attrs.edges.insert((from, to, e), a);
+        }
+
+        self.observers.notify(|| GraphEvent::AddNode(into_in.clone()));
+        self.observers.notify(|| GraphEvent::AddNode(into_out.clone()));
+        self.observers
+            .notify(|| GraphEvent::AddEdge(into_in.clone(), into_out.clone(), edge));
+        for e in self.nodes[out].neighbors.iter() {
+            self.observers.notify(|| {
+                let to = self.nodes[e.node].elem.clone();
+                GraphEvent::AddEdge(into_out.clone(), to, e.edge.clone())
+            });
+        }
+        for (k, e) in incoming {
+            self.observers.notify(|| {
+                GraphEvent::AddEdge(self.nodes[k].elem.clone(), into_in.clone(), e)
+            });
+        }
+        Ok(())
     }
-}
 
-impl<T, E> Drop for MultiDiGraph<T, E>
-where
-    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
-    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
-{
-    fn drop(&mut self) {
-        self.nodes.borrow_mut().clear();
+    /// Returns the values of the parallel edges from `from` to `to`, in the
+    /// order they were added, without copying them
+    pub fn edges_between(&self, from: &T, to: &T) -> Vec<&E> {
+        let (Some(idx_from), Some(idx_to)) = (self.index.get(from), self.index.get(to)) else {
+            return Vec::new();
+        };
+        self.nodes[*idx_from]
+            .neighbors
+            .iter()
+            .filter(|e| e.node == *idx_to)
+            .map(|e| &e.edge)
+            .collect()
+    }
+
+    fn get_index_by_node_id(&self, from: T) -> Result<usize, &'static str> {
+        match self.index.get(&from).copied() {
+            None => Err("Element not found"),
+            Some(value) => Ok(value),
+        }
+    }
+
+    /// Deep first search from the node at position `from`. Helper function
+    /// to get all the simple paths within `bounds`, `visited` marks the
+    /// nodes in the path
+    fn dfs(
+        &self,
+        from: usize,
+        to: usize,
+        bounds: &PathBounds,
+        simple_path: &mut Vec<Vec<(T, T, E)>>,
+        current_path: &mut Vec<(T, T, E)>,
+        visited: &mut VisitMap,
+    ) {
+        if current_path.len() >= bounds.max_len {
+            return;
+        }
+        visited.visit(from);
+        for e in self.nodes[from].neighbors.iter() {
+            if bounds.done(simple_path) {
+                break;
+            }
+            current_path.push((
+                self.nodes[from].elem.clone(),
+                self.nodes[e.node].elem.clone(),
+                e.edge.clone(),
+            ));
+            if e.node == to {
+                simple_path.push(current_path.clone());
+            } else if !visited.is_visited(e.node) {
+                self.dfs(e.node, to, bounds, simple_path, current_path, visited);
+            }
+            current_path.pop();
+        }
+        visited.unvisit(from);
     }
 }
 
@@ -147,28 +451,18 @@ where
             return;
         }
 
-        let mut nodes = self.nodes.borrow_mut();
-        let n = Rc::new(MultiNode::<T, E>::new(elem));
-
-        nodes.push(n);
+        self.index.insert(elem.clone(), self.nodes.len());
+        self.nodes.push(MultiNode::<T, E>::new(elem));
+        let node = &self.nodes[self.nodes.len() - 1].elem;
+        self.observers.notify(|| GraphEvent::AddNode(node.clone()));
     }
 
     fn node_exists(&self, from: T) -> bool {
-        let nodes = self.nodes.borrow();
-        let idx_from = nodes.iter().position(|r| r.elem == from);
-        match idx_from {
-            None => {
-                return false;
-            }
-            Some(_value) => {
-                return true;
-            }
-        }
+        self.index.contains_key(&from)
     }
 
     /// Returns if node `to` is a neighbord of `from`
     fn is_directly_connected(&self, from: T, to: T) -> bool {
-        let nodes = self.nodes.borrow();
         let ret_idx_from = self.get_index_by_node_id(from.clone());
         let idx_from;
         match ret_idx_from {
@@ -189,168 +483,64 @@ where
             }
         };
 
-        let n = &nodes[idx_from];
-        let m = nodes[idx_to].clone();
-        for e in n.neighbors.borrow().iter() {
-            if Rc::ptr_eq(&e.node, &m) {
-                //println!("Node {} is connected to {}", from, to);
-                return true;
-            }
-        }
-        //println!("Node {} is NOT connected to {}", from, to);
-        return false;
+        self.nodes[idx_from]
+            .neighbors
+            .iter()
+            .any(|e| e.node == idx_to)
     }
 
     /// Returns if a node `from` is connected to a node `to`
     fn is_connected(&self, from: T, to: T) -> bool {
-        //println!("Checking from {} to {}", from, to);
-        let mut seen = Vec::<(T, E)>::new();
-        let mut to_process = Vec::<(T, E)>::new();
-
-        let neighbors = self.get_neighbors(from.clone());
-        for n in neighbors.iter() {
-            to_process.push(n.clone());
-        }
-        //println!(" |-> Neighbors of {} : {:?}",from,neighbors);
-
-        let mut end = false;
-        while !end {
-            let node = to_process.pop().unwrap().clone();
-            let node_id = node.0;
-
-            let neighbors = self.get_neighbors(node_id.clone());
-            //println!(" |-> Neighbors of {} : {:?}",node_id,neighbors);
-            let contains = neighbors.iter().any(|r| r.0 == to.clone());
-            //println!("    |-> Neighbors of {} contains {}? {}",node_id,from,contains);
-
-            if contains {
-                return true;
-            } else {
-                for n in neighbors.iter() {
-                    if !seen.contains(n) {
-                        to_process.push(n.clone());
-                        seen.push(n.clone());
-                    }
-                }
-            }
-
-            end = to_process.is_empty();
-        }
-
-        return false;
-    }
-
-    /// Exports the graph to a dot file. `file` must be a valid
-    /// file ready to be written.
-    /// `graph_name` is the name of the graph
-    fn to_dot_file(&self, file: &mut File, graph_name: &str) {
-        let s = self.to_dot_string(graph_name);
-        file.write_all(s.as_bytes()).expect("Error writing file!");
+        self.is_connected_with(from, to, &mut VisitMap::default())
     }
 
-    /// Returns an `String` with a dot file representation of the graph
+    /// Returns an `String` with a dot file representation of the graph with a
+    /// statement for every node and every edge (labeled with its value), so
+    /// it parses back into an equal graph
     fn to_dot_string(&self, graph_name: &str) -> String {
-        let mut s = String::from("digraph ") + graph_name + &String::from("{\n");
-        let nodes = self.nodes.borrow();
-        for n in nodes.iter() {
-            for m in n.neighbors.borrow().iter() {
-                s = s + &n.elem.to_string();
-                s = s
-                    + &String::from(" -> ")
-                    + &m.node.elem.to_string()
-                    + &String::from(" [label=\"")
-                    + &m.edge.to_string()
-                    + &String::from("\"];\n");
-            }
-        }
-        s = s + &String::from("}\n");
-        return s;
+        self.to_dot_string_with(graph_name, &DotStyle::new())
     }
 
     fn is_empty(&self) -> bool {
-        return self.nodes.borrow().is_empty();
+        return self.nodes.is_empty();
     }
 
     fn count_nodes(&self) -> usize {
-        return self.nodes.borrow().len();
+        return self.nodes.len();
     }
     fn get_nodes(&self) -> Vec<T> {
         let mut ret = Vec::<T>::new();
-        for n in self.nodes.borrow().iter() {
+        for n in self.nodes.iter() {
             ret.push(n.elem.clone());
         }
         return ret;
     }
 }
 
-/// Returns a multidirected string graph `MultiDiGraph<String, String>` from a dot file content
+/// Returns a multidirected string graph `MultiDiGraph<String, String>` from a dot file content.
+/// The `label` attribute of every edge is used as the edge value, edges without
+/// label get an empty one
 pub fn multidigraph_from_dot_string(
     content: &String,
 ) -> Result<MultiDiGraph<String, String>, &'static str> {
-    let mut graph = MultiDiGraph::<String, String>::new();
-    let idx1: usize;
-    let idx2: usize;
-    match content.chars().position(|c| c == '{') {
-        None => {
-            return Err("Dot file not correct. { not found.");
-        }
-        Some(i) => {
-            idx1 = i + 1;
-        }
-    }
-
-    match content.chars().position(|c| c == '}') {
-        None => {
-            return Err("Dot file not correct. } not found.");
-        }
-        Some(i) => {
-            idx2 = i - 1;
-        }
-    }
-
-    if idx2 < idx1 {
-        return Err("Dot file not correct. } before {");
-    }
-
-    let c = &content[idx1..idx2];
-    //println!("Content {}",c);
-    let v_c: Vec<&str> = c.split(';').collect();
-
-    for line in v_c.iter() {
-        if line.is_empty() {
-            continue;
-        }
-        //println!("Line {}", line);
-        // [
-        let idx3 = match line.chars().position(|c| c == '[') {
-            None => {
-                return Err("Dot file not correct. [ not found.");
-            }
-            Some(i) => i - 1,
-        };
-
-        let l_nodes = line[0..idx3].trim();
-
-        let v_nodes: Vec<&str> = l_nodes.split("->").collect();
+    multidigraph_from_dot_reader(content.as_bytes())
+}
 
-        let n_from;
-        let n_to;
-        if v_nodes.len() == 2 {
-            n_from = v_nodes[0].trim().to_string();
-            n_to = v_nodes[1].trim().to_string();
-            graph.add_node(n_from.clone());
-            graph.add_node(n_to.clone());
-        } else {
-            return Err("Dot file not correct.");
+/// Same as `multidigraph_from_dot_string` reading the dot file incrementally
+/// from `reader`, so big files don't need to be loaded in memory
+pub fn multidigraph_from_dot_reader(
+    reader: impl BufRead,
+) -> Result<MultiDiGraph<String, String>, &'static str> {
+    let mut graph = MultiDiGraph::<String, String>::new();
+    let header = parse_dot_reader(reader, |stmt| match stmt {
+        DotStatement::Node(id) => graph.add_node(id),
+        DotStatement::Edge(from, to, attrs) => {
+            let label = attr(&attrs, "label").cloned().unwrap_or_default();
+            graph.add_edge(from, to, label)
         }
-
-        let label = line[idx3..]
-            .replace("[label=\"", "")
-            .replace("\"]", "")
-            .trim()
-            .to_string();
-        //println!("LAbel {}",label.clone());
-        graph.add_edge(n_from, n_to, label.to_string())
+    })?;
+    if !header.directed {
+        return Err("Dot file not correct. digraph expected.");
     }
 
     Ok(graph)
@@ -371,23 +561,25 @@ where
             return;
         }
 
-        let nodes = self.nodes.borrow_mut();
-
-        let idx_from = nodes.iter().position(|r| r.elem == from).unwrap();
-        let idx_to = nodes.iter().position(|r| r.elem == to).unwrap();
-
-        let n = &nodes[idx_from];
-        let m = nodes[idx_to].clone();
-
-        n.neighbors.borrow_mut().push(Rc::new(Edge {
-            node: m.clone(),
-            edge: edge,
-        }));
+        let idx_from = self.get_index_by_node_id(from).unwrap();
+        let idx_to = self.get_index_by_node_id(to).unwrap();
+
+        self.nodes[idx_from].neighbors.push(Edge {
+            node: idx_to,
+            edge,
+        });
+        self.observers.notify(|| {
+            let e = &self.nodes[idx_from].neighbors[self.nodes[idx_from].neighbors.len() - 1];
+            GraphEvent::AddEdge(
+                self.nodes[idx_from].elem.clone(),
+                self.nodes[idx_to].elem.clone(),
+                e.edge.clone(),
+            )
+        });
     }
 
     /// Returns if node `to` is a neighbord of `from` by edge `edge`
     fn is_directly_connected_by(&self, from: T, to: T, edge: E) -> bool {
-        let nodes = self.nodes.borrow();
         let ret_idx_from = self.get_index_by_node_id(from.clone());
         let idx_from;
         match ret_idx_from {
@@ -408,41 +600,44 @@ where
             }
         };
 
-        let n = &nodes[idx_from];
-        let m = nodes[idx_to].clone();
-        for e in n.neighbors.borrow().iter() {
-            if Rc::ptr_eq(&e.node, &m) && (e.edge == edge) {
-                //println!("Node {} is connected to {}", from, to);
-                return true;
-            }
-        }
-        //println!("Node {} is NOT connected to {}", from, to);
-        return false;
+        self.nodes[idx_from]
+            .neighbors
+            .iter()
+            .any(|e| e.node == idx_to && e.edge == edge)
     }
 
     /// Returns a vector `Vec<Vec<(T, T, E)>>` containing all the simple paths
     /// from node `from` to node `to` in a vector of tuples `(from,to,edge)`
     fn all_simple_paths(&self, from: T, to: T) -> Vec<Vec<(T, T, E)>> {
+        self.all_simple_paths_with_cutoff(from, to, usize::MAX)
+    }
+
+    fn all_simple_paths_limited(
+        &self,
+        from: T,
+        to: T,
+        max_len: usize,
+        limit: usize,
+    ) -> LimitedPaths<(T, T, E)> {
         let mut ret = Vec::<Vec<(T, T, E)>>::new();
+        let (Ok(idx_from), Ok(idx_to)) = (
+            self.get_index_by_node_id(from),
+            self.get_index_by_node_id(to),
+        ) else {
+            return (ret, false);
+        };
+        let bounds = PathBounds { max_len, limit };
         let mut current_path = Vec::<(T, T, E)>::new();
-        let mut visited = Vec::<T>::new();
-        let neighbors = self.get_neighbors(from.clone());
-        if neighbors.len() == 0 {
-            return ret;
-        }
-        for n in neighbors.iter() {
-            self.dfs(
-                from.clone(),
-                n.0.clone(),
-                to.clone(),
-                n.0.clone(),
-                n.1.clone(),
-                &mut ret,
-                &mut current_path,
-                &mut visited,
-            );
-        }
-        return ret;
+        let mut visited = VisitMap::new(self.nodes.len());
+        self.dfs(
+            idx_from,
+            idx_to,
+            &bounds,
+            &mut ret,
+            &mut current_path,
+            &mut visited,
+        );
+        bounds.truncate(ret)
     }
 
     fn get_neighbors(&self, from: T) -> Vec<(T, E)> {
@@ -452,15 +647,10 @@ where
             return neighbors;
         }
 
-        let nodes = self.nodes.borrow();
+        let idx_from = self.get_index_by_node_id(from).unwrap();
 
-        let idx_from = nodes.iter().position(|r| r.elem == from).unwrap();
-
-        let n = &nodes[idx_from];
-
-        //n.neighbors
-        for e in n.neighbors.borrow().iter() {
-            neighbors.push((e.node.elem.clone(), e.edge.clone()));
+        for e in self.nodes[idx_from].neighbors.iter() {
+            neighbors.push((self.nodes[e.node].elem.clone(), e.edge.clone()));
         }
 
         return neighbors;
@@ -471,9 +661,12 @@ where
 mod tests {
     use super::MultiDiGraph;
     use crate::multidigraph::multidigraph_from_dot_string;
-    use crate::multidigraph::File;
+    use std::fs::File;
     use crate::rugraph::IGraph;
     use crate::rugraph::IMultiDiGraph;
+    use crate::log::GraphLog;
+    use std::sync::Arc;
+    use std::sync::Mutex;
 
     #[test]
     fn multidigraph_test1() {
@@ -759,6 +952,160 @@ mod tests {
         println!("Dot:\n{}", s);
         assert_eq!(s.is_empty(), false);
         let mut fd = File::create("test_multidirected.dot").expect("error creating file");
-        graph.to_dot_file(&mut fd, &String::from("paths_test"));
+        graph
+            .to_dot_file(&mut fd, &String::from("paths_test"))
+            .expect("error writing file");
+    }
+
+    #[test]
+    fn multidigraph_is_connected() {
+        let mut graph = MultiDiGraph::<u32, u32>::new();
+        for n in 0..20_000 {
+            graph.add_node(n);
+        }
+        for n in 1..20_000 {
+            graph.add_edge(n - 1, n, 0);
+        }
+        graph.add_node(99_999);
+        assert!(graph.is_connected(0, 1));
+        assert!(graph.is_connected(0, 19_999));
+        assert!(!graph.is_connected(19_999, 0));
+        assert!(!graph.is_connected(99_999, 0));
+        assert_eq!(graph.all_simple_paths(0, 3).len(), 1);
+    }
+
+    #[test]
+    fn multidigraph_paths_with_cutoff() {
+        let mut graph = MultiDiGraph::<i32, char>::new();
+        for n in 1..=3 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2, 'a');
+        graph.add_edge(2, 3, 'b');
+        graph.add_edge(1, 3, 'c');
+        graph.add_edge(1, 3, 'd');
+
+        assert_eq!(graph.all_simple_paths(1, 3).len(), 3);
+        assert_eq!(
+            graph.all_simple_paths_with_cutoff(1, 3, 1),
+            vec![vec![(1, 3, 'c')], vec![(1, 3, 'd')]]
+        );
+        assert!(graph.all_simple_paths_with_cutoff(1, 3, 0).is_empty());
+    }
+
+    #[test]
+    fn multidigraph_remove() {
+        let mut graph = MultiDiGraph::<i32, char>::new();
+        for n in 1..=4 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2, 'a');
+        graph.add_edge(1, 2, 'b');
+        graph.add_edge(2, 4, 'c');
+        graph.add_edge(4, 1, 'd');
+        graph.add_edge(3, 4, 'e');
+        graph.edge_attrs_mut(2, 4, 'c').insert("w", 1);
+
+        assert!(graph.remove_edge(1, 2, 'a'));
+        assert!(!graph.remove_edge(1, 2, 'a'));
+        assert!(!graph.remove_edge(1, 9, 'a'));
+        assert_eq!(graph.get_neighbors(1), vec![(2, 'b')]);
+
+        assert!(graph.remove_node(2));
+        assert!(!graph.remove_node(2));
+        assert!(!graph.node_exists(2));
+        assert_eq!(graph.count_nodes(), 3);
+        assert!(graph.get_neighbors(1).is_empty());
+        assert!(graph.edge_attrs(&2, &4, &'c').is_none());
+        assert_eq!(graph.get_neighbors(4), vec![(1, 'd')]);
+        assert_eq!(graph.get_neighbors(3), vec![(4, 'e')]);
+        assert!(graph.is_connected(3, 1));
+
+        assert!(graph.remove_node(4));
+        assert!(!graph.is_connected(3, 1));
+        graph.add_node(5);
+        graph.add_edge(5, 1, 'f');
+        assert!(graph.is_directly_connected_by(5, 1, 'f'));
+    }
+
+    #[test]
+    fn multidigraph_edges_between() {
+        let mut graph = MultiDiGraph::<i32, char>::new();
+        graph.extend_with_edges([(1, 2, 'b'), (1, 3, 'x'), (1, 2, 'a'), (2, 1, 'c')]);
+        assert_eq!(graph.edges_between(&1, &2), vec![&'b', &'a']);
+        assert_eq!(graph.edges_between(&2, &1), vec![&'c']);
+        assert!(graph.edges_between(&3, &1).is_empty());
+        assert!(graph.edges_between(&1, &9).is_empty());
+    }
+
+    #[test]
+    fn multidigraph_retain_edges() {
+        let mut graph = MultiDiGraph::<i32, u32>::new();
+        graph.extend_with_edges([(1, 2, 2019), (1, 2, 2023), (2, 3, 2015), (3, 1, 2024)]);
+        graph.edge_attrs_mut(2, 3, 2015).insert("w", 1);
+        graph.retain_edges(|_, _, year| *year >= 2020);
+        assert_eq!(graph.edges_between(&1, &2), vec![&2023]);
+        assert!(graph.get_neighbors(2).is_empty());
+        assert!(graph.edge_attrs(&2, &3, &2015).is_none());
+        assert!(graph.is_directly_connected_by(3, 1, 2024));
+    }
+
+    #[test]
+    fn multidigraph_update_edge() {
+        let mut graph = MultiDiGraph::<i32, char>::new();
+        graph.extend_with_edges([(1, 2, 'a'), (1, 2, 'b'), (1, 2, 'c')]);
+        graph.edge_attrs_mut(1, 2, 'b').insert("w", 3);
+        assert_eq!(graph.update_edge(1, 2, 'b', 'x'), Ok(()));
+        assert_eq!(graph.edges_between(&1, &2), vec![&'a', &'x', &'c']);
+        assert_eq!(graph.edge_attrs(&1, &2, &'x').unwrap().get_int("w"), Some(3));
+        assert!(graph.edge_attrs(&1, &2, &'b').is_none());
+        assert_eq!(graph.update_edge(1, 2, 'a', 'c'), Err("Edge already exists"));
+        assert_eq!(graph.update_edge(1, 2, 'b', 'y'), Err("Edge not found"));
+        assert_eq!(graph.update_edge(2, 1, 'a', 'a'), Err("Edge not found"));
+        assert_eq!(graph.update_edge(1, 2, 'a', 'a'), Ok(()));
+        assert_eq!(graph.update_edge(1, 9, 'a', 'y'), Err("Element not found"));
+    }
+
+    #[test]
+    fn multidigraph_split_node() {
+        let mirror = Arc::new(Mutex::new(GraphLog::<i32, char>::new()));
+        let mut graph = MultiDiGraph::<i32, char>::new();
+        let log = mirror.clone();
+        graph.on_change(move |e| log.lock().unwrap().record(e.clone()).unwrap());
+        graph.extend_with_edges([(1, 2, 'a'), (1, 2, 'b'), (2, 3, 'c'), (2, 2, 'd')]);
+        graph.edge_attrs_mut(1, 2, 'b').insert("w", 1);
+        assert_eq!(graph.split_node(2, 1, 5, 'x'), Err("Element already exists"));
+
+        graph.split_node(2, 20, 21, 'x').unwrap();
+        assert_eq!(graph.get_nodes(), vec![1, 20, 3, 21]);
+        assert_eq!(graph.get_neighbors(1), vec![(20, 'a'), (20, 'b')]);
+        assert_eq!(graph.get_neighbors(20), vec![(21, 'x')]);
+        assert_eq!(graph.get_neighbors(21), vec![(3, 'c'), (20, 'd')]);
+        assert!(graph.edge_attrs(&1, &20, &'b').is_some());
+
+        let mirror = mirror.lock().unwrap();
+        assert_eq!(mirror.graph().get_nodes(), vec![1, 3, 20, 21]);
+        for n in graph.get_nodes() {
+            assert_eq!(mirror.graph().get_neighbors(n), graph.get_neighbors(n));
+        }
+    }
+
+    #[test]
+    fn multidigraph_reverse() {
+        let mirror = Arc::new(Mutex::new(GraphLog::<i32, char>::new()));
+        let mut graph = MultiDiGraph::<i32, char>::new();
+        let log = mirror.clone();
+        graph.on_change(move |e| log.lock().unwrap().record(e.clone()).unwrap());
+        graph.extend_with_edges([(1, 2, 'a'), (1, 2, 'b'), (3, 1, 'c')]);
+        graph.edge_attrs_mut(1, 2, 'b').insert("w", 1);
+        graph.reverse();
+        assert_eq!(graph.get_neighbors(1), vec![(3, 'c')]);
+        assert_eq!(graph.get_neighbors(2), vec![(1, 'a'), (1, 'b')]);
+        assert!(graph.get_neighbors(3).is_empty());
+        assert!(graph.edge_attrs(&2, &1, &'b').is_some());
+        let mirror = mirror.lock().unwrap();
+        for n in 1..=3 {
+            assert_eq!(mirror.graph().get_neighbors(n), graph.get_neighbors(n));
+        }
     }
 }
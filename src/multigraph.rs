@@ -0,0 +1,254 @@
+use crate::dot::attr;
+use crate::dot::parse_dot_reader;
+use crate::dot::DotStatement;
+use crate::dot::DotStyle;
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+use crate::rugraph::IMultiGraph;
+use crate::rugraph::LimitedPaths;
+use std::io::BufRead;
+use std::vec::Vec;
+
+/// `MultiGraph` is a `generic` undirected graph with labeled parallel edges
+/// where each node of type `T` and edge of type `E` must implement:
+/// `T: Ord + Clone + std::fmt::Display + std::fmt::Debug` and
+/// `E: Ord + Clone + std::fmt::Display + std::fmt::Debug`
+pub struct MultiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub(crate) multidigraph: MultiDiGraph<T, E>,
+}
+
+impl<T, E> MultiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        MultiGraph::<T, E> {
+            multidigraph: MultiDiGraph::<T, E>::new(),
+        }
+    }
+
+    /// Changes the value of the edge between `a` and `b` labeled `old_edge`
+    /// to `new_edge`, see `MultiDiGraph::update_edge`
+    pub fn update_edge(
+        &mut self,
+        a: T,
+        b: T,
+        old_edge: E,
+        new_edge: E,
+    ) -> Result<(), &'static str> {
+        self.multidigraph
+            .update_edge(a.clone(), b.clone(), old_edge.clone(), new_edge.clone())?;
+        if a != b {
+            self.multidigraph.update_edge(b, a, old_edge, new_edge)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, E> Default for MultiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, E> IGraph<T> for MultiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn add_node(&mut self, elem: T) {
+        self.multidigraph.add_node(elem);
+    }
+
+    fn node_exists(&self, node: T) -> bool {
+        self.multidigraph.node_exists(node)
+    }
+
+    /// Edges are stored in both directions so connectivity is symmetric
+    fn is_connected(&self, from: T, to: T) -> bool {
+        self.multidigraph.is_connected(from, to)
+    }
+
+    fn is_directly_connected(&self, from: T, to: T) -> bool {
+        self.multidigraph.is_directly_connected(from, to)
+    }
+
+    /// Returns a dot representation of the graph with a statement for every
+    /// node and every edge (written once and labeled with its value), so it
+    /// parses back into an equal graph
+    fn to_dot_string(&self, graph_name: &str) -> String {
+        self.to_dot_string_with(graph_name, &DotStyle::new())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.multidigraph.is_empty()
+    }
+
+    fn count_nodes(&self) -> usize {
+        self.multidigraph.count_nodes()
+    }
+
+    fn get_nodes(&self) -> Vec<T> {
+        self.multidigraph.get_nodes()
+    }
+}
+
+impl<T, E> IMultiGraph<T, E> for MultiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn add_edge(&mut self, a: T, b: T, edge: E) {
+        self.multidigraph
+            .add_edge(a.clone(), b.clone(), edge.clone());
+        self.multidigraph.add_edge(b, a, edge);
+    }
+
+    fn is_directly_connected_by(&self, a: T, b: T, edge: E) -> bool {
+        self.multidigraph.is_directly_connected_by(a, b, edge)
+    }
+
+    fn all_simple_paths(&self, from: T, to: T) -> Vec<Vec<(T, T, E)>> {
+        self.multidigraph.all_simple_paths(from, to)
+    }
+
+    fn all_simple_paths_limited(
+        &self,
+        from: T,
+        to: T,
+        max_len: usize,
+        limit: usize,
+    ) -> LimitedPaths<(T, T, E)> {
+        self.multidigraph
+            .all_simple_paths_limited(from, to, max_len, limit)
+    }
+
+    fn get_neighbors(&self, from: T) -> Vec<(T, E)> {
+        self.multidigraph.get_neighbors(from)
+    }
+}
+
+/// Returns an undirected multi graph `MultiGraph<String, String>` from a dot
+/// file content. The `label` attribute of every edge is used as the edge
+/// value, edges without label get an empty one
+pub fn multigraph_from_dot_string(
+    content: &String,
+) -> Result<MultiGraph<String, String>, &'static str> {
+    multigraph_from_dot_reader(content.as_bytes())
+}
+
+/// Same as `multigraph_from_dot_string` reading the dot file incrementally
+/// from `reader`, so big files don't need to be loaded in memory
+pub fn multigraph_from_dot_reader(
+    reader: impl BufRead,
+) -> Result<MultiGraph<String, String>, &'static str> {
+    let mut graph = MultiGraph::<String, String>::new();
+    let header = parse_dot_reader(reader, |stmt| match stmt {
+        DotStatement::Node(id) => graph.add_node(id),
+        DotStatement::Edge(a, b, attrs) => {
+            let label = attr(&attrs, "label").cloned().unwrap_or_default();
+            graph.add_edge(a, b, label)
+        }
+    })?;
+    if header.directed {
+        return Err("Dot file not correct. graph expected.");
+    }
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::multigraph_from_dot_string;
+    use super::MultiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiGraph;
+
+    #[test]
+    fn multigraph_edges() {
+        let mut graph = MultiGraph::<String, i32>::new();
+        for n in ["a", "b", "c", "d"] {
+            graph.add_node(n.to_string());
+        }
+        graph.add_edge("a".to_string(), "b".to_string(), 1);
+        graph.add_edge("b".to_string(), "a".to_string(), 2);
+        graph.add_edge("a".to_string(), "b".to_string(), 1);
+        graph.add_edge("c".to_string(), "b".to_string(), 3);
+        graph.add_edge("a".to_string(), "c".to_string(), 4);
+
+        assert_eq!(
+            graph.get_neighbors("b".to_string()),
+            vec![
+                ("a".to_string(), 1),
+                ("a".to_string(), 2),
+                ("c".to_string(), 3)
+            ]
+        );
+        assert!(graph.is_directly_connected_by("b".to_string(), "a".to_string(), 1));
+        assert!(!graph.is_directly_connected_by("b".to_string(), "c".to_string(), 1));
+        assert!(graph.is_connected("c".to_string(), "a".to_string()));
+        assert!(!graph.is_connected("a".to_string(), "d".to_string()));
+
+        let paths = graph.all_simple_paths("a".to_string(), "c".to_string());
+        assert_eq!(
+            paths,
+            vec![
+                vec![
+                    ("a".to_string(), "b".to_string(), 1),
+                    ("b".to_string(), "c".to_string(), 3)
+                ],
+                vec![
+                    ("a".to_string(), "b".to_string(), 2),
+                    ("b".to_string(), "c".to_string(), 3)
+                ],
+                vec![("a".to_string(), "c".to_string(), 4)]
+            ]
+        );
+    }
+
+    #[test]
+    fn multigraph_dot() {
+        let mut graph = MultiGraph::<String, String>::new();
+        graph.add_node("a".to_string());
+        graph.add_node("b".to_string());
+        graph.add_node("c".to_string());
+        graph.add_edge("a".to_string(), "b".to_string(), "x".to_string());
+        graph.add_edge("b".to_string(), "a".to_string(), "y".to_string());
+        graph.add_edge("b".to_string(), "b".to_string(), "z".to_string());
+        let s = graph.to_dot_string("m");
+        assert_eq!(
+            s,
+            "graph m {\n    a;\n    b;\n    c;\n    a -- b [label=\"x\"];\n    a -- b [label=\"y\"];\n    b -- b [label=\"z\"];\n}\n"
+        );
+        let read = multigraph_from_dot_string(&s).unwrap();
+        assert_eq!(read.get_nodes(), graph.get_nodes());
+        assert_eq!(
+            read.get_neighbors("b".to_string()),
+            graph.get_neighbors("b".to_string())
+        );
+        assert!(multigraph_from_dot_string(&String::from("digraph { a -> b }")).is_err());
+    }
+
+    #[test]
+    fn multigraph_update_edge() {
+        let mut graph = MultiGraph::<i32, char>::new();
+        graph.extend_with_edges([(1, 2, 'a'), (2, 2, 'b')]);
+        assert_eq!(graph.update_edge(2, 1, 'a', 'x'), Ok(()));
+        assert!(graph.is_directly_connected_by(1, 2, 'x'));
+        assert!(graph.is_directly_connected_by(2, 1, 'x'));
+        assert!(!graph.is_directly_connected_by(1, 2, 'a'));
+        assert_eq!(graph.update_edge(2, 2, 'b', 'y'), Ok(()));
+        assert_eq!(graph.get_neighbors(2), vec![(1, 'x'), (2, 'y')]);
+        assert_eq!(graph.update_edge(1, 2, 'a', 'z'), Err("Edge not found"));
+    }
+}
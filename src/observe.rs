@@ -0,0 +1,158 @@
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::log::EdgeEvent;
+use crate::multidigraph::MultiDiGraph;
+use crate::multigraph::MultiGraph;
+
+/// A change to a graph, as passed to the callbacks registered with
+/// `on_change`. Graphs without edge values use `()`
+pub type GraphEvent<T, E> = EdgeEvent<T, E>;
+
+type Callback<T, E> = Box<dyn Fn(&GraphEvent<T, E>) + Send + Sync>;
+
+/// Callbacks registered on a graph, called in order of registration
+pub(crate) struct Observers<T, E> {
+    callbacks: Vec<Callback<T, E>>,
+}
+
+impl<T, E> Observers<T, E> {
+    pub fn new() -> Self {
+        Observers {
+            callbacks: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, callback: Callback<T, E>) {
+        self.callbacks.push(callback);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+
+    /// Calls every callback with the event built by `event`, which is only
+    /// built if there is a callback
+    pub fn notify(&self, event: impl FnOnce() -> GraphEvent<T, E>) {
+        if self.callbacks.is_empty() {
+            return;
+        }
+        let event = event();
+        for callback in self.callbacks.iter() {
+            callback(&event);
+        }
+    }
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Registers `callback` to be called after every node or edge added to
+    /// the graph, every edge removed by `retain_edges` or flipped by
+    /// `reverse` (removed and added back) and every node split by
+    /// `split_node`. Adding an element that already exists calls nothing
+    pub fn on_change(&mut self, callback: impl Fn(&GraphEvent<T, ()>) + Send + Sync + 'static) {
+        self.observers.push(Box::new(callback));
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Registers `callback` to be called after every change to the graph,
+    /// see `DiGraph::on_change`. Edges are stored in both directions, so
+    /// adding one calls it once per direction
+    pub fn on_change(&mut self, callback: impl Fn(&GraphEvent<T, ()>) + Send + Sync + 'static) {
+        self.digraph.on_change(callback);
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Registers `callback` to be called after every node or edge added to
+    /// or removed from the graph. Removing a node first reports the removal
+    /// of each of its edges. Changes that do nothing call nothing
+    pub fn on_change(&mut self, callback: impl Fn(&GraphEvent<T, E>) + Send + Sync + 'static) {
+        self.observers.push(Box::new(callback));
+    }
+}
+
+impl<T, E> MultiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Registers `callback` to be called after every change to the graph,
+    /// see `MultiDiGraph::on_change`. Edges are stored in both directions,
+    /// so adding one calls it once per direction
+    pub fn on_change(&mut self, callback: impl Fn(&GraphEvent<T, E>) + Send + Sync + 'static) {
+        self.multidigraph.on_change(callback);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GraphEvent;
+    use crate::digraph::DiGraph;
+    use crate::log::GraphLog;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn digraph_on_change() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut graph = DiGraph::<i32>::new();
+        graph.add_node(1);
+        let events = seen.clone();
+        graph.on_change(move |e| events.lock().unwrap().push(e.clone()));
+        graph.add_node(2);
+        graph.add_node(2);
+        graph.add_edge(1, 2);
+        graph.add_edge(1, 2);
+        graph.add_edge(1, 3);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![GraphEvent::AddNode(2), GraphEvent::AddEdge(1, 2, ())]
+        );
+    }
+
+    #[test]
+    fn multidigraph_on_change() {
+        let mirror = Arc::new(Mutex::new(GraphLog::<i32, char>::new()));
+        let mut graph = MultiDiGraph::<i32, char>::new();
+        let log = mirror.clone();
+        graph.on_change(move |e| log.lock().unwrap().record(e.clone()).unwrap());
+        for n in 1..=3 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2, 'a');
+        graph.add_edge(2, 3, 'b');
+        graph.add_edge(3, 1, 'c');
+        assert!(graph.remove_edge(1, 2, 'a'));
+        assert!(!graph.remove_edge(1, 2, 'a'));
+        assert!(graph.remove_node(3));
+
+        let mirror = mirror.lock().unwrap();
+        assert_eq!(
+            mirror.events()[6..],
+            [
+                GraphEvent::RemoveEdge(1, 2, 'a'),
+                GraphEvent::RemoveEdge(3, 1, 'c'),
+                GraphEvent::RemoveEdge(2, 3, 'b'),
+                GraphEvent::RemoveNode(3),
+            ]
+        );
+        assert_eq!(mirror.graph().get_nodes(), graph.get_nodes());
+        assert!(mirror.graph().get_neighbors(2).is_empty());
+    }
+}
@@ -0,0 +1,393 @@
+use std::fmt;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+use crate::multigraph::MultiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+use crate::rugraph::IMultiGraph;
+
+/// Node of a product of graphs: a node of each graph. Displayed as
+/// `(a, b)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pair<A, B>(pub A, pub B);
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for Pair<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+/// Graph of a binary operation a node comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// The graph the method is called on
+    Left,
+    /// The other graph
+    Right,
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the nodes and edges present in both graphs, in the order of
+    /// this one and with its attributes
+    pub fn intersection(&self, other: &DiGraph<T>) -> DiGraph<T> {
+        self.filter_map(
+            |n| other.node_exists(n.clone()).then(|| n.clone()),
+            |a, b| other.is_directly_connected(a.clone(), b.clone()),
+        )
+    }
+
+    /// Returns a graph with the nodes and edges of both graphs, with their
+    /// attributes, where node `n` of each graph is renamed to
+    /// `relabel(side, n)`. Nodes renamed to the same value are merged, so
+    /// `relabel` should keep both sides apart, e.g. with a prefix
+    pub fn disjoint_union(
+        &self,
+        other: &DiGraph<T>,
+        relabel: impl Fn(Side, &T) -> T,
+    ) -> DiGraph<T> {
+        let mut graph = self.map(|n| relabel(Side::Left, n));
+        let right = other.map(|n| relabel(Side::Right, n));
+        let nodes = right.get_nodes();
+        for n in nodes.iter() {
+            graph.add_node(n.clone());
+        }
+        for (i, n) in nodes.iter().enumerate() {
+            for j in right.neighbor_ids(i) {
+                graph.add_edge(n.clone(), nodes[*j].clone());
+            }
+        }
+        graph.attrs.nodes.extend(right.attrs.nodes);
+        graph.attrs.edges.extend(right.attrs.edges);
+        graph
+    }
+
+    /// Returns the Cartesian product of the graphs: node `(a, b)` points to
+    /// `(a', b)` for every edge `a -> a'` of this graph and to `(a, b')` for
+    /// every edge `b -> b'` of `other`
+    pub fn cartesian_product<U>(&self, other: &DiGraph<U>) -> DiGraph<Pair<T, U>>
+    where
+        U: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        self.product(other, |i, j, succ| {
+            let left = self.neighbor_ids(i).iter().map(|k| (*k, j));
+            let right = other.neighbor_ids(j).iter().map(|l| (i, *l));
+            succ.extend(left.chain(right));
+        })
+    }
+
+    /// Returns the tensor (categorical) product of the graphs: node `(a, b)`
+    /// points to `(a', b')` for every edge `a -> a'` of this graph and every
+    /// edge `b -> b'` of `other`
+    pub fn tensor_product<U>(&self, other: &DiGraph<U>) -> DiGraph<Pair<T, U>>
+    where
+        U: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        self.product(other, |i, j, succ| {
+            for k in self.neighbor_ids(i) {
+                succ.extend(other.neighbor_ids(j).iter().map(|l| (*k, *l)));
+            }
+        })
+    }
+
+    /// Returns a graph with a node `(a, b)` for every pair of nodes of the
+    /// graphs, in the order of this graph and then of `other`, pointing to
+    /// the pairs of positions pushed by `succ(i, j, ..)` for node `(i, j)`
+    fn product<U>(
+        &self,
+        other: &DiGraph<U>,
+        succ: impl Fn(usize, usize, &mut Vec<(usize, usize)>),
+    ) -> DiGraph<Pair<T, U>>
+    where
+        U: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        let (a, b) = (self.get_nodes(), other.get_nodes());
+        let mut nodes = Vec::with_capacity(a.len() * b.len());
+        let mut lists = Vec::with_capacity(a.len() * b.len());
+        let mut pairs = Vec::new();
+        for (i, n) in a.iter().enumerate() {
+            for (j, m) in b.iter().enumerate() {
+                nodes.push(Pair(n.clone(), m.clone()));
+                pairs.clear();
+                succ(i, j, &mut pairs);
+                let mut list: Vec<usize> = pairs.iter().map(|(k, l)| k * b.len() + l).collect();
+                list.sort_unstable();
+                list.dedup();
+                lists.push(list);
+            }
+        }
+        DiGraph::from_index_lists(nodes, lists)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the nodes and edges present in both graphs, see
+    /// `DiGraph::intersection`
+    pub fn intersection(&self, other: &Graph<T>) -> Graph<T> {
+        self.filter_map(
+            |n| other.node_exists(n.clone()).then(|| n.clone()),
+            |a, b| other.is_directly_connected(a.clone(), b.clone()),
+        )
+    }
+
+    /// Returns a graph with the nodes and edges of both graphs where node `n`
+    /// of each graph is renamed to `relabel(side, n)`, see
+    /// `DiGraph::disjoint_union`
+    pub fn disjoint_union(&self, other: &Graph<T>, relabel: impl Fn(Side, &T) -> T) -> Graph<T> {
+        let mut graph = self.map(|n| relabel(Side::Left, n));
+        let right = other.map(|n| relabel(Side::Right, n));
+        graph.digraph = graph
+            .digraph
+            .disjoint_union(&right.digraph, |_, n| n.clone());
+        graph
+    }
+
+    /// Returns the Cartesian product of the graphs: node `(a, b)` is joined
+    /// to `(a', b)` for every edge `a - a'` of this graph and to `(a, b')`
+    /// for every edge `b - b'` of `other`
+    pub fn cartesian_product<U>(&self, other: &Graph<U>) -> Graph<Pair<T, U>>
+    where
+        U: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        Graph {
+            digraph: self.digraph.cartesian_product(&other.digraph),
+        }
+    }
+
+    /// Returns the tensor (categorical) product of the graphs: node `(a, b)`
+    /// is joined to `(a', b')` for every edge `a - a'` of this graph and
+    /// every edge `b - b'` of `other`
+    pub fn tensor_product<U>(&self, other: &Graph<U>) -> Graph<Pair<T, U>>
+    where
+        U: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        Graph {
+            digraph: self.digraph.tensor_product(&other.digraph),
+        }
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the nodes and the edges (with the same value) present in both
+    /// graphs, in the order of this one and with its attributes
+    pub fn intersection(&self, other: &MultiDiGraph<T, E>) -> MultiDiGraph<T, E> {
+        self.filter_map(
+            |n| other.node_exists(n.clone()).then(|| n.clone()),
+            |a, b, e| {
+                other
+                    .is_directly_connected_by(a.clone(), b.clone(), e.clone())
+                    .then(|| e.clone())
+            },
+        )
+    }
+
+    /// Returns a graph with the nodes and edges of both graphs, with their
+    /// attributes, where node `n` of each graph is renamed to
+    /// `relabel(side, n)`, see `DiGraph::disjoint_union`
+    pub fn disjoint_union(
+        &self,
+        other: &MultiDiGraph<T, E>,
+        relabel: impl Fn(Side, &T) -> T,
+    ) -> MultiDiGraph<T, E> {
+        let mut graph = self.map(|n| relabel(Side::Left, n), E::clone);
+        let right = other.map(|n| relabel(Side::Right, n), E::clone);
+        for n in right.get_nodes() {
+            graph.add_node(n);
+        }
+        for n in right.get_nodes() {
+            for (m, e) in right.get_neighbors(n.clone()) {
+                graph.add_edge(n.clone(), m, e);
+            }
+        }
+        graph.attrs.nodes.extend(right.attrs.nodes);
+        graph.attrs.edges.extend(right.attrs.edges);
+        graph
+    }
+}
+
+impl<T, E> MultiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the nodes and the edges (with the same value) present in both
+    /// graphs, see `MultiDiGraph::intersection`
+    pub fn intersection(&self, other: &MultiGraph<T, E>) -> MultiGraph<T, E> {
+        self.filter_map(
+            |n| other.node_exists(n.clone()).then(|| n.clone()),
+            |a, b, e| {
+                other
+                    .is_directly_connected_by(a.clone(), b.clone(), e.clone())
+                    .then(|| e.clone())
+            },
+        )
+    }
+
+    /// Returns a graph with the nodes and edges of both graphs where node `n`
+    /// of each graph is renamed to `relabel(side, n)`, see
+    /// `DiGraph::disjoint_union`
+    pub fn disjoint_union(
+        &self,
+        other: &MultiGraph<T, E>,
+        relabel: impl Fn(Side, &T) -> T,
+    ) -> MultiGraph<T, E> {
+        MultiGraph {
+            multidigraph: self
+                .multidigraph
+                .disjoint_union(&other.multidigraph, relabel),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pair;
+    use super::Side;
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::multigraph::MultiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+    use crate::rugraph::IMultiGraph;
+
+    #[test]
+    fn intersection() {
+        let mut old = DiGraph::<&str>::new();
+        old.extend_with_edges([
+            ("app", "log"),
+            ("app", "net"),
+            ("net", "tls"),
+            ("log", "fmt"),
+        ]);
+        old.node_attrs_mut("net").insert("version", "1.0");
+        let mut new = DiGraph::<&str>::new();
+        new.extend_with_edges([
+            ("app", "net"),
+            ("net", "tls"),
+            ("app", "fmt"),
+            ("log", "fmt"),
+        ]);
+        let core = old.intersection(&new);
+        assert_eq!(core.get_nodes(), vec!["app", "log", "net", "tls", "fmt"]);
+        assert_eq!(core.get_neighbors("app"), vec!["net"]);
+        assert_eq!(core.get_neighbors("log"), vec!["fmt"]);
+        assert_eq!(
+            core.node_attrs(&"net").unwrap().get_str("version"),
+            Some("1.0")
+        );
+
+        let mut a = Graph::<i32>::new();
+        a.extend_with_edges([(1, 2), (2, 3)]);
+        let mut b = Graph::<i32>::new();
+        b.extend_with_edges([(3, 2), (3, 4)]);
+        let core = a.intersection(&b);
+        assert_eq!(core.get_nodes(), vec![2, 3]);
+        assert!(core.is_directly_connected(2, 3));
+
+        let mut a = MultiDiGraph::<i32, char>::new();
+        a.extend_with_edges([(1, 2, 'a'), (1, 2, 'b')]);
+        let mut b = MultiDiGraph::<i32, char>::new();
+        b.extend_with_edges([(1, 2, 'b'), (2, 1, 'a')]);
+        assert_eq!(a.intersection(&b).get_neighbors(1), vec![(2, 'b')]);
+
+        let mut a = MultiGraph::<i32, char>::new();
+        a.extend_with_edges([(1, 2, 'a'), (2, 3, 'b')]);
+        let mut b = MultiGraph::<i32, char>::new();
+        b.extend_with_edges([(2, 1, 'a'), (2, 3, 'c')]);
+        let core = a.intersection(&b);
+        assert!(core.is_directly_connected_by(1, 2, 'a'));
+        assert!(core.get_neighbors(3).is_empty());
+    }
+
+    #[test]
+    fn products() {
+        let mut path = Graph::<i32>::new();
+        path.extend_with_edges([(0, 1), (1, 2)]);
+        let mut edge = Graph::<char>::new();
+        edge.extend_with_edges([('a', 'b')]);
+
+        let ladder = path.cartesian_product(&edge);
+        assert_eq!(ladder.count_nodes(), 6);
+        assert_eq!(ladder.get_nodes()[1], Pair(0, 'b'));
+        assert_eq!(
+            ladder.get_neighbors(Pair(1, 'a')),
+            vec![Pair(0, 'a'), Pair(1, 'b'), Pair(2, 'a')]
+        );
+        assert!(ladder.is_directly_connected(Pair(2, 'b'), Pair(2, 'a')));
+
+        let tensor = path.tensor_product(&edge);
+        assert_eq!(
+            tensor.get_neighbors(Pair(1, 'a')),
+            vec![Pair(0, 'b'), Pair(2, 'b')]
+        );
+        assert!(!tensor.is_connected(Pair(0, 'a'), Pair(0, 'b')));
+
+        let mut cycle = DiGraph::<i32>::new();
+        cycle.extend_with_edges([(0, 1), (1, 0)]);
+        let mut arc = DiGraph::<i32>::new();
+        arc.extend_with_edges([(0, 1), (1, 1)]);
+        let tensor = cycle.tensor_product(&arc);
+        assert_eq!(tensor.get_neighbors(Pair(0, 0)), vec![Pair(1, 1)]);
+        assert_eq!(tensor.get_neighbors(Pair(0, 1)), vec![Pair(1, 1)]);
+        assert_eq!(tensor.get_neighbors(Pair(1, 0)), vec![Pair(0, 1)]);
+        let cartesian = cycle.cartesian_product(&arc);
+        assert_eq!(
+            cartesian.get_neighbors(Pair(0, 1)),
+            vec![Pair(0, 1), Pair(1, 1)]
+        );
+        assert_eq!(cartesian.to_dot_string("g").matches("->").count(), 8);
+        assert_eq!(Pair(1, 'x').to_string(), "(1, x)");
+    }
+
+    #[test]
+    fn disjoint_union() {
+        let mut a = DiGraph::<String>::new();
+        a.extend_with_edges([("x", "y")].map(|(a, b)| (a.to_string(), b.to_string())));
+        a.node_attrs_mut("x".to_string()).insert("color", "red");
+        let mut b = DiGraph::<String>::new();
+        b.extend_with_edges([("y", "x"), ("x", "z")].map(|(a, b)| (a.to_string(), b.to_string())));
+        let prefix = |side, n: &String| match side {
+            Side::Left => format!("a.{}", n),
+            Side::Right => format!("b.{}", n),
+        };
+        let union = a.disjoint_union(&b, prefix);
+        assert_eq!(union.get_nodes(), vec!["a.x", "a.y", "b.y", "b.x", "b.z"]);
+        assert_eq!(union.get_neighbors("b.x".to_string()), vec!["b.z"]);
+        assert!(!union.is_connected("a.x".to_string(), "b.z".to_string()));
+        assert!(union.node_attrs(&"a.x".to_string()).is_some());
+
+        let mut a = Graph::<i32>::new();
+        a.extend_with_edges([(1, 2)]);
+        let union = a.disjoint_union(&a, |side, n| if side == Side::Left { *n } else { -n });
+        assert_eq!(union.get_nodes(), vec![1, 2, -1, -2]);
+        assert!(union.is_directly_connected(-2, -1));
+
+        let mut a = MultiDiGraph::<i32, char>::new();
+        a.extend_with_edges([(1, 2, 'a'), (1, 2, 'b')]);
+        a.edge_attrs_mut(1, 2, 'b').insert("w", 1);
+        let union = a.disjoint_union(&a, |side, n| n * if side == Side::Left { 1 } else { 10 });
+        assert_eq!(union.get_neighbors(10), vec![(20, 'a'), (20, 'b')]);
+        assert!(union.edge_attrs(&10, &20, &'b').is_some());
+
+        let mut a = MultiGraph::<i32, char>::new();
+        a.extend_with_edges([(1, 2, 'a')]);
+        let union = a.disjoint_union(&a, |side, n| n + if side == Side::Left { 0 } else { 2 });
+        assert_eq!(union.count_nodes(), 4);
+        assert!(union.is_directly_connected_by(4, 3, 'a'));
+    }
+}
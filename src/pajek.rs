@@ -0,0 +1,278 @@
+use std::str::FromStr;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a Pajek `.net` representation of the graph, edges are written
+    /// as `*Arcs`
+    pub fn to_pajek_string(&self) -> String {
+        let adj = self.adjacency();
+        write_pajek("*Arcs", &adj.nodes, &adj.edges(true))
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a Pajek `.net` representation of the graph, edges are written
+    /// as `*Edges`
+    pub fn to_pajek_string(&self) -> String {
+        let adj = self.adjacency();
+        write_pajek("*Edges", &adj.nodes, &adj.edges(false))
+    }
+}
+
+/// Reads a directed graph from a Pajek `.net` file. Vertex labels (or the
+/// vertex numbers when there is no label) are parsed as `T`.
+/// `*Arcs` become edges and `*Edges` become a pair of opposite edges
+pub fn digraph_from_pajek_str<T>(content: &str) -> Result<DiGraph<T>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let net = read_pajek(content)?;
+    let mut graph = DiGraph::<T>::new();
+    let nodes = net
+        .vertices
+        .iter()
+        .map(|v| parse_value(v))
+        .collect::<Result<Vec<T>, &'static str>>()?;
+    for n in nodes.iter() {
+        graph.add_node(n.clone());
+    }
+    for (i, j, directed) in net.lines.iter() {
+        graph.add_edge(nodes[*i].clone(), nodes[*j].clone());
+        if !directed {
+            graph.add_edge(nodes[*j].clone(), nodes[*i].clone());
+        }
+    }
+    Ok(graph)
+}
+
+/// Reads an undirected graph from a Pajek `.net` file. Vertex labels (or the
+/// vertex numbers when there is no label) are parsed as `T`.
+/// Both `*Arcs` and `*Edges` become edges, the direction of arcs is dropped
+pub fn graph_from_pajek_str<T>(content: &str) -> Result<Graph<T>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let net = read_pajek(content)?;
+    let mut graph = Graph::<T>::new();
+    let nodes = net
+        .vertices
+        .iter()
+        .map(|v| parse_value(v))
+        .collect::<Result<Vec<T>, &'static str>>()?;
+    for n in nodes.iter() {
+        graph.add_node(n.clone());
+    }
+    for (i, j, _) in net.lines.iter() {
+        graph.add_edge(nodes[*i].clone(), nodes[*j].clone());
+    }
+    Ok(graph)
+}
+
+fn parse_value<V: FromStr>(s: &str) -> Result<V, &'static str> {
+    V::from_str(s).map_err(|_| "Pajek file not correct. Value can't be parsed.")
+}
+
+fn write_pajek<T>(section: &str, nodes: &[T], edges: &[(usize, usize)]) -> String
+where
+    T: std::fmt::Display,
+{
+    let mut s = format!("*Vertices {}\n", nodes.len());
+    for (i, n) in nodes.iter().enumerate() {
+        s += &format!("{} \"{}\"\n", i + 1, n.to_string().replace('"', "'"));
+    }
+    s += section;
+    s += "\n";
+    for (i, j) in edges.iter() {
+        s += &format!("{} {}\n", i + 1, j + 1);
+    }
+    s
+}
+
+/// Splits a line in words, a double quoted label is one word
+fn words(line: &str) -> Result<Vec<String>, &'static str> {
+    let mut ret = Vec::<String>::new();
+    let mut rest = line.trim_start();
+    while !rest.is_empty() {
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted
+                .find('"')
+                .ok_or("Pajek file not correct. Unterminated label.")?;
+            ret.push(quoted[..end].to_string());
+            rest = &quoted[end + 1..];
+        } else {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            ret.push(rest[..end].to_string());
+            rest = &rest[end..];
+        }
+        rest = rest.trim_start();
+    }
+    Ok(ret)
+}
+
+/// Vertex values and edges `(from, to, directed)` as indexes in `vertices`
+struct PajekNet {
+    vertices: Vec<String>,
+    lines: Vec<(usize, usize, bool)>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    None,
+    Vertices,
+    Arcs,
+    Edges,
+    ArcsList,
+    EdgesList,
+    Other,
+}
+
+fn read_pajek(content: &str) -> Result<PajekNet, &'static str> {
+    let mut net = PajekNet {
+        vertices: Vec::new(),
+        lines: Vec::new(),
+    };
+    let mut section = Section::None;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+        if line.starts_with('*') {
+            let w = words(line)?;
+            section = match w[0].to_lowercase().as_str() {
+                "*vertices" => {
+                    let n = w
+                        .get(1)
+                        .and_then(|n| n.parse::<usize>().ok())
+                        .ok_or("Pajek file not correct. Number of vertices expected.")?;
+                    net.vertices = (1..=n).map(|i| i.to_string()).collect();
+                    Section::Vertices
+                }
+                "*arcs" => Section::Arcs,
+                "*edges" => Section::Edges,
+                "*arcslist" => Section::ArcsList,
+                "*edgeslist" => Section::EdgesList,
+                _ => Section::Other,
+            };
+            continue;
+        }
+
+        let w = words(line)?;
+        let index = |s: &String| match s.parse::<usize>() {
+            Ok(i) if i >= 1 && i <= net.vertices.len() => Ok(i - 1),
+            _ => Err("Pajek file not correct. Unknown vertex."),
+        };
+        match section {
+            Section::None => return Err("Pajek file not correct. *Vertices expected."),
+            Section::Other => {}
+            Section::Vertices => {
+                let i = index(&w[0])?;
+                if let Some(label) = w.get(1) {
+                    net.vertices[i] = label.clone();
+                }
+            }
+            Section::Arcs | Section::Edges => {
+                if w.len() < 2 {
+                    return Err("Pajek file not correct. Two vertices expected.");
+                }
+                net.lines
+                    .push((index(&w[0])?, index(&w[1])?, section == Section::Arcs));
+            }
+            Section::ArcsList | Section::EdgesList => {
+                let from = index(&w[0])?;
+                for to in w[1..].iter() {
+                    net.lines
+                        .push((from, index(to)?, section == Section::ArcsList));
+                }
+            }
+        }
+    }
+    if section == Section::None {
+        return Err("Pajek file not correct. *Vertices expected.");
+    }
+    Ok(net)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::digraph_from_pajek_str;
+    use super::graph_from_pajek_str;
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn pajek_round_trip() {
+        let mut graph = DiGraph::<String>::new();
+        graph.add_node(String::from("a b"));
+        graph.add_node(String::from("c"));
+        graph.add_node(String::from("d"));
+        graph.add_edge(String::from("a b"), String::from("c"));
+        graph.add_edge(String::from("d"), String::from("c"));
+        let s = graph.to_pajek_string();
+        assert_eq!(
+            s,
+            "*Vertices 3\n1 \"a b\"\n2 \"c\"\n3 \"d\"\n*Arcs\n1 2\n3 2\n"
+        );
+        let read = digraph_from_pajek_str::<String>(&s).unwrap();
+        assert_eq!(read.get_nodes(), graph.get_nodes());
+        assert_eq!(read.get_neighbors(String::from("d")), vec!["c"]);
+
+        let mut graph = Graph::<i32>::new();
+        for n in 10..=12 {
+            graph.add_node(n);
+        }
+        graph.add_edge(10, 11);
+        graph.add_edge(11, 12);
+        let s = graph.to_pajek_string();
+        assert!(s.ends_with("*Edges\n1 2\n2 3\n"));
+        let read = graph_from_pajek_str::<i32>(&s).unwrap();
+        assert_eq!(read.get_neighbors(11), vec![10, 12]);
+    }
+
+    #[test]
+    fn pajek_sections() {
+        let content = "% comment
+*Vertices 4
+1 \"first\" 0.1 0.2 0.5
+2 \"second\"
+3
+*Arcs
+1 2 1.5
+*Edges
+2 3
+*Arcslist
+4 1 2
+";
+        let graph = digraph_from_pajek_str::<String>(content).unwrap();
+        assert_eq!(graph.get_nodes(), vec!["first", "second", "3", "4"]);
+        assert_eq!(graph.get_neighbors(String::from("second")), vec!["3"]);
+        assert_eq!(graph.get_neighbors(String::from("3")), vec!["second"]);
+        assert_eq!(
+            graph.get_neighbors(String::from("4")),
+            vec!["first", "second"]
+        );
+
+        let graph = graph_from_pajek_str::<String>(content).unwrap();
+        assert_eq!(
+            graph.get_neighbors(String::from("second")),
+            vec!["first", "3", "4"]
+        );
+
+        assert!(graph_from_pajek_str::<String>("1 2").is_err());
+        assert!(graph_from_pajek_str::<String>("*Vertices 2\n*Edges\n1 3").is_err());
+        assert!(graph_from_pajek_str::<i32>("*Vertices 1\n1 \"x\"").is_err());
+    }
+}
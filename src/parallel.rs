@@ -0,0 +1,387 @@
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::vec::Vec;
+
+use crate::adjacency::Adjacency;
+use crate::csr::CsrGraph;
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::rugraph::IGraph;
+use crate::rugraph::PathBounds;
+use crate::visit::VisitMap;
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + Send + Sync,
+{
+    /// Same as `all_simple_paths` but the paths going through every neighbor
+    /// of `from` are searched in parallel, each with its own visited set.
+    /// The paths are returned in the same order
+    pub fn par_all_simple_paths(&self, from: T, to: T) -> Vec<Vec<T>> {
+        par_simple_paths(&self.adjacency(), &from, &to)
+    }
+
+    /// Returns a parallel iterator over the nodes, in insertion order
+    pub fn par_nodes(&self) -> impl IndexedParallelIterator<Item = &T> + '_ {
+        (0..self.count_nodes())
+            .into_par_iter()
+            .map(|i| self.node(i).unwrap())
+    }
+
+    /// Returns a parallel iterator over the edges as `(from, to)`
+    pub fn par_edges(&self) -> impl ParallelIterator<Item = (&T, &T)> + '_ {
+        self.par_nodes().enumerate().flat_map_iter(move |(i, n)| {
+            self.neighbor_ids(i)
+                .iter()
+                .map(move |j| (n, self.node(*j).unwrap()))
+        })
+    }
+
+    /// Returns a graph with the edges `(from, to)` of `edges` and their
+    /// nodes, building the neighbor lists in parallel. Repeated edges are
+    /// added once. Unlike adding the edges one by one, nodes are sorted and
+    /// so are the neighbors of every node
+    pub fn from_par_edges<I>(edges: I) -> Self
+    where
+        I: IntoParallelIterator<Item = (T, T)>,
+    {
+        let edges: Vec<(T, T)> = edges.into_par_iter().collect();
+        let mut nodes: Vec<T> = edges
+            .par_iter()
+            .flat_map_iter(|(from, to)| [from.clone(), to.clone()])
+            .collect();
+        nodes.par_sort_unstable();
+        nodes.dedup();
+
+        let position = |n: &T| nodes.binary_search(n).unwrap();
+        let mut pairs: Vec<(usize, usize)> = edges
+            .par_iter()
+            .map(|(from, to)| (position(from), position(to)))
+            .collect();
+        pairs.par_sort_unstable();
+        pairs.dedup();
+
+        // pairs are sorted by source, split them into one slice per node
+        let mut bounds = vec![0; nodes.len() + 1];
+        for (from, _) in pairs.iter() {
+            bounds[*from + 1] += 1;
+        }
+        for i in 0..nodes.len() {
+            bounds[i + 1] += bounds[i];
+        }
+        let succ: Vec<Vec<usize>> = (0..nodes.len())
+            .into_par_iter()
+            .map(|i| {
+                pairs[bounds[i]..bounds[i + 1]]
+                    .iter()
+                    .map(|p| p.1)
+                    .collect()
+            })
+            .collect();
+        DiGraph::from_index_lists(nodes, succ)
+    }
+
+    /// Returns the number of hops from node `from` to every node reachable
+    /// from it, exploring every BFS level in parallel. Meant for graphs with
+    /// millions of edges, on small ones `DiGraph::freeze` and
+    /// `CsrGraph::bfs_distances` are faster
+    pub fn parallel_distances_from(&self, from: T) -> BTreeMap<T, usize> {
+        self.freeze().parallel_distances_map(&from)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + Send + Sync,
+{
+    /// Same as `all_simple_paths` but the paths going through every neighbor
+    /// of `from` are searched in parallel. The paths are returned in the
+    /// same order
+    pub fn par_all_simple_paths(&self, from: T, to: T) -> Vec<Vec<T>> {
+        par_simple_paths(&self.adjacency(), &from, &to)
+    }
+
+    /// Returns a parallel iterator over the nodes, in insertion order
+    pub fn par_nodes(&self) -> impl IndexedParallelIterator<Item = &T> + '_ {
+        self.digraph.par_nodes()
+    }
+
+    /// Returns a parallel iterator over the edges as `(a, b)`, every edge
+    /// once
+    pub fn par_edges(&self) -> impl ParallelIterator<Item = (&T, &T)> + '_ {
+        self.par_nodes().enumerate().flat_map_iter(move |(i, n)| {
+            self.neighbor_ids(i)
+                .iter()
+                .filter(move |j| **j >= i)
+                .map(move |j| (n, self.node(*j).unwrap()))
+        })
+    }
+
+    /// Returns the number of hops from node `from` to every node reachable
+    /// from it, exploring every BFS level in parallel
+    pub fn parallel_distances_from(&self, from: T) -> BTreeMap<T, usize> {
+        self.digraph.parallel_distances_from(from)
+    }
+}
+
+impl<T> CsrGraph<T>
+where
+    T: Ord + Clone + Sync,
+{
+    /// Same as `bfs_distances` but the nodes of every BFS level are expanded
+    /// in parallel
+    pub fn parallel_distances_from(&self, from: usize) -> Vec<Option<usize>> {
+        let dist: Vec<AtomicUsize> = (0..self.count_nodes())
+            .map(|_| AtomicUsize::new(usize::MAX))
+            .collect();
+        dist[from].store(0, Ordering::Relaxed);
+        let mut frontier = vec![from];
+        let mut d = 0;
+        while !frontier.is_empty() {
+            d += 1;
+            frontier = frontier
+                .par_iter()
+                .flat_map_iter(|i| self.neighbors(*i).iter().copied())
+                .filter(|j| {
+                    dist[*j]
+                        .compare_exchange(usize::MAX, d, Ordering::Relaxed, Ordering::Relaxed)
+                        .is_ok()
+                })
+                .collect();
+        }
+        dist.into_iter()
+            .map(|d| Some(d.into_inner()).filter(|d| *d != usize::MAX))
+            .collect()
+    }
+
+    /// Parallel distances from node `from` keyed by node, without the
+    /// unreachable ones
+    fn parallel_distances_map(&self, from: &T) -> BTreeMap<T, usize> {
+        let Some(i) = self.index_of(from) else {
+            return BTreeMap::new();
+        };
+        self.parallel_distances_from(i)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(j, d)| Some((self.node(j).clone(), d?)))
+            .collect()
+    }
+}
+
+/// Splits the search of the simple paths from `from` to `to` by first hop
+fn par_simple_paths<T>(adj: &Adjacency<T>, from: &T, to: &T) -> Vec<Vec<T>>
+where
+    T: Ord + Clone + Send + Sync,
+{
+    let (Some(i), Some(j)) = (
+        adj.nodes.iter().position(|n| n == from),
+        adj.nodes.iter().position(|n| n == to),
+    ) else {
+        return Vec::new();
+    };
+    if i == j {
+        return vec![vec![from.clone()]];
+    }
+    let bounds = PathBounds {
+        max_len: usize::MAX,
+        limit: usize::MAX,
+    };
+    adj.succ[i]
+        .par_iter()
+        .flat_map_iter(|first| {
+            let mut paths = Vec::<Vec<T>>::new();
+            let mut current_path = vec![i];
+            let mut visited = VisitMap::new(adj.len());
+            visited.visit(i);
+            simple_paths_dfs(
+                adj,
+                *first,
+                j,
+                &bounds,
+                &mut paths,
+                &mut current_path,
+                &mut visited,
+            );
+            paths
+        })
+        .collect()
+}
+
+/// Deep first search from node `from` extending `current_path` until `to`,
+/// collecting the simple paths within `bounds` in `paths`. `visited` marks
+/// the nodes in the path
+fn simple_paths_dfs<T: Ord + Clone>(
+    adj: &Adjacency<T>,
+    from: usize,
+    to: usize,
+    bounds: &PathBounds,
+    paths: &mut Vec<Vec<T>>,
+    current_path: &mut Vec<usize>,
+    visited: &mut VisitMap,
+) {
+    if visited.is_visited(from) {
+        return;
+    }
+    current_path.push(from);
+    if from == to {
+        paths.push(current_path.iter().map(|i| adj.nodes[*i].clone()).collect());
+    } else if current_path.len() <= bounds.max_len {
+        visited.visit(from);
+        for n in adj.succ[from].iter() {
+            if bounds.done(paths) {
+                break;
+            }
+            simple_paths_dfs(adj, *n, to, bounds, paths, current_path, visited);
+        }
+        visited.unvisit(from);
+    }
+    current_path.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn par_all_simple_paths() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 0..12 {
+            graph.add_node(n);
+        }
+        for n in 0..12 {
+            for m in [n + 1, n + 2, n + 3] {
+                if m < 12 {
+                    graph.add_edge(n, m);
+                }
+            }
+        }
+        graph.add_edge(11, 0);
+        let paths = graph.par_all_simple_paths(0, 11);
+        assert_eq!(paths.len(), 504);
+        assert_eq!(paths, graph.all_simple_paths(0, 11));
+        assert_eq!(graph.par_all_simple_paths(3, 3), vec![vec![3]]);
+        assert!(graph.par_all_simple_paths(0, 20).is_empty());
+
+        let mut graph = Graph::<i32>::new();
+        for n in 1..=4 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(1, 3);
+        graph.add_edge(3, 4);
+        assert_eq!(
+            graph.par_all_simple_paths(1, 4),
+            graph.all_simple_paths(1, 4)
+        );
+        assert_eq!(graph.par_all_simple_paths(1, 4).len(), 2);
+    }
+
+    #[test]
+    fn from_par_edges() {
+        let edges: Vec<(u32, u32)> = (0..10_000).map(|n| ((n * 7) % 1000, n % 997)).collect();
+        let graph = DiGraph::from_par_edges(edges.clone());
+
+        let mut expected = DiGraph::<u32>::new();
+        let mut sorted = edges.clone();
+        sorted.sort();
+        for (from, to) in sorted.iter() {
+            expected.add_node(*from);
+            expected.add_node(*to);
+        }
+        for (from, to) in sorted.iter() {
+            expected.add_edge(*from, *to);
+        }
+        let mut nodes = expected.get_nodes();
+        nodes.sort();
+        assert_eq!(graph.get_nodes(), nodes);
+        for n in nodes {
+            let mut neighbors = expected.get_neighbors(n);
+            neighbors.sort();
+            assert_eq!(graph.get_neighbors(n), neighbors);
+        }
+        assert!(graph.is_directly_connected(7, 1));
+
+        let graph = DiGraph::from_par_edges(vec![("a", "b"), ("b", "a"), ("a", "b")]);
+        assert_eq!(graph.get_nodes(), vec!["a", "b"]);
+        assert_eq!(graph.get_neighbors("a"), vec!["b"]);
+        assert!(DiGraph::<i32>::from_par_edges(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn par_nodes_and_edges() {
+        let mut graph = DiGraph::<u32>::new();
+        for n in 0..1000 {
+            graph.add_node(n);
+        }
+        for n in 0..1000 {
+            graph.add_edge(n, (n + 1) % 1000);
+            graph.add_edge(n, (n * 3) % 1000);
+        }
+        let nodes: Vec<u32> = graph.par_nodes().copied().collect();
+        assert_eq!(nodes, graph.get_nodes());
+        let max_degree = graph
+            .par_nodes()
+            .map(|n| graph.get_neighbors(*n).len())
+            .max();
+        assert_eq!(max_degree, Some(2));
+        assert_eq!(graph.par_edges().count(), 2000);
+        assert!(graph
+            .par_edges()
+            .all(|(a, b)| graph.is_directly_connected(*a, *b)));
+
+        let mut graph = Graph::<&str>::new();
+        for n in ["a", "b", "c"] {
+            graph.add_node(n);
+        }
+        graph.add_edge("a", "b");
+        graph.add_edge("c", "b");
+        graph.add_edge("c", "c");
+        assert_eq!(graph.par_nodes().count(), 3);
+        let mut edges: Vec<(&str, &str)> = graph.par_edges().map(|(a, b)| (*a, *b)).collect();
+        edges.sort();
+        assert_eq!(edges, vec![("a", "b"), ("b", "c"), ("c", "c")]);
+    }
+
+    #[test]
+    fn parallel_distances_from() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 0..1000 {
+            graph.add_node(n);
+        }
+        for n in 0..999 {
+            graph.add_edge(n, n + 1);
+            graph.add_edge(n, (n * 7) % 1000);
+        }
+        graph.add_node(1000);
+        let csr = graph.freeze();
+        assert_eq!(csr.parallel_distances_from(0), csr.bfs_distances(0));
+        assert_eq!(csr.parallel_distances_from(500), csr.bfs_distances(500));
+
+        let dist = graph.parallel_distances_from(0);
+        assert_eq!(dist.len(), 1000);
+        assert_eq!(dist[&0], 0);
+        assert_eq!(dist[&7], 2);
+        assert!(!dist.contains_key(&1000));
+        assert!(graph.parallel_distances_from(2000).is_empty());
+
+        let mut graph = Graph::<&str>::new();
+        for n in ["a", "b", "c", "d"] {
+            graph.add_node(n);
+        }
+        graph.add_edge("a", "b");
+        graph.add_edge("c", "b");
+        let dist = graph.parallel_distances_from("c");
+        assert_eq!(
+            dist.into_iter().collect::<Vec<_>>(),
+            vec![("a", 2), ("b", 1), ("c", 0)]
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use crate::attributes::AttrMap;
+use crate::digraph::DiGraph;
+use crate::multidigraph::MultiDiGraph;
+use crate::query::quoted;
+use crate::query::AttrQuery;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+/// A node of a pattern
+#[derive(Debug, Clone, PartialEq)]
+enum NodeTerm {
+    /// `*`, any node
+    Any,
+    /// A name, bound to the matched node
+    Var(String),
+    /// A quoted string or a number, the node displayed as that text
+    Node(String),
+}
+
+/// A chain of nodes joined by edges, `edges[i]` going from `nodes[i]` to
+/// `nodes[i + 1]` and filtered by the query between its brackets, if any
+struct Pattern {
+    nodes: Vec<NodeTerm>,
+    edges: Vec<Option<AttrQuery>>,
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> Result<Self, &'static str> {
+        let mut chars = pattern.chars().peekable();
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        while let Some(c) = chars.next() {
+            let expect_node = nodes.len() == edges.len();
+            if c.is_whitespace() {
+                continue;
+            }
+            if c == '-' {
+                if expect_node {
+                    return Err("Pattern not correct. Expected a node.");
+                }
+                edges.push(match chars.next() {
+                    Some('>') => None,
+                    Some('[') => {
+                        let mut filter = String::new();
+                        loop {
+                            match chars.next() {
+                                None => return Err("Pattern not correct. Expected ']'."),
+                                Some(']') => break,
+                                Some(q) if q == '\'' || q == '"' => {
+                                    let s = quoted(&mut chars, q)?;
+                                    filter.push(q);
+                                    for c in s.chars() {
+                                        if c == q || c == '\\' {
+                                            filter.push('\\');
+                                        }
+                                        filter.push(c);
+                                    }
+                                    filter.push(q);
+                                }
+                                Some(c) => filter.push(c),
+                            }
+                        }
+                        if chars.next() != Some('-') || chars.next() != Some('>') {
+                            return Err("Pattern not correct. Expected ']->'.");
+                        }
+                        Some(AttrQuery::parse(&filter)?)
+                    }
+                    _ => return Err("Pattern not correct. Expected '->'."),
+                });
+                continue;
+            }
+            if !expect_node {
+                return Err("Pattern not correct. Expected '->'.");
+            }
+            nodes.push(match c {
+                '*' => NodeTerm::Any,
+                '\'' | '"' => NodeTerm::Node(quoted(&mut chars, c)?),
+                c if c.is_alphanumeric() || c == '_' => {
+                    let mut name = String::from(c);
+                    while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                        name.push(c);
+                    }
+                    if c.is_ascii_digit() {
+                        NodeTerm::Node(name)
+                    } else {
+                        NodeTerm::Var(name)
+                    }
+                }
+                _ => return Err("Pattern not correct. Unexpected character."),
+            });
+        }
+        if nodes.is_empty() || nodes.len() == edges.len() {
+            return Err("Pattern not correct. Expected a node.");
+        }
+        Ok(Pattern { nodes, edges })
+    }
+}
+
+/// A match of a pattern: the nodes matched by every node of the pattern, in
+/// order, and the nodes bound to every name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternMatch<T> {
+    pub path: Vec<T>,
+    pub bindings: BTreeMap<String, T>,
+}
+
+/// Successors of every node with the attributes of the edge leading to them
+type EdgeLists = Vec<Vec<(usize, AttrMap)>>;
+
+struct Matcher<'a, T> {
+    pattern: &'a Pattern,
+    nodes: &'a [T],
+    /// Nodes as text, matched by quoted strings and numbers
+    labels: Vec<String>,
+    succ: &'a EdgeLists,
+}
+
+impl<T> Matcher<'_, T>
+where
+    T: Clone + std::fmt::Display,
+{
+    /// Extends the partial match `path` with node `i` matched by node `step`
+    /// of the pattern, collecting the complete matches in `out`
+    fn extend(
+        &self,
+        i: usize,
+        path: &mut Vec<usize>,
+        bindings: &mut BTreeMap<String, usize>,
+        out: &mut Vec<PatternMatch<T>>,
+    ) {
+        let step = path.len();
+        let mut bound = None;
+        match &self.pattern.nodes[step] {
+            NodeTerm::Any => {}
+            NodeTerm::Node(label) => {
+                if self.labels[i] != *label {
+                    return;
+                }
+            }
+            NodeTerm::Var(name) => match bindings.get(name) {
+                Some(j) if *j != i => return,
+                Some(_) => {}
+                None => {
+                    bindings.insert(name.clone(), i);
+                    bound = Some(name);
+                }
+            },
+        }
+        path.push(i);
+        if path.len() == self.pattern.nodes.len() {
+            out.push(PatternMatch {
+                path: path.iter().map(|j| self.nodes[*j].clone()).collect(),
+                bindings: bindings
+                    .iter()
+                    .map(|(k, j)| (k.clone(), self.nodes[*j].clone()))
+                    .collect(),
+            });
+        } else {
+            let filter = &self.pattern.edges[step];
+            // parallel edges lead to the same match
+            let mut next: Vec<usize> = self.succ[i]
+                .iter()
+                .filter(|(_, attrs)| filter.as_ref().is_none_or(|q| q.matches(attrs)))
+                .map(|(j, _)| *j)
+                .collect();
+            next.sort_unstable();
+            next.dedup();
+            for j in next {
+                self.extend(j, path, bindings, out);
+            }
+        }
+        path.pop();
+        if let Some(name) = bound {
+            bindings.remove(name);
+        }
+    }
+}
+
+fn find_matches<T>(
+    pattern: &str,
+    nodes: &[T],
+    succ: &EdgeLists,
+) -> Result<Vec<PatternMatch<T>>, &'static str>
+where
+    T: Clone + std::fmt::Display,
+{
+    let pattern = Pattern::parse(pattern)?;
+    let matcher = Matcher {
+        pattern: &pattern,
+        nodes,
+        labels: nodes.iter().map(|n| n.to_string()).collect(),
+        succ,
+    };
+    let mut out = Vec::new();
+    for i in 0..nodes.len() {
+        matcher.extend(i, &mut Vec::new(), &mut BTreeMap::new(), &mut out);
+    }
+    Ok(out)
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the matches of `pattern`, a chain of nodes joined by `->`
+    /// such as `x -> * -> 'd'`. A node is `*` (any node), a quoted string or
+    /// a number (the node displayed as that text) or a name, bound to the
+    /// matched node: every occurrence of a name matches the same node.
+    /// `-[query]->` only follows edges whose attributes satisfy `query`, see
+    /// `AttrQuery`. Nodes may repeat along a match.
+    /// Fails if the pattern is not valid
+    pub fn query(&self, pattern: &str) -> Result<Vec<PatternMatch<T>>, &'static str> {
+        let nodes = self.get_nodes();
+        let succ: EdgeLists = (0..nodes.len())
+            .map(|i| {
+                self.neighbor_ids(i)
+                    .iter()
+                    .map(|j| {
+                        let attrs = self.edge_attrs(&nodes[i], &nodes[*j]);
+                        (*j, attrs.cloned().unwrap_or_default())
+                    })
+                    .collect()
+            })
+            .collect();
+        find_matches(pattern, &nodes, &succ)
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the matches of `pattern`, see `DiGraph::query`. Edge queries
+    /// also see the value of the edge, as text, as attribute `label`: e.g.
+    /// `x -[label == 'ab']-> y`
+    pub fn query(&self, pattern: &str) -> Result<Vec<PatternMatch<T>>, &'static str> {
+        let nodes = self.get_nodes();
+        let index: BTreeMap<&T, usize> = nodes.iter().enumerate().map(|(i, n)| (n, i)).collect();
+        let succ: EdgeLists = nodes
+            .iter()
+            .map(|n| {
+                self.get_neighbors(n.clone())
+                    .into_iter()
+                    .map(|(m, e)| {
+                        let mut attrs = self.edge_attrs(n, &m, &e).cloned().unwrap_or_default();
+                        attrs.insert("label", e.to_string());
+                        (index[&m], attrs)
+                    })
+                    .collect()
+            })
+            .collect();
+        find_matches(pattern, &nodes, &succ)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn digraph_query() {
+        let mut graph = DiGraph::<&str>::new();
+        for n in ["a", "b", "c", "d"] {
+            graph.add_node(n);
+        }
+        graph.add_edge("a", "b");
+        graph.add_edge("a", "c");
+        graph.add_edge("b", "d");
+        graph.add_edge("c", "d");
+        graph.add_edge("d", "a");
+        graph.edge_attrs_mut("a", "c").insert("cost", 5);
+
+        let paths: Vec<Vec<&str>> = graph
+            .query("'a' -> * -> 'd'")
+            .unwrap()
+            .into_iter()
+            .map(|m| m.path)
+            .collect();
+        assert_eq!(paths, vec![vec!["a", "b", "d"], vec!["a", "c", "d"]]);
+
+        let matches = graph.query("x -[cost > 2]-> y").unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].bindings["x"], "a");
+        assert_eq!(matches[0].bindings["y"], "c");
+
+        let cycles = graph.query("x -> * -> * -> x").unwrap();
+        let starts: Vec<&str> = cycles.iter().map(|m| m.bindings["x"]).collect();
+        assert_eq!(starts, vec!["a", "a", "b", "c", "d", "d"]);
+        assert_eq!(graph.query("x").unwrap().len(), 4);
+        assert!(graph.query("'b' -> 'c'").unwrap().is_empty());
+
+        assert!(graph.query("").is_err());
+        assert!(graph.query("a ->").is_err());
+        assert!(graph.query("-> a").is_err());
+        assert!(graph.query("a b").is_err());
+        assert!(graph.query("a -[cost >]-> b").is_err());
+        assert!(graph.query("a -[cost > 1] b").is_err());
+    }
+
+    #[test]
+    fn multidigraph_query() {
+        let mut graph = MultiDiGraph::<i32, String>::new();
+        for n in 1..=3 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2, "ab".to_string());
+        graph.add_edge(1, 2, "cd".to_string());
+        graph.add_edge(2, 3, "ab".to_string());
+        graph.add_edge(1, 3, "x]".to_string());
+
+        let matches = graph.query("x -[label='ab']-> y").unwrap();
+        let pairs: Vec<(i32, i32)> = matches
+            .iter()
+            .map(|m| (m.bindings["x"], m.bindings["y"]))
+            .collect();
+        assert_eq!(pairs, vec![(1, 2), (2, 3)]);
+        assert_eq!(graph.query("1 -> *").unwrap().len(), 2);
+        assert_eq!(graph.query("1 -[label == 'x]']-> 3").unwrap().len(), 1);
+        assert_eq!(graph.query("x -> y -> z").unwrap()[0].path, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,470 @@
+use rusqlite::params;
+use rusqlite::types::Type;
+use rusqlite::types::Value;
+use rusqlite::Connection;
+use rusqlite::Result;
+use rusqlite::Transaction;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+use std::vec::Vec;
+
+use crate::attributes::AttrMap;
+use crate::attributes::AttrValue;
+use crate::digraph::DiGraph;
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+/// Tables of the store, shared by every graph. Nodes and edge values are
+/// stored as text, edges of directed graphs have an empty label. Rows are
+/// loaded in insertion order
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS nodes (
+    graph TEXT NOT NULL, node TEXT NOT NULL,
+    PRIMARY KEY (graph, node));
+CREATE TABLE IF NOT EXISTS edges (
+    graph TEXT NOT NULL, source TEXT NOT NULL, target TEXT NOT NULL, label TEXT NOT NULL,
+    PRIMARY KEY (graph, source, target, label));
+CREATE TABLE IF NOT EXISTS node_attrs (
+    graph TEXT NOT NULL, node TEXT NOT NULL, key TEXT NOT NULL, value,
+    PRIMARY KEY (graph, node, key));
+CREATE TABLE IF NOT EXISTS edge_attrs (
+    graph TEXT NOT NULL, source TEXT NOT NULL, target TEXT NOT NULL, label TEXT NOT NULL,
+    key TEXT NOT NULL, value,
+    PRIMARY KEY (graph, source, target, label, key));
+";
+
+/// `SqliteGraphStore` keeps named `DiGraph`s and `MultiDiGraph`s, with their
+/// node and edge attributes, in a SQLite file. Graphs are written whole with
+/// `save_digraph` and `save_multidigraph`, or kept up to date one change at
+/// a time with `insert_node`, `insert_edge` and the like, so the file never
+/// needs to be rewritten. Nodes and edge values are stored as text and
+/// parsed back with `FromStr`
+pub struct SqliteGraphStore {
+    conn: Connection,
+}
+
+impl SqliteGraphStore {
+    /// Opens the store in the SQLite file `path`, creating it if needed
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// Opens a store kept in memory, lost when dropped
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(SqliteGraphStore { conn })
+    }
+
+    /// Returns the names of the stored graphs with at least a node
+    pub fn graphs(&self) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT graph FROM nodes ORDER BY graph")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect()
+    }
+
+    /// Deletes graph `graph`
+    pub fn delete_graph(&mut self, graph: &str) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        delete_graph(&tx, graph)?;
+        tx.commit()
+    }
+
+    /// Stores `digraph` as graph `graph`, replacing it
+    pub fn save_digraph<T>(&mut self, graph: &str, digraph: &DiGraph<T>) -> Result<()>
+    where
+        T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        let tx = self.conn.transaction()?;
+        delete_graph(&tx, graph)?;
+        for n in digraph.get_nodes() {
+            insert_node(&tx, graph, &n, digraph.node_attrs(&n))?;
+            for m in digraph.get_neighbors(n.clone()) {
+                insert_edge(&tx, graph, (&n, &m, ""), digraph.edge_attrs(&n, &m))?;
+            }
+        }
+        tx.commit()
+    }
+
+    /// Loads the directed graph stored as `graph`, empty if there is none
+    pub fn load_digraph<T>(&self, graph: &str) -> Result<DiGraph<T>>
+    where
+        T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+    {
+        let mut ret = DiGraph::<T>::new();
+        for (node, attrs) in self.load_nodes(graph)? {
+            let node: T = parse(&node)?;
+            if !attrs.is_empty() {
+                *ret.node_attrs_mut(node.clone()) = attrs;
+            }
+            ret.add_node(node);
+        }
+        for (from, to, _, attrs) in self.load_edges(graph)? {
+            let (from, to): (T, T) = (parse(&from)?, parse(&to)?);
+            if !attrs.is_empty() {
+                *ret.edge_attrs_mut(from.clone(), to.clone()) = attrs;
+            }
+            ret.add_edge(from, to);
+        }
+        Ok(ret)
+    }
+
+    /// Stores `multidigraph` as graph `graph`, replacing it
+    pub fn save_multidigraph<T, E>(
+        &mut self,
+        graph: &str,
+        multidigraph: &MultiDiGraph<T, E>,
+    ) -> Result<()>
+    where
+        T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+        E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        let tx = self.conn.transaction()?;
+        delete_graph(&tx, graph)?;
+        for n in multidigraph.get_nodes() {
+            insert_node(&tx, graph, &n, multidigraph.node_attrs(&n))?;
+            for (m, e) in multidigraph.get_neighbors(n.clone()) {
+                let attrs = multidigraph.edge_attrs(&n, &m, &e);
+                insert_edge(&tx, graph, (&n, &m, &e.to_string()), attrs)?;
+            }
+        }
+        tx.commit()
+    }
+
+    /// Loads the multi directed graph stored as `graph`, empty if there is
+    /// none
+    pub fn load_multidigraph<T, E>(&self, graph: &str) -> Result<MultiDiGraph<T, E>>
+    where
+        T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+        E: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+    {
+        let mut ret = MultiDiGraph::<T, E>::new();
+        for (node, attrs) in self.load_nodes(graph)? {
+            let node: T = parse(&node)?;
+            if !attrs.is_empty() {
+                *ret.node_attrs_mut(node.clone()) = attrs;
+            }
+            ret.add_node(node);
+        }
+        for (from, to, label, attrs) in self.load_edges(graph)? {
+            let (from, to, edge): (T, T, E) = (parse(&from)?, parse(&to)?, parse(&label)?);
+            if !attrs.is_empty() {
+                *ret.edge_attrs_mut(from.clone(), to.clone(), edge.clone()) = attrs;
+            }
+            ret.add_edge(from, to, edge);
+        }
+        Ok(ret)
+    }
+
+    /// Adds node `node` to graph `graph`
+    pub fn insert_node<T: Display>(&self, graph: &str, node: &T) -> Result<()> {
+        insert_node(&self.conn, graph, node, None)
+    }
+
+    /// Removes node `node` of graph `graph` with its edges and attributes
+    pub fn remove_node<T: Display>(&mut self, graph: &str, node: &T) -> Result<()> {
+        let node = node.to_string();
+        let tx = self.conn.transaction()?;
+        for sql in [
+            "DELETE FROM nodes WHERE graph = ?1 AND node = ?2",
+            "DELETE FROM node_attrs WHERE graph = ?1 AND node = ?2",
+            "DELETE FROM edges WHERE graph = ?1 AND (source = ?2 OR target = ?2)",
+            "DELETE FROM edge_attrs WHERE graph = ?1 AND (source = ?2 OR target = ?2)",
+        ] {
+            tx.execute(sql, params![graph, node])?;
+        }
+        tx.commit()
+    }
+
+    /// Adds an edge from `from` to `to` to directed graph `graph`
+    pub fn insert_edge<T: Display>(&self, graph: &str, from: &T, to: &T) -> Result<()> {
+        insert_edge(&self.conn, graph, (from, to, ""), None)
+    }
+
+    /// Removes the edge from `from` to `to` of directed graph `graph`
+    pub fn remove_edge<T: Display>(&mut self, graph: &str, from: &T, to: &T) -> Result<()> {
+        self.delete_edge(graph, from, to, "")
+    }
+
+    /// Adds an edge from `from` to `to` labeled `edge` to multi directed
+    /// graph `graph`
+    pub fn insert_labeled_edge<T: Display, E: Display>(
+        &self,
+        graph: &str,
+        from: &T,
+        to: &T,
+        edge: &E,
+    ) -> Result<()> {
+        insert_edge(&self.conn, graph, (from, to, &edge.to_string()), None)
+    }
+
+    /// Removes the edge from `from` to `to` labeled `edge` of multi directed
+    /// graph `graph`
+    pub fn remove_labeled_edge<T: Display, E: Display>(
+        &mut self,
+        graph: &str,
+        from: &T,
+        to: &T,
+        edge: &E,
+    ) -> Result<()> {
+        self.delete_edge(graph, from, to, &edge.to_string())
+    }
+
+    fn delete_edge<T: Display>(
+        &mut self,
+        graph: &str,
+        from: &T,
+        to: &T,
+        label: &str,
+    ) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        for sql in [
+            "DELETE FROM edges WHERE graph = ?1 AND source = ?2 AND target = ?3 AND label = ?4",
+            "DELETE FROM edge_attrs WHERE graph = ?1 AND source = ?2 AND target = ?3 AND label = ?4",
+        ] {
+            tx.execute(sql, params![graph, from.to_string(), to.to_string(), label])?;
+        }
+        tx.commit()
+    }
+
+    /// Sets attribute `key` of node `node` of graph `graph`
+    pub fn set_node_attr<T: Display>(
+        &self,
+        graph: &str,
+        node: &T,
+        key: &str,
+        value: impl Into<AttrValue>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO node_attrs VALUES (?1, ?2, ?3, ?4)",
+            params![graph, node.to_string(), key, to_sql(&value.into())],
+        )?;
+        Ok(())
+    }
+
+    /// Returns the nodes of `graph` with their attributes
+    fn load_nodes(&self, graph: &str) -> Result<Vec<(String, AttrMap)>> {
+        let mut attrs = self.conn.prepare(
+            "SELECT key, value FROM node_attrs WHERE graph = ?1 AND node = ?2 ORDER BY rowid",
+        )?;
+        let mut stmt = self
+            .conn
+            .prepare("SELECT node FROM nodes WHERE graph = ?1 ORDER BY rowid")?;
+        let nodes = stmt.query_map([graph], |row| row.get::<_, String>(0))?;
+        let mut ret = Vec::new();
+        for node in nodes {
+            let node = node?;
+            let map = load_attrs(attrs.query(params![graph, node])?)?;
+            ret.push((node, map));
+        }
+        Ok(ret)
+    }
+
+    /// Returns the edges of `graph` as `(source, target, label)` with their
+    /// attributes
+    fn load_edges(&self, graph: &str) -> Result<Vec<(String, String, String, AttrMap)>> {
+        let mut attrs = self.conn.prepare(
+            "SELECT key, value FROM edge_attrs
+             WHERE graph = ?1 AND source = ?2 AND target = ?3 AND label = ?4 ORDER BY rowid",
+        )?;
+        let mut stmt = self
+            .conn
+            .prepare("SELECT source, target, label FROM edges WHERE graph = ?1 ORDER BY rowid")?;
+        let edges = stmt.query_map([graph], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+        let mut ret = Vec::new();
+        for edge in edges {
+            let (from, to, label) = edge?;
+            let map = load_attrs(attrs.query(params![graph, from, to, label])?)?;
+            ret.push((from, to, label, map));
+        }
+        Ok(ret)
+    }
+}
+
+fn delete_graph(tx: &Transaction, graph: &str) -> Result<()> {
+    for table in ["nodes", "edges", "node_attrs", "edge_attrs"] {
+        tx.execute(&format!("DELETE FROM {} WHERE graph = ?1", table), [graph])?;
+    }
+    Ok(())
+}
+
+fn insert_node<T: Display>(
+    conn: &Connection,
+    graph: &str,
+    node: &T,
+    attrs: Option<&AttrMap>,
+) -> Result<()> {
+    let node = node.to_string();
+    conn.execute(
+        "INSERT OR IGNORE INTO nodes VALUES (?1, ?2)",
+        params![graph, node],
+    )?;
+    for (key, value) in attrs.into_iter().flat_map(|a| a.iter()) {
+        conn.execute(
+            "INSERT OR REPLACE INTO node_attrs VALUES (?1, ?2, ?3, ?4)",
+            params![graph, node, key, to_sql(value)],
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_edge<T: Display>(
+    conn: &Connection,
+    graph: &str,
+    (from, to, label): (&T, &T, &str),
+    attrs: Option<&AttrMap>,
+) -> Result<()> {
+    let (from, to) = (from.to_string(), to.to_string());
+    conn.execute(
+        "INSERT OR IGNORE INTO edges VALUES (?1, ?2, ?3, ?4)",
+        params![graph, from, to, label],
+    )?;
+    for (key, value) in attrs.into_iter().flat_map(|a| a.iter()) {
+        conn.execute(
+            "INSERT OR REPLACE INTO edge_attrs VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![graph, from, to, label, key, to_sql(value)],
+        )?;
+    }
+    Ok(())
+}
+
+/// Booleans are stored as the text `true` or `false` with a `b:` prefix
+/// that tells them apart from strings, which get an `s:` prefix
+fn to_sql(value: &AttrValue) -> Value {
+    match value {
+        AttrValue::Bool(b) => Value::Text(format!("b:{}", b)),
+        AttrValue::Int(i) => Value::Integer(*i),
+        AttrValue::Float(x) => Value::Real(*x),
+        AttrValue::Str(s) => Value::Text(format!("s:{}", s)),
+    }
+}
+
+fn from_sql(value: Value) -> Result<AttrValue> {
+    let invalid = || rusqlite::Error::InvalidColumnType(1, "value".to_string(), Type::Text);
+    Ok(match value {
+        Value::Integer(i) => AttrValue::Int(i),
+        Value::Real(x) => AttrValue::Float(x),
+        Value::Text(t) => match t.split_at_checked(2) {
+            Some(("s:", s)) => AttrValue::Str(s.to_string()),
+            Some(("b:", b)) => AttrValue::Bool(b.parse().map_err(|_| invalid())?),
+            _ => return Err(invalid()),
+        },
+        _ => return Err(invalid()),
+    })
+}
+
+fn load_attrs(mut rows: rusqlite::Rows) -> Result<AttrMap> {
+    let mut map = AttrMap::new();
+    while let Some(row) = rows.next()? {
+        let key: String = row.get(0)?;
+        map.insert(&key, from_sql(row.get(1)?)?);
+    }
+    Ok(map)
+}
+
+fn parse<V: FromStr>(s: &str) -> Result<V> {
+    s.parse().map_err(|_| {
+        rusqlite::Error::FromSqlConversionFailure(0, Type::Text, "Element not valid".into())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SqliteGraphStore;
+    use crate::digraph::DiGraph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn sqlite_digraph() {
+        let path = std::env::temp_dir().join(format!("rugraph_{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut graph = DiGraph::<i32>::new();
+        for n in [3, 1, 2] {
+            graph.add_node(n);
+        }
+        graph.add_edge(3, 1);
+        graph.add_edge(1, 2);
+        graph.node_attrs_mut(1).insert("name", "one");
+        graph.node_attrs_mut(1).insert("ok", true);
+        graph.edge_attrs_mut(3, 1).insert("w", 2.5);
+        graph.edge_attrs_mut(3, 1).insert("n", 7);
+        {
+            let mut store = SqliteGraphStore::open(&path).unwrap();
+            store.save_digraph("g", &graph).unwrap();
+        }
+
+        let mut store = SqliteGraphStore::open(&path).unwrap();
+        assert_eq!(store.graphs().unwrap(), vec!["g"]);
+        let loaded = store.load_digraph::<i32>("g").unwrap();
+        assert_eq!(loaded.get_nodes(), vec![3, 1, 2]);
+        assert!(loaded.is_directly_connected(3, 1));
+        assert!(loaded.is_connected(3, 2));
+        assert_eq!(loaded.node_attrs(&1), graph.node_attrs(&1));
+        assert_eq!(loaded.edge_attrs(&3, &1), graph.edge_attrs(&3, &1));
+        assert!(loaded.node_attrs(&2).is_none());
+
+        store.insert_node("g", &4).unwrap();
+        store.insert_edge("g", &2, &4).unwrap();
+        store.set_node_attr("g", &4, "name", "four").unwrap();
+        store.remove_edge("g", &3, &1).unwrap();
+        store.remove_node("g", &1).unwrap();
+        let loaded = store.load_digraph::<i32>("g").unwrap();
+        assert_eq!(loaded.get_nodes(), vec![3, 2, 4]);
+        assert!(loaded.is_directly_connected(2, 4));
+        assert!(loaded.get_neighbors(3).is_empty());
+        assert_eq!(loaded.node_attrs(&4).unwrap().get_str("name"), Some("four"));
+        assert!(store.load_digraph::<bool>("g").is_err());
+
+        store.delete_graph("g").unwrap();
+        assert!(store.graphs().unwrap().is_empty());
+        assert!(store.load_digraph::<i32>("g").unwrap().is_empty());
+        drop(store);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn sqlite_multidigraph() {
+        let mut graph = MultiDiGraph::<String, char>::new();
+        graph.add_node("a".to_string());
+        graph.add_node("b".to_string());
+        graph.add_edge("a".to_string(), "b".to_string(), 'x');
+        graph.add_edge("a".to_string(), "b".to_string(), 'y');
+        graph
+            .edge_attrs_mut("a".to_string(), "b".to_string(), 'y')
+            .insert("w", 1);
+
+        let mut store = SqliteGraphStore::open_in_memory().unwrap();
+        store.save_multidigraph("m", &graph).unwrap();
+        store.save_multidigraph("m", &graph).unwrap();
+        store.insert_labeled_edge("m", &"b", &"a", &'z').unwrap();
+        store.remove_labeled_edge("m", &"a", &"b", &'x').unwrap();
+        let loaded = store.load_multidigraph::<String, char>("m").unwrap();
+        assert_eq!(
+            loaded.get_neighbors("a".to_string()),
+            vec![("b".to_string(), 'y')]
+        );
+        assert_eq!(
+            loaded.get_neighbors("b".to_string()),
+            vec![("a".to_string(), 'z')]
+        );
+        let attrs = loaded.edge_attrs(&"a".to_string(), &"b".to_string(), &'y');
+        assert_eq!(attrs.unwrap().get_int("w"), Some(1));
+    }
+}
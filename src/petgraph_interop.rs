@@ -0,0 +1,187 @@
+use petgraph::graph::NodeIndex;
+use petgraph::graph::UnGraph;
+use std::collections::BTreeMap;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+/// Nodes keep their insertion order: node `i` of the graph is `NodeIndex(i)`
+impl<T> From<&DiGraph<T>> for petgraph::Graph<T, ()>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn from(graph: &DiGraph<T>) -> Self {
+        let nodes = graph.get_nodes();
+        let mut ret = petgraph::Graph::<T, ()>::with_capacity(nodes.len(), 0);
+        for n in nodes {
+            ret.add_node(n);
+        }
+        for i in 0..ret.node_count() {
+            for j in graph.neighbor_ids(i) {
+                ret.add_edge(NodeIndex::new(i), NodeIndex::new(*j), ());
+            }
+        }
+        ret
+    }
+}
+
+/// Edge weights are dropped. Nodes holding equal values are merged, and so
+/// are parallel edges
+impl<T, E> From<&petgraph::Graph<T, E>> for DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn from(graph: &petgraph::Graph<T, E>) -> Self {
+        let mut ret = DiGraph::<T>::with_capacity(graph.node_count(), graph.edge_count());
+        for n in graph.node_weights() {
+            ret.add_node(n.clone());
+        }
+        for e in graph.raw_edges() {
+            ret.add_edge(graph[e.source()].clone(), graph[e.target()].clone());
+        }
+        ret
+    }
+}
+
+/// Every undirected edge becomes a single petgraph edge
+impl<T> From<&Graph<T>> for UnGraph<T, ()>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn from(graph: &Graph<T>) -> Self {
+        let mut ret = UnGraph::<T, ()>::default();
+        for n in graph.get_nodes() {
+            ret.add_node(n);
+        }
+        for i in 0..ret.node_count() {
+            for j in graph.neighbor_ids(i).iter().filter(|j| **j >= i) {
+                ret.add_edge(NodeIndex::new(i), NodeIndex::new(*j), ());
+            }
+        }
+        ret
+    }
+}
+
+/// Edge weights are dropped and equal nodes merged
+impl<T, E> From<&UnGraph<T, E>> for Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn from(graph: &UnGraph<T, E>) -> Self {
+        let mut ret = Graph::<T>::new();
+        for n in graph.node_weights() {
+            ret.add_node(n.clone());
+        }
+        for e in graph.raw_edges() {
+            ret.add_edge(graph[e.source()].clone(), graph[e.target()].clone());
+        }
+        ret
+    }
+}
+
+/// Parallel edges are kept, with their values as edge weights
+impl<T, E> From<&MultiDiGraph<T, E>> for petgraph::Graph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn from(graph: &MultiDiGraph<T, E>) -> Self {
+        let mut ret = petgraph::Graph::<T, E>::new();
+        let mut index = BTreeMap::<T, NodeIndex>::new();
+        let nodes = graph.get_nodes();
+        for n in nodes.iter() {
+            index.insert(n.clone(), ret.add_node(n.clone()));
+        }
+        for n in nodes {
+            for (m, e) in graph.get_neighbors(n.clone()) {
+                ret.add_edge(index[&n], index[&m], e);
+            }
+        }
+        ret
+    }
+}
+
+/// Equal nodes are merged, as are equal edges between the same nodes
+impl<T, E> From<&petgraph::Graph<T, E>> for MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn from(graph: &petgraph::Graph<T, E>) -> Self {
+        let mut ret = MultiDiGraph::<T, E>::new();
+        for n in graph.node_weights() {
+            ret.add_node(n.clone());
+        }
+        for e in graph.raw_edges() {
+            ret.add_edge(
+                graph[e.source()].clone(),
+                graph[e.target()].clone(),
+                e.weight.clone(),
+            );
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::algo::dijkstra;
+    use petgraph::graph::NodeIndex;
+    use petgraph::graph::UnGraph;
+
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn petgraph_conversions() {
+        let mut graph = DiGraph::<&str>::new();
+        for n in ["a", "b", "c", "d"] {
+            graph.add_node(n);
+        }
+        graph.add_edge("a", "b");
+        graph.add_edge("b", "c");
+        graph.add_edge("a", "c");
+        let pg = petgraph::Graph::<&str, ()>::from(&graph);
+        assert_eq!((pg.node_count(), pg.edge_count()), (4, 3));
+        assert_eq!(pg[NodeIndex::new(2)], "c");
+        let dist = dijkstra(&pg, NodeIndex::new(0), None, |_| 1);
+        assert_eq!(dist[&NodeIndex::new(2)], 1);
+        assert!(!dist.contains_key(&NodeIndex::new(3)));
+        let back = DiGraph::from(&pg);
+        assert_eq!(back.get_nodes(), graph.get_nodes());
+        assert_eq!(back.get_neighbors("a"), vec!["b", "c"]);
+
+        let mut graph = Graph::<i32>::new();
+        for n in 1..=3 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(3, 2);
+        let pg = UnGraph::<i32, ()>::from(&graph);
+        assert_eq!(pg.edge_count(), 2);
+        let back = Graph::from(&pg);
+        assert!(back.is_directly_connected(2, 3));
+        assert!(!back.is_directly_connected(1, 3));
+
+        let mut graph = MultiDiGraph::<i32, char>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_edge(1, 2, 'x');
+        graph.add_edge(1, 2, 'y');
+        let pg = petgraph::Graph::<i32, char>::from(&graph);
+        assert_eq!(pg.edge_count(), 2);
+        let weights: Vec<char> = pg.edge_weights().copied().collect();
+        assert_eq!(weights, vec!['x', 'y']);
+        let back = MultiDiGraph::from(&pg);
+        assert!(back.is_directly_connected_by(1, 2, 'y'));
+        assert_eq!(back.get_neighbors(1).len(), 2);
+    }
+}
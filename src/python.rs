@@ -0,0 +1,297 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::fmt;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+/// A node or edge value coming from Python: an `int` or a `str`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, FromPyObject, IntoPyObject)]
+pub enum PyKey {
+    Int(i64),
+    Str(String),
+}
+
+impl fmt::Display for PyKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PyKey::Int(i) => write!(f, "{}", i),
+            PyKey::Str(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+type PyPaths = Vec<Vec<(PyKey, PyKey, PyKey)>>;
+
+fn value_error(e: &'static str) -> PyErr {
+    PyValueError::new_err(e)
+}
+
+/// `rugraph.DiGraph`: a `DiGraph` whose nodes are `int`s or `str`s
+#[pyclass(name = "DiGraph")]
+pub struct PyDiGraph {
+    graph: DiGraph<PyKey>,
+}
+
+#[pymethods]
+impl PyDiGraph {
+    #[new]
+    pub fn new() -> Self {
+        PyDiGraph {
+            graph: DiGraph::new(),
+        }
+    }
+
+    /// Adds a new node `node`
+    pub fn add_node(&mut self, node: PyKey) {
+        self.graph.add_node(node);
+    }
+
+    /// Creates a new edge from node `source` to node `target`. Raises
+    /// `ValueError` if a node doesn't exist
+    pub fn add_edge(&mut self, source: PyKey, target: PyKey) -> PyResult<()> {
+        if !self.graph.node_exists(source.clone()) || !self.graph.node_exists(target.clone()) {
+            return Err(value_error("Element not found"));
+        }
+        self.graph.add_edge(source, target);
+        Ok(())
+    }
+
+    pub fn __contains__(&self, node: PyKey) -> bool {
+        self.graph.node_exists(node)
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.graph.count_nodes()
+    }
+
+    /// Returns the nodes of the graph
+    pub fn nodes(&self) -> Vec<PyKey> {
+        self.graph.get_nodes()
+    }
+
+    /// Returns the neighbors of node `node`
+    pub fn neighbors(&self, node: PyKey) -> Vec<PyKey> {
+        self.graph.get_neighbors(node)
+    }
+
+    /// Returns if node `target` can be reached from node `source`
+    pub fn is_connected(&self, source: PyKey, target: PyKey) -> bool {
+        self.graph.is_connected(source, target)
+    }
+
+    /// Returns the simple paths from node `source` to node `target`, with at
+    /// most `cutoff` edges if given
+    #[pyo3(signature = (source, target, cutoff = None))]
+    pub fn all_simple_paths(
+        &self,
+        source: PyKey,
+        target: PyKey,
+        cutoff: Option<usize>,
+    ) -> Vec<Vec<PyKey>> {
+        match cutoff {
+            None => self.graph.all_simple_paths(source, target),
+            Some(c) => self.graph.all_simple_paths_with_cutoff(source, target, c),
+        }
+    }
+
+    /// Returns a path with the fewest edges from node `source` to node
+    /// `target`, `None` if `target` can't be reached
+    pub fn shortest_path(&self, source: PyKey, target: PyKey) -> Option<Vec<PyKey>> {
+        self.graph.shortest_path(source, target)
+    }
+
+    /// Returns the PageRank of every node
+    #[pyo3(signature = (damping = 0.85, max_iter = 100, epsilon = 1e-6))]
+    pub fn pagerank(&self, damping: f64, max_iter: usize, epsilon: f64) -> HashMap<PyKey, f64> {
+        self.graph.pagerank(damping, max_iter, epsilon)
+    }
+
+    /// Returns the layers of a DAG. Raises `ValueError` if the graph has a
+    /// cycle
+    pub fn topological_generations(&self) -> PyResult<Vec<Vec<PyKey>>> {
+        self.graph.topological_generations().map_err(value_error)
+    }
+
+    /// Returns the DOT representation of the graph
+    #[pyo3(signature = (graph_name = "G"))]
+    pub fn to_dot(&self, graph_name: &str) -> String {
+        self.graph.to_dot_string(graph_name)
+    }
+}
+
+/// `rugraph.MultiDiGraph`: a `MultiDiGraph` whose nodes and edge labels are
+/// `int`s or `str`s
+#[pyclass(name = "MultiDiGraph")]
+pub struct PyMultiDiGraph {
+    graph: MultiDiGraph<PyKey, PyKey>,
+}
+
+#[pymethods]
+impl PyMultiDiGraph {
+    #[new]
+    pub fn new() -> Self {
+        PyMultiDiGraph {
+            graph: MultiDiGraph::new(),
+        }
+    }
+
+    /// Adds a new node `node`
+    pub fn add_node(&mut self, node: PyKey) {
+        self.graph.add_node(node);
+    }
+
+    /// Creates a new edge labeled `edge` from node `source` to node
+    /// `target`. Raises `ValueError` if a node doesn't exist
+    pub fn add_edge(&mut self, source: PyKey, target: PyKey, edge: PyKey) -> PyResult<()> {
+        if !self.graph.node_exists(source.clone()) || !self.graph.node_exists(target.clone()) {
+            return Err(value_error("Element not found"));
+        }
+        self.graph.add_edge(source, target, edge);
+        Ok(())
+    }
+
+    /// Removes node `node` and its edges, returning if it existed
+    pub fn remove_node(&mut self, node: PyKey) -> bool {
+        self.graph.remove_node(node)
+    }
+
+    /// Removes the edge labeled `edge` from `source` to `target`, returning
+    /// if it existed
+    pub fn remove_edge(&mut self, source: PyKey, target: PyKey, edge: PyKey) -> bool {
+        self.graph.remove_edge(source, target, edge)
+    }
+
+    pub fn __contains__(&self, node: PyKey) -> bool {
+        self.graph.node_exists(node)
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.graph.count_nodes()
+    }
+
+    /// Returns the nodes of the graph
+    pub fn nodes(&self) -> Vec<PyKey> {
+        self.graph.get_nodes()
+    }
+
+    /// Returns the `(neighbor, edge)` pairs of node `node`
+    pub fn neighbors(&self, node: PyKey) -> Vec<(PyKey, PyKey)> {
+        self.graph.get_neighbors(node)
+    }
+
+    /// Returns if node `target` can be reached from node `source`
+    pub fn is_connected(&self, source: PyKey, target: PyKey) -> bool {
+        self.graph.is_connected(source, target)
+    }
+
+    /// Returns the simple paths from node `source` to node `target` as lists
+    /// of `(source, target, edge)` triples, with at most `cutoff` edges if
+    /// given
+    #[pyo3(signature = (source, target, cutoff = None))]
+    pub fn all_simple_paths(&self, source: PyKey, target: PyKey, cutoff: Option<usize>) -> PyPaths {
+        match cutoff {
+            None => self.graph.all_simple_paths(source, target),
+            Some(c) => self.graph.all_simple_paths_with_cutoff(source, target, c),
+        }
+    }
+
+    /// Returns the PageRank of every node
+    #[pyo3(signature = (damping = 0.85, max_iter = 100, epsilon = 1e-6))]
+    pub fn pagerank(&self, damping: f64, max_iter: usize, epsilon: f64) -> HashMap<PyKey, f64> {
+        self.graph.pagerank(damping, max_iter, epsilon)
+    }
+
+    /// Returns the layers of a DAG. Raises `ValueError` if the graph has a
+    /// cycle
+    pub fn topological_generations(&self) -> PyResult<Vec<Vec<PyKey>>> {
+        self.graph.topological_generations().map_err(value_error)
+    }
+
+    /// Returns the DOT representation of the graph
+    #[pyo3(signature = (graph_name = "G"))]
+    pub fn to_dot(&self, graph_name: &str) -> String {
+        self.graph.to_dot_string(graph_name)
+    }
+}
+
+impl Default for PyDiGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for PyMultiDiGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The `rugraph` Python module
+#[pymodule]
+fn rugraph(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyDiGraph>()?;
+    m.add_class::<PyMultiDiGraph>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::prelude::*;
+    use pyo3::types::PyDict;
+    use pyo3::types::PyModule;
+
+    #[test]
+    fn python_module() {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "rugraph").unwrap();
+            super::rugraph(&module).unwrap();
+            let locals = PyDict::new(py);
+            locals.set_item("rugraph", module).unwrap();
+            py.run(
+                cr#"
+g = rugraph.DiGraph()
+for n in ["a", "b", "c", 1]:
+    g.add_node(n)
+g.add_edge("a", "b")
+g.add_edge("b", "c")
+g.add_edge("c", 1)
+g.add_edge(source="a", target="c")
+assert len(g) == 4 and "a" in g and 2 not in g
+assert g.neighbors("a") == ["b", "c"]
+assert g.shortest_path("a", 1) == ["a", "c", 1]
+assert g.shortest_path(1, "a") is None
+assert len(g.all_simple_paths("a", 1)) == 2
+assert g.all_simple_paths("a", 1, cutoff=2) == [["a", "c", 1]]
+assert g.topological_generations() == [["a"], ["b"], ["c"], [1]]
+assert abs(sum(g.pagerank().values()) - 1.0) < 1e-3
+assert "a -> b;" in g.to_dot("g")
+try:
+    g.add_edge("a", "z")
+    assert False
+except ValueError:
+    pass
+
+m = rugraph.MultiDiGraph()
+m.add_node(1)
+m.add_node(2)
+m.add_edge(1, 2, "x")
+m.add_edge(1, 2, "y")
+assert m.neighbors(1) == [(2, "x"), (2, "y")]
+assert m.all_simple_paths(1, 2) == [[(1, 2, "x")], [(1, 2, "y")]]
+assert m.remove_edge(1, 2, "x") and not m.remove_edge(1, 2, "x")
+assert m.remove_node(2) and m.nodes() == [1]
+"#,
+                None,
+                Some(&locals),
+            )
+            .unwrap();
+        });
+    }
+}
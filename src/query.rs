@@ -0,0 +1,381 @@
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::Chars;
+use std::vec::Vec;
+
+use crate::attributes::AttrMap;
+use crate::attributes::AttrValue;
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IGraph;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Attribute name or keyword
+    Name(String),
+    Value(AttrValue),
+    Op(CmpOp),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CmpOp {
+    fn eval(&self, ord: Option<Ordering>) -> bool {
+        match ord {
+            None => *self == CmpOp::Ne,
+            Some(o) => match self {
+                CmpOp::Eq => o.is_eq(),
+                CmpOp::Ne => o.is_ne(),
+                CmpOp::Lt => o.is_lt(),
+                CmpOp::Le => o.is_le(),
+                CmpOp::Gt => o.is_gt(),
+                CmpOp::Ge => o.is_ge(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    /// The attribute is set
+    Has(String),
+    Cmp(String, CmpOp, AttrValue),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// Filter over attribute maps parsed from expressions such as
+/// `color == 'red' && weight >= 2.5 || !visited`. Supported are the
+/// comparisons `==` (or `=`), `!=`, `<`, `<=`, `>` and `>=` of an attribute
+/// with a number, a quoted string or `true`/`false`, `&&` (or `and`), `||`
+/// (or `or`), `!` (or `not`) and parentheses. A bare attribute name matches when
+/// the attribute is set. Comparisons with a missing attribute, or with a
+/// value of another type, only match for `!=`. Integers and floats compare
+/// as numbers
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttrQuery {
+    expr: Expr,
+}
+
+impl AttrQuery {
+    /// Parses `query`, failing if it is not a valid expression
+    pub fn parse(query: &str) -> Result<Self, &'static str> {
+        let tokens = tokenize(query)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or()?;
+        if parser.pos < parser.tokens.len() {
+            return Err("Query not correct. Unexpected token.");
+        }
+        Ok(AttrQuery { expr })
+    }
+
+    /// Returns if the attributes `attrs` satisfy the query
+    pub fn matches(&self, attrs: &AttrMap) -> bool {
+        eval(&self.expr, attrs)
+    }
+}
+
+fn eval(expr: &Expr, attrs: &AttrMap) -> bool {
+    match expr {
+        Expr::Has(name) => attrs.contains_key(name),
+        Expr::Cmp(name, op, value) => op.eval(attrs.get(name).and_then(|v| compare(v, value))),
+        Expr::Not(e) => !eval(e, attrs),
+        Expr::And(a, b) => eval(a, attrs) && eval(b, attrs),
+        Expr::Or(a, b) => eval(a, attrs) || eval(b, attrs),
+    }
+}
+
+/// Orders two values of the same type, `None` if they can't be compared
+fn compare(a: &AttrValue, b: &AttrValue) -> Option<Ordering> {
+    match (a, b) {
+        (AttrValue::Int(x), AttrValue::Int(y)) => Some(x.cmp(y)),
+        (AttrValue::Str(x), AttrValue::Str(y)) => Some(x.cmp(y)),
+        (AttrValue::Bool(x), AttrValue::Bool(y)) => Some(x.cmp(y)),
+        _ => a.as_float()?.partial_cmp(&b.as_float()?),
+    }
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>, &'static str> {
+    let mut chars = query.chars().peekable();
+    let mut tokens = Vec::new();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '&' | '|' => {
+                if chars.next() != Some(c) {
+                    return Err("Query not correct. Expected '&&' or '||'.");
+                }
+                if c == '&' {
+                    Token::And
+                } else {
+                    Token::Or
+                }
+            }
+            '=' => {
+                chars.next_if_eq(&'=');
+                Token::Op(CmpOp::Eq)
+            }
+            '!' | '<' | '>' => {
+                let eq = chars.next_if_eq(&'=').is_some();
+                match (c, eq) {
+                    ('!', true) => Token::Op(CmpOp::Ne),
+                    ('!', false) => Token::Not,
+                    ('<', true) => Token::Op(CmpOp::Le),
+                    ('<', false) => Token::Op(CmpOp::Lt),
+                    ('>', true) => Token::Op(CmpOp::Ge),
+                    _ => Token::Op(CmpOp::Gt),
+                }
+            }
+            '\'' | '"' => Token::Value(AttrValue::Str(quoted(&mut chars, c)?)),
+            c if c.is_ascii_digit() || c == '-' || c == '.' => Token::Value(number(&mut chars, c)?),
+            c if c.is_alphabetic() || c == '_' => {
+                let mut name = String::from(c);
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                    name.push(c);
+                }
+                match name.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    "true" => Token::Value(AttrValue::Bool(true)),
+                    "false" => Token::Value(AttrValue::Bool(false)),
+                    _ => Token::Name(name),
+                }
+            }
+            _ => return Err("Query not correct. Unexpected character."),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Text of a string opened by `quote`, which may contain escaped quotes
+pub(crate) fn quoted(chars: &mut Peekable<Chars>, quote: char) -> Result<String, &'static str> {
+    let mut s = String::new();
+    loop {
+        match chars.next() {
+            None => return Err("Query not correct. Unterminated string."),
+            Some('\\') => match chars.next() {
+                None => return Err("Query not correct. Unterminated string."),
+                Some(c) => s.push(c),
+            },
+            Some(c) if c == quote => return Ok(s),
+            Some(c) => s.push(c),
+        }
+    }
+}
+
+fn number(chars: &mut Peekable<Chars>, first: char) -> Result<AttrValue, &'static str> {
+    let mut s = String::from(first);
+    while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '.' || *c == '-') {
+        s.push(c);
+    }
+    if let Ok(i) = s.parse::<i64>() {
+        return Ok(AttrValue::Int(i));
+    }
+    s.parse::<f64>()
+        .map(AttrValue::Float)
+        .map_err(|_| "Query not correct. Invalid number.")
+}
+
+/// Recursive descent parser, from lowest to highest precedence:
+/// `||`, `&&`, `!`, comparisons and parentheses
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.pos) == Some(token) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn or(&mut self) -> Result<Expr, &'static str> {
+        let mut expr = self.and()?;
+        while self.eat(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, &'static str> {
+        let mut expr = self.not()?;
+        while self.eat(&Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<Expr, &'static str> {
+        if self.eat(&Token::Not) {
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, &'static str> {
+        match self.next() {
+            Some(Token::LParen) => {
+                let expr = self.or()?;
+                if !self.eat(&Token::RParen) {
+                    return Err("Query not correct. Expected ')'.");
+                }
+                Ok(expr)
+            }
+            Some(Token::Name(name)) => {
+                let Some(Token::Op(op)) = self.tokens.get(self.pos).cloned() else {
+                    return Ok(Expr::Has(name));
+                };
+                self.pos += 1;
+                match self.next() {
+                    Some(Token::Value(value)) => Ok(Expr::Cmp(name, op, value)),
+                    Some(Token::Name(s)) => Ok(Expr::Cmp(name, op, AttrValue::Str(s))),
+                    _ => Err("Query not correct. Expected a value."),
+                }
+            }
+            _ => Err("Query not correct. Expected an attribute."),
+        }
+    }
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the nodes whose attributes satisfy `query`, see `AttrQuery`.
+    /// Fails if the query is not valid
+    pub fn query_nodes(&self, query: &str) -> Result<Vec<T>, &'static str> {
+        let query = AttrQuery::parse(query)?;
+        let empty = AttrMap::new();
+        Ok(self
+            .get_nodes()
+            .into_iter()
+            .filter(|n| query.matches(self.node_attrs(n).unwrap_or(&empty)))
+            .collect())
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the nodes whose attributes satisfy `query`, see `AttrQuery`.
+    /// Fails if the query is not valid
+    pub fn query_nodes(&self, query: &str) -> Result<Vec<T>, &'static str> {
+        self.digraph.query_nodes(query)
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the nodes whose attributes satisfy `query`, see `AttrQuery`.
+    /// Fails if the query is not valid
+    pub fn query_nodes(&self, query: &str) -> Result<Vec<T>, &'static str> {
+        let query = AttrQuery::parse(query)?;
+        let empty = AttrMap::new();
+        Ok(self
+            .get_nodes()
+            .into_iter()
+            .filter(|n| query.matches(self.node_attrs(n).unwrap_or(&empty)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AttrQuery;
+    use crate::attributes::AttrMap;
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn attr_query() {
+        let mut attrs = AttrMap::new();
+        attrs.insert("color", "red");
+        attrs.insert("weight", 3);
+        attrs.insert("score", 0.5);
+        attrs.insert("visited", false);
+
+        let matches = |q: &str| AttrQuery::parse(q).unwrap().matches(&attrs);
+        assert!(matches("color == 'red'"));
+        assert!(matches("color == red"));
+        assert!(matches("color != \"blue\""));
+        assert!(matches("weight >= 3 && weight < 3.5"));
+        assert!(matches("score > -1 and score <= 0.5"));
+        assert!(matches("visited == false"));
+        assert!(matches("!(color == 'blue' || weight > 10)"));
+        assert!(matches("not missing and color"));
+        assert!(!matches("missing == 1"));
+        assert!(matches("missing != 1"));
+        assert!(!matches("color > 1"));
+        assert!(matches("weight == 3.0"));
+        assert!(matches("color == 'a' || color == 'b' || weight == 3"));
+
+        assert!(AttrQuery::parse("").is_err());
+        assert!(matches("color = 'red'"));
+        assert!(AttrQuery::parse("color == == 'red'").is_err());
+        assert!(AttrQuery::parse("color == 'red").is_err());
+        assert!(AttrQuery::parse("(color").is_err());
+        assert!(AttrQuery::parse("color == red weight").is_err());
+        assert!(AttrQuery::parse("weight > 1x").is_err());
+    }
+
+    #[test]
+    fn query_nodes() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 1..=4 {
+            graph.add_node(n);
+        }
+        graph.node_attrs_mut(1).insert("kind", "server");
+        graph.node_attrs_mut(2).insert("kind", "client");
+        graph.node_attrs_mut(3).insert("kind", "server");
+        graph.node_attrs_mut(3).insert("load", 0.9);
+        assert_eq!(graph.query_nodes("kind == 'server'").unwrap(), vec![1, 3]);
+        assert_eq!(graph.query_nodes("!kind").unwrap(), vec![4]);
+        assert_eq!(graph.query_nodes("load > 0.5").unwrap(), vec![3]);
+        assert!(graph.query_nodes("load >").is_err());
+
+        let mut graph = Graph::<i32>::new();
+        graph.add_node(1);
+        graph.node_attrs_mut(1).insert("x", 1);
+        assert_eq!(graph.query_nodes("x == 1").unwrap(), vec![1]);
+
+        let mut graph = MultiDiGraph::<i32, i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.node_attrs_mut(2).insert("x", true);
+        assert_eq!(graph.query_nodes("x == true").unwrap(), vec![2]);
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::rugraph::IGraph;
+use crate::visit::VisitMap;
+
+/// Memoized `is_connected` answers of a graph: the nodes reachable from
+/// every node queried so far, by node index. Emptied on every new edge
+pub(crate) struct ReachabilityCache {
+    enabled: bool,
+    reachable: Mutex<BTreeMap<usize, VisitMap>>,
+}
+
+impl ReachabilityCache {
+    pub fn new() -> Self {
+        ReachabilityCache {
+            enabled: false,
+            reachable: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Forgets every memoized answer, called when an edge is added
+    pub fn invalidate(&mut self) {
+        self.reachable
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Makes `is_connected` remember the nodes reachable from every node it
+    /// is asked about, so repeated queries from the same node don't traverse
+    /// the graph again. The memoized answers are dropped when an edge is added
+    pub fn enable_reachability_cache(&mut self) {
+        self.reachability.enabled = true;
+    }
+
+    /// Stops memoizing `is_connected` and frees the memoized answers
+    pub fn disable_reachability_cache(&mut self) {
+        self.reachability.enabled = false;
+        self.reachability.invalidate();
+    }
+
+    /// Returns if `is_connected` answers are memoized
+    pub fn is_reachability_cache_enabled(&self) -> bool {
+        self.reachability.enabled
+    }
+
+    /// Returns if the node at position `to` is reachable from the one at
+    /// position `from`, using the cache. `None` if the cache is disabled
+    pub(crate) fn cached_is_connected(&self, from: usize, to: usize) -> Option<bool> {
+        if !self.reachability.enabled {
+            return None;
+        }
+        let mut cache = self
+            .reachability
+            .reachable
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let reachable = cache.entry(from).or_insert_with(|| {
+            let mut visited = VisitMap::new(self.count_nodes());
+            let mut to_process = vec![from];
+            while let Some(i) = to_process.pop() {
+                for n in self.neighbor_ids(i) {
+                    if visited.visit(*n) {
+                        to_process.push(*n);
+                    }
+                }
+            }
+            visited
+        });
+        // nodes added after the answer was memoized have no edges yet
+        Some(to < reachable.len() && reachable.is_visited(to))
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Makes `is_connected` remember the nodes reachable from every node it
+    /// is asked about. The memoized answers are dropped when an edge is added
+    pub fn enable_reachability_cache(&mut self) {
+        self.digraph.enable_reachability_cache();
+    }
+
+    /// Stops memoizing `is_connected` and frees the memoized answers
+    pub fn disable_reachability_cache(&mut self) {
+        self.digraph.disable_reachability_cache();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn reachability_cache() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 1..=4 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.enable_reachability_cache();
+        assert!(graph.is_reachability_cache_enabled());
+        assert!(graph.is_connected(1, 3));
+        assert!(!graph.is_connected(1, 4));
+        assert!(!graph.is_connected(1, 1));
+        assert!(!graph.is_connected(1, 9));
+
+        graph.add_edge(3, 4);
+        assert!(graph.is_connected(1, 4));
+        graph.add_node(5);
+        assert!(!graph.is_connected(1, 5));
+        graph.add_edge(4, 1);
+        assert!(graph.is_connected(1, 1));
+        assert!(graph.is_connected(3, 2));
+
+        graph.disable_reachability_cache();
+        assert!(!graph.is_reachability_cache_enabled());
+        assert!(graph.is_connected(3, 2));
+        assert!(!graph.is_connected(5, 1));
+
+        let mut graph = Graph::<i32>::new();
+        for n in 1..=3 {
+            graph.add_node(n);
+        }
+        graph.enable_reachability_cache();
+        graph.add_edge(1, 2);
+        assert!(graph.is_connected(2, 1));
+        assert!(!graph.is_connected(2, 3));
+        graph.add_edge(3, 2);
+        assert!(graph.is_connected(1, 3));
+    }
+}
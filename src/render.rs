@@ -0,0 +1,341 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::layout::positions;
+use crate::layout::Layout;
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+/// Environment variable with the path of the `dot` binary to use instead of
+/// the one found in `PATH`
+pub const DOT_ENV: &str = "RUGRAPH_DOT";
+
+/// Distance in pixels between neighbor nodes in the fallback SVG
+const SCALE: f64 = 80.0;
+/// Radius in pixels of the nodes in the fallback SVG
+const RADIUS: f64 = 18.0;
+
+/// Image formats `render_to_file` can produce
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Png,
+    Svg,
+    Pdf,
+}
+
+impl OutputFormat {
+    /// Name of the format for `dot -T`
+    fn name(&self) -> &'static str {
+        match self {
+            OutputFormat::Png => "png",
+            OutputFormat::Svg => "svg",
+            OutputFormat::Pdf => "pdf",
+        }
+    }
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Renders the graph to the image `path` with Graphviz `dot`. Without a
+    /// `dot` binary only SVG is available, drawn with `Layout::Spring`
+    pub fn render_to_file(&self, path: impl AsRef<Path>, format: OutputFormat) -> io::Result<()> {
+        render(&self.to_dot_string("G"), path.as_ref(), format, || {
+            self.to_svg_string(Layout::Spring(100))
+        })
+    }
+
+    /// Returns an SVG picture of the graph with the nodes placed following
+    /// `layout`, drawn without Graphviz
+    pub fn to_svg_string(&self, layout: Layout) -> String {
+        let adj = self.adjacency();
+        let edges: Vec<(usize, usize, Option<String>)> = adj
+            .edges(true)
+            .into_iter()
+            .map(|(i, j)| (i, j, None))
+            .collect();
+        write_svg(true, &adj.nodes, &positions(&adj, layout), &edges)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Renders the graph to the image `path` with Graphviz `dot`. Without a
+    /// `dot` binary only SVG is available, drawn with `Layout::Spring`
+    pub fn render_to_file(&self, path: impl AsRef<Path>, format: OutputFormat) -> io::Result<()> {
+        render(&self.to_dot_string("G"), path.as_ref(), format, || {
+            self.to_svg_string(Layout::Spring(100))
+        })
+    }
+
+    /// Returns an SVG picture of the graph with the nodes placed following
+    /// `layout`, drawn without Graphviz
+    pub fn to_svg_string(&self, layout: Layout) -> String {
+        let adj = self.adjacency();
+        let edges: Vec<(usize, usize, Option<String>)> = adj
+            .edges(false)
+            .into_iter()
+            .map(|(i, j)| (i, j, None))
+            .collect();
+        write_svg(false, &adj.nodes, &positions(&adj, layout), &edges)
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Renders the graph to the image `path` with Graphviz `dot`. Without a
+    /// `dot` binary only SVG is available, drawn with `Layout::Spring`
+    pub fn render_to_file(&self, path: impl AsRef<Path>, format: OutputFormat) -> io::Result<()> {
+        render(&self.to_dot_string("G"), path.as_ref(), format, || {
+            self.to_svg_string(Layout::Spring(100))
+        })
+    }
+
+    /// Returns an SVG picture of the graph with the nodes placed following
+    /// `layout`, drawn without Graphviz. Edges are labeled with their value
+    pub fn to_svg_string(&self, layout: Layout) -> String {
+        let adj = self.adjacency();
+        let mut edges = Vec::<(usize, usize, Option<String>)>::new();
+        for (i, n) in adj.nodes.iter().enumerate() {
+            for (m, e) in self.get_neighbors(n.clone()) {
+                let j = adj.nodes.iter().position(|x| *x == m).unwrap();
+                edges.push((i, j, Some(e.to_string())));
+            }
+        }
+        write_svg(true, &adj.nodes, &positions(&adj, layout), &edges)
+    }
+}
+
+/// Returns the `dot` binary given by `RUGRAPH_DOT` or the first one found in
+/// `PATH`
+pub fn find_dot() -> Option<PathBuf> {
+    if let Some(dot) = env::var_os(DOT_ENV) {
+        return Some(PathBuf::from(dot));
+    }
+    let name = if cfg!(windows) { "dot.exe" } else { "dot" };
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|dir| dir.join(name))
+        .find(|p| p.is_file())
+}
+
+fn render(
+    dot: &str,
+    path: &Path,
+    format: OutputFormat,
+    fallback: impl FnOnce() -> String,
+) -> io::Result<()> {
+    let binary = match find_dot() {
+        Some(b) => b,
+        None if format == OutputFormat::Svg => return fs::write(path, fallback()),
+        None => {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "Graphviz dot not found, only SVG can be rendered without it",
+            ))
+        }
+    };
+    let mut child = Command::new(binary)
+        .arg(format!("-T{}", format.name()))
+        .arg("-o")
+        .arg(path)
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(dot.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "dot failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Escapes the characters with a special meaning in XML
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn write_svg<T>(
+    directed: bool,
+    nodes: &[T],
+    pos: &[(f64, f64)],
+    edges: &[(usize, usize, Option<String>)],
+) -> String
+where
+    T: std::fmt::Display,
+{
+    // Pixel coordinates with the y axis pointing down and a margin around
+    let min_x = pos.iter().map(|p| p.0).fold(0.0, f64::min);
+    let max_y = pos.iter().map(|p| p.1).fold(0.0, f64::max);
+    let margin = 2.0 * RADIUS;
+    let px: Vec<(f64, f64)> = pos
+        .iter()
+        .map(|p| {
+            (
+                (p.0 - min_x) * SCALE + margin,
+                (max_y - p.1) * SCALE + margin,
+            )
+        })
+        .collect();
+    let width = px.iter().map(|p| p.0).fold(0.0, f64::max) + margin;
+    let height = px.iter().map(|p| p.1).fold(0.0, f64::max) + margin;
+
+    let mut s = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+        width, height
+    );
+    if directed {
+        s += "  <defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\"><path d=\"M0,0 L10,5 L0,10 z\"/></marker></defs>\n";
+    }
+    let marker = if directed {
+        " marker-end=\"url(#arrow)\""
+    } else {
+        ""
+    };
+    for (k, (i, j, label)) in edges.iter().enumerate() {
+        // Edges already drawn between the same two nodes
+        let parallel = edges[..k]
+            .iter()
+            .filter(|(a, b, _)| (a, b) == (i, j) || (!directed && (b, a) == (i, j)))
+            .count() as f64;
+        let (a, b) = (px[*i], px[*j]);
+        let (path, middle) = if i == j {
+            let h = RADIUS * (2.5 + parallel);
+            (
+                format!(
+                    "M{:.1},{:.1} C{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}",
+                    a.0 - RADIUS * 0.6,
+                    a.1 - RADIUS * 0.8,
+                    a.0 - h,
+                    a.1 - 2.0 * h,
+                    a.0 + h,
+                    a.1 - 2.0 * h,
+                    a.0 + RADIUS * 0.6,
+                    a.1 - RADIUS * 0.8
+                ),
+                (a.0, a.1 - 1.5 * h),
+            )
+        } else {
+            let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+            let len = (dx * dx + dy * dy).sqrt().max(1.0);
+            let (ux, uy) = (dx / len, dy / len);
+            // Parallel edges bend away from the straight line
+            let bend = 20.0 * parallel;
+            let control = ((a.0 + b.0) / 2.0 - uy * bend, (a.1 + b.1) / 2.0 + ux * bend);
+            (
+                format!(
+                    "M{:.1},{:.1} Q{:.1},{:.1} {:.1},{:.1}",
+                    a.0 + ux * RADIUS,
+                    a.1 + uy * RADIUS,
+                    control.0,
+                    control.1,
+                    b.0 - ux * RADIUS,
+                    b.1 - uy * RADIUS
+                ),
+                (
+                    (a.0 + b.0) / 2.0 - uy * bend / 2.0,
+                    (a.1 + b.1) / 2.0 + ux * bend / 2.0,
+                ),
+            )
+        };
+        s += &format!(
+            "  <path d=\"{}\" fill=\"none\" stroke=\"black\"{}/>\n",
+            path, marker
+        );
+        if let Some(l) = label {
+            s += &format!(
+                "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n",
+                middle.0,
+                middle.1 - 4.0,
+                escape(l)
+            );
+        }
+    }
+    for (i, n) in nodes.iter().enumerate() {
+        s += &format!(
+            "  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.0}\" fill=\"white\" stroke=\"black\"/>\n",
+            px[i].0, px[i].1, RADIUS
+        );
+        s += &format!(
+            "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+            px[i].0,
+            px[i].1,
+            escape(&n.to_string())
+        );
+    }
+    s += "</svg>\n";
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OutputFormat;
+    use crate::digraph::DiGraph;
+    use crate::layout::Layout;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn svg_export() {
+        let mut graph = DiGraph::<String>::new();
+        graph.add_node(String::from("a<b"));
+        graph.add_node(String::from("c"));
+        graph.add_edge(String::from("a<b"), String::from("c"));
+        let s = graph.to_svg_string(Layout::Grid);
+        assert!(s.contains("<circle cx=\"36.0\" cy=\"36.0\" r=\"18\""));
+        assert!(s.contains("<circle cx=\"116.0\" cy=\"36.0\" r=\"18\""));
+        assert!(s.contains(">a&lt;b</text>"));
+        assert!(s.contains("<path d=\"M54.0,36.0 Q76.0,36.0 98.0,36.0\""));
+        assert!(s.contains("marker-end=\"url(#arrow)\""));
+
+        let mut graph = MultiDiGraph::<i32, String>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_edge(1, 2, String::from("x"));
+        graph.add_edge(1, 2, String::from("y"));
+        graph.add_edge(2, 2, String::from("z"));
+        let s = graph.to_svg_string(Layout::Circular);
+        assert_eq!(s.matches("fill=\"none\"").count(), 3);
+        assert!(s.contains(">y</text>"));
+    }
+
+    #[test]
+    fn render_svg() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_edge(1, 2);
+        let path = std::env::temp_dir().join(format!("rugraph_render_{}.svg", std::process::id()));
+        // With or without Graphviz installed an SVG can always be rendered
+        graph.render_to_file(&path, OutputFormat::Svg).unwrap();
+        let s = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(s.contains("<svg"));
+    }
+}
@@ -1,10 +1,25 @@
 use std::fs::File;
+use std::io;
+use std::io::Write;
 use std::vec::Vec;
 
+/// Paths found by a limited path enumeration and whether more paths were
+/// left out
+pub type LimitedPaths<P> = (Vec<Vec<P>>, bool);
+
 /// This trait is contains the basic behaviour of a `Graph`
 pub trait IGraph<T> {
     /// Adds a new node `elem` to the graph
     fn add_node(&mut self, elem: T);
+    /// Same as `add_node` returning the graph, so insertions can be
+    /// chained: `g.with_node(a).with_node(b).with_edge(a, b)`
+    fn with_node(&mut self, elem: T) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self.add_node(elem);
+        self
+    }
     /// Returns `true` if node `node` exists
     fn node_exists(&self, node: T) -> bool;
     /// Returns if a node `from` is connected to a node `to`
@@ -13,10 +28,18 @@ pub trait IGraph<T> {
     fn is_directly_connected(&self, from: T, to: T) -> bool;
     /// Returns an `String` with a dot file representation of the graph
     fn to_dot_string(&self, graph_name: &str) -> String;
+    /// Writes the dot representation of the graph to `writer`: a file,
+    /// stdout, an in-memory buffer...
+    /// `graph_name` is the name of the graph
+    fn to_dot_writer(&self, writer: &mut dyn Write, graph_name: &str) -> io::Result<()> {
+        writer.write_all(self.to_dot_string(graph_name).as_bytes())
+    }
     /// Exports the graph to a dot file. `file` must be a valid
     /// file ready to be written.
     /// `graph_name` is the name of the graph
-    fn to_dot_file(&self, file: &mut File, graph_name: &str);
+    fn to_dot_file(&self, file: &mut File, graph_name: &str) -> io::Result<()> {
+        self.to_dot_writer(file, graph_name)
+    }
     /// Returns if a graph doesn't contain nodes
     fn is_empty(&self) -> bool;
     /// Returns how many nodes are in the graph
@@ -30,11 +53,69 @@ pub trait IDiGraph<T> {
     ///Creates a new edge from node `from` to node `to`
     ///nodes `from` and `to` must be previously added to the graph
     fn add_edge(&mut self, from: T, to: T);
+    /// Same as `add_edge` returning the graph, so insertions can be chained
+    fn with_edge(&mut self, from: T, to: T) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self.add_edge(from, to);
+        self
+    }
+    /// Same as `add_edge` but first adds nodes `from` and `to` if they are
+    /// not in the graph yet
+    fn add_edge_auto(&mut self, from: T, to: T)
+    where
+        Self: IGraph<T>,
+        T: Clone,
+    {
+        self.add_node(from.clone());
+        self.add_node(to.clone());
+        self.add_edge(from, to);
+    }
+    /// Adds every edge `(from, to)` of `edges` with `add_edge_auto`
+    fn extend_with_edges(&mut self, edges: impl IntoIterator<Item = (T, T)>)
+    where
+        Self: IGraph<T>,
+        T: Clone,
+    {
+        for (from, to) in edges {
+            self.add_edge_auto(from, to);
+        }
+    }
     /// Returns a `Vec<Vec<T>>` containing all the simple paths
     /// from node `from` to node `to`
     fn all_simple_paths(&self, from: T, to: T) -> Vec<Vec<T>>;
     /// Returns a vector containing the `neighbors` of node `from`
     fn get_neighbors(&self, from: T) -> Vec<T>;
+    /// Same as `all_simple_paths` but only with the paths of at most
+    /// `max_len` edges, longer ones are not explored
+    fn all_simple_paths_with_cutoff(&self, from: T, to: T, max_len: usize) -> Vec<Vec<T>>
+    where
+        T: Clone + PartialEq,
+    {
+        self.all_simple_paths_limited(from, to, max_len, usize::MAX)
+            .0
+    }
+    /// Same as `all_simple_paths_with_cutoff` but stops after finding
+    /// `limit` paths. Returns the paths and `true` if there were more
+    fn all_simple_paths_limited(
+        &self,
+        from: T,
+        to: T,
+        max_len: usize,
+        limit: usize,
+    ) -> LimitedPaths<T>
+    where
+        T: Clone + PartialEq,
+    {
+        let mut ret = Vec::<Vec<T>>::new();
+        let mut current_path = vec![from];
+        let bounds = PathBounds { max_len, limit };
+        simple_paths_dfs(&to, &bounds, &mut ret, &mut current_path, &|n| {
+            self.get_neighbors(n)
+        });
+        bounds.truncate(ret)
+    }
 }
 
 /// This trait is contains the basic behaviour of a `multi directed graph`
@@ -43,6 +124,38 @@ pub trait IMultiDiGraph<T, E> {
     ///nodes `from` and `to` must be previously added to the graph
     fn add_edge(&mut self, from: T, to: T, edge: E);
 
+    /// Same as `add_edge` returning the graph, so insertions can be chained
+    fn with_edge(&mut self, from: T, to: T, edge: E) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self.add_edge(from, to, edge);
+        self
+    }
+
+    /// Same as `add_edge` but first adds nodes `from` and `to` if they are
+    /// not in the graph yet
+    fn add_edge_auto(&mut self, from: T, to: T, edge: E)
+    where
+        Self: IGraph<T>,
+        T: Clone,
+    {
+        self.add_node(from.clone());
+        self.add_node(to.clone());
+        self.add_edge(from, to, edge);
+    }
+
+    /// Adds every edge `(from, to, edge)` of `edges` with `add_edge_auto`
+    fn extend_with_edges(&mut self, edges: impl IntoIterator<Item = (T, T, E)>)
+    where
+        Self: IGraph<T>,
+        T: Clone,
+    {
+        for (from, to, edge) in edges {
+            self.add_edge_auto(from, to, edge);
+        }
+    }
+
     /// Returns if node `to` is a neighbord of `from` by edge `edge`
     fn is_directly_connected_by(&self, from: T, to: T, edge: E) -> bool;
 
@@ -52,4 +165,349 @@ pub trait IMultiDiGraph<T, E> {
 
     /// Returns a vector containing the `neighbors` of node `from`
     fn get_neighbors(&self, from: T) -> Vec<(T, E)>;
+
+    /// Same as `all_simple_paths` but only with the paths of at most
+    /// `max_len` edges, longer ones are not explored
+    fn all_simple_paths_with_cutoff(&self, from: T, to: T, max_len: usize) -> Vec<Vec<(T, T, E)>>
+    where
+        T: Clone + PartialEq,
+        E: Clone,
+    {
+        self.all_simple_paths_limited(from, to, max_len, usize::MAX)
+            .0
+    }
+
+    /// Same as `all_simple_paths_with_cutoff` but stops after finding
+    /// `limit` paths. Returns the paths and `true` if there were more
+    fn all_simple_paths_limited(
+        &self,
+        from: T,
+        to: T,
+        max_len: usize,
+        limit: usize,
+    ) -> LimitedPaths<(T, T, E)>
+    where
+        T: Clone + PartialEq,
+        E: Clone,
+    {
+        let mut ret = Vec::<Vec<(T, T, E)>>::new();
+        let mut visited = Vec::<T>::new();
+        let bounds = PathBounds { max_len, limit };
+        multi_paths_dfs(
+            from,
+            &to,
+            &bounds,
+            &mut ret,
+            &mut Vec::new(),
+            &mut visited,
+            &|n| self.get_neighbors(n),
+        );
+        bounds.truncate(ret)
+    }
+}
+
+/// This trait is contains the basic behaviour of an `undirected multi graph`
+pub trait IMultiGraph<T, E> {
+    ///Creates a new edge between nodes `a` and `b` labeled `edge`
+    ///nodes `a` and `b` must be previously added to the graph
+    fn add_edge(&mut self, a: T, b: T, edge: E);
+
+    /// Same as `add_edge` returning the graph, so insertions can be chained
+    fn with_edge(&mut self, a: T, b: T, edge: E) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self.add_edge(a, b, edge);
+        self
+    }
+
+    /// Same as `add_edge` but first adds nodes `a` and `b` if they are not
+    /// in the graph yet
+    fn add_edge_auto(&mut self, a: T, b: T, edge: E)
+    where
+        Self: IGraph<T>,
+        T: Clone,
+    {
+        self.add_node(a.clone());
+        self.add_node(b.clone());
+        self.add_edge(a, b, edge);
+    }
+
+    /// Adds every edge `(a, b, edge)` of `edges` with `add_edge_auto`
+    fn extend_with_edges(&mut self, edges: impl IntoIterator<Item = (T, T, E)>)
+    where
+        Self: IGraph<T>,
+        T: Clone,
+    {
+        for (a, b, edge) in edges {
+            self.add_edge_auto(a, b, edge);
+        }
+    }
+
+    /// Returns if nodes `a` and `b` are joined by edge `edge`
+    fn is_directly_connected_by(&self, a: T, b: T, edge: E) -> bool;
+
+    /// Returns a vector `Vec<Vec<(T, T, E)>>` containing all the simple paths
+    /// from node `from` to node `to` in a vector of tuples `(from,to,edge)`
+    fn all_simple_paths(&self, from: T, to: T) -> Vec<Vec<(T, T, E)>>;
+
+    /// Returns a vector containing the `neighbors` of node `from` with the
+    /// edge joining them
+    fn get_neighbors(&self, from: T) -> Vec<(T, E)>;
+
+    /// Same as `all_simple_paths` but only with the paths of at most
+    /// `max_len` edges, longer ones are not explored
+    fn all_simple_paths_with_cutoff(&self, from: T, to: T, max_len: usize) -> Vec<Vec<(T, T, E)>>
+    where
+        T: Clone + PartialEq,
+        E: Clone,
+    {
+        self.all_simple_paths_limited(from, to, max_len, usize::MAX)
+            .0
+    }
+
+    /// Same as `all_simple_paths_with_cutoff` but stops after finding
+    /// `limit` paths. Returns the paths and `true` if there were more
+    fn all_simple_paths_limited(
+        &self,
+        from: T,
+        to: T,
+        max_len: usize,
+        limit: usize,
+    ) -> LimitedPaths<(T, T, E)>
+    where
+        T: Clone + PartialEq,
+        E: Clone,
+    {
+        let mut ret = Vec::<Vec<(T, T, E)>>::new();
+        let mut visited = Vec::<T>::new();
+        let bounds = PathBounds { max_len, limit };
+        multi_paths_dfs(
+            from,
+            &to,
+            &bounds,
+            &mut ret,
+            &mut Vec::new(),
+            &mut visited,
+            &|n| self.get_neighbors(n),
+        );
+        bounds.truncate(ret)
+    }
+}
+
+/// Bounds of a simple path enumeration: paths have at most `max_len` edges
+/// and the search stops once more than `limit` paths are found
+pub(crate) struct PathBounds {
+    pub max_len: usize,
+    pub limit: usize,
+}
+
+impl PathBounds {
+    /// Returns if enough paths were found to stop the search
+    pub fn done<P>(&self, paths: &[P]) -> bool {
+        paths.len() > self.limit
+    }
+
+    /// Drops the paths over the limit, returning if there were any
+    pub fn truncate<P>(&self, mut paths: Vec<P>) -> (Vec<P>, bool) {
+        let truncated = self.done(&paths);
+        paths.truncate(self.limit);
+        (paths, truncated)
+    }
+}
+
+/// Deep first search extending `current_path` (a list of nodes) until `to`
+/// or `max_len` edges. Helper function of the provided path methods
+pub(crate) fn simple_paths_dfs<T: Clone + PartialEq>(
+    to: &T,
+    bounds: &PathBounds,
+    simple_path: &mut Vec<Vec<T>>,
+    current_path: &mut Vec<T>,
+    neighbors: &dyn Fn(T) -> Vec<T>,
+) {
+    let last = current_path[current_path.len() - 1].clone();
+    if last == *to {
+        simple_path.push(current_path.clone());
+        return;
+    }
+    if current_path.len() > bounds.max_len {
+        return;
+    }
+    for n in neighbors(last) {
+        if bounds.done(simple_path) {
+            return;
+        }
+        if !current_path.contains(&n) {
+            current_path.push(n);
+            simple_paths_dfs(to, bounds, simple_path, current_path, neighbors);
+            current_path.pop();
+        }
+    }
+}
+
+/// Deep first search extending `current_path` (a list of edges) from node
+/// `from` until `to` or `max_len` edges. Helper function of the provided
+/// path methods of multi graphs
+fn multi_paths_dfs<T: Clone + PartialEq, E: Clone>(
+    from: T,
+    to: &T,
+    bounds: &PathBounds,
+    simple_path: &mut Vec<Vec<(T, T, E)>>,
+    current_path: &mut Vec<(T, T, E)>,
+    visited: &mut Vec<T>,
+    neighbors: &dyn Fn(T) -> Vec<(T, E)>,
+) {
+    if current_path.len() >= bounds.max_len {
+        return;
+    }
+    visited.push(from.clone());
+    for (n, e) in neighbors(from.clone()) {
+        if bounds.done(simple_path) {
+            break;
+        }
+        current_path.push((from.clone(), n.clone(), e));
+        if n == *to {
+            simple_path.push(current_path.clone());
+        } else if !visited.contains(&n) {
+            multi_paths_dfs(n, to, bounds, simple_path, current_path, visited, neighbors);
+        }
+        current_path.pop();
+    }
+    visited.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IDiGraph;
+    use super::IGraph;
+    use super::IMultiDiGraph;
+    use super::IMultiGraph;
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::multigraph::MultiGraph;
+
+    /// Graph on the numbers below `n` where `i` points to `i + 1` and `i + 2`,
+    /// to check the provided methods
+    struct Ladder(u32);
+
+    impl IDiGraph<u32> for Ladder {
+        fn add_edge(&mut self, _: u32, _: u32) {}
+        fn all_simple_paths(&self, from: u32, to: u32) -> Vec<Vec<u32>> {
+            IDiGraph::all_simple_paths_with_cutoff(self, from, to, usize::MAX)
+        }
+        fn get_neighbors(&self, from: u32) -> Vec<u32> {
+            (from + 1..=from + 2).filter(|m| *m < self.0).collect()
+        }
+    }
+
+    impl IMultiDiGraph<u32, char> for Ladder {
+        fn add_edge(&mut self, _: u32, _: u32, _: char) {}
+        fn is_directly_connected_by(&self, from: u32, to: u32, _: char) -> bool {
+            IDiGraph::get_neighbors(self, from).contains(&to)
+        }
+        fn all_simple_paths(&self, from: u32, to: u32) -> Vec<Vec<(u32, u32, char)>> {
+            IMultiDiGraph::all_simple_paths_with_cutoff(self, from, to, usize::MAX)
+        }
+        fn get_neighbors(&self, from: u32) -> Vec<(u32, char)> {
+            IDiGraph::get_neighbors(self, from)
+                .into_iter()
+                .map(|m| (m, 'x'))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn provided_paths_with_cutoff() {
+        let ladder = Ladder(5);
+        assert_eq!(IDiGraph::all_simple_paths(&ladder, 0, 4).len(), 5);
+        assert_eq!(
+            IDiGraph::all_simple_paths_with_cutoff(&ladder, 0, 4, 2),
+            vec![vec![0, 2, 4]]
+        );
+        assert_eq!(
+            IDiGraph::all_simple_paths_with_cutoff(&ladder, 0, 4, 1).len(),
+            0
+        );
+        assert_eq!(
+            IMultiDiGraph::all_simple_paths_with_cutoff(&ladder, 0, 4, 2),
+            vec![vec![(0, 2, 'x'), (2, 4, 'x')]]
+        );
+        assert_eq!(IMultiDiGraph::all_simple_paths(&ladder, 0, 4).len(), 5);
+
+        let (paths, truncated) = IDiGraph::all_simple_paths_limited(&ladder, 0, 4, usize::MAX, 2);
+        assert_eq!(paths, vec![vec![0, 1, 2, 3, 4], vec![0, 1, 2, 4]]);
+        assert!(truncated);
+        let (paths, truncated) = IMultiDiGraph::all_simple_paths_limited(&ladder, 0, 4, 3, 5);
+        assert_eq!(paths.len(), 4);
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn add_edge_auto() {
+        let mut digraph = DiGraph::<i32>::new();
+        digraph.add_edge_auto(1, 2);
+        digraph.add_edge_auto(2, 3);
+        digraph.add_edge_auto(1, 2);
+        assert_eq!(digraph.get_nodes(), vec![1, 2, 3]);
+        assert!(digraph.is_connected(1, 3));
+        assert_eq!(digraph.get_neighbors(1), vec![2]);
+
+        let mut graph = Graph::<i32>::new();
+        graph.add_edge_auto(1, 2);
+        assert!(graph.is_directly_connected(2, 1));
+
+        let mut multidigraph = MultiDiGraph::<i32, char>::new();
+        multidigraph.add_edge_auto(1, 2, 'a');
+        multidigraph.add_edge_auto(1, 2, 'b');
+        assert_eq!(multidigraph.get_neighbors(1), vec![(2, 'a'), (2, 'b')]);
+
+        let mut multigraph = MultiGraph::<i32, char>::new();
+        multigraph.add_edge_auto(1, 2, 'a');
+        assert!(multigraph.is_directly_connected_by(2, 1, 'a'));
+    }
+
+    #[test]
+    fn extend_with_edges() {
+        let mut digraph = DiGraph::<&str>::new();
+        digraph.extend_with_edges([("a", "b"), ("b", "c"), ("c", "a")]);
+        assert_eq!(digraph.count_nodes(), 3);
+        assert!(digraph.is_connected("b", "a"));
+
+        let mut graph = Graph::<i32>::new();
+        graph.extend_with_edges((0..4).map(|i| (i, i + 1)));
+        assert!(graph.is_connected(4, 0));
+
+        let mut multidigraph = MultiDiGraph::<i32, char>::new();
+        multidigraph.extend_with_edges(vec![(1, 2, 'a'), (1, 2, 'b'), (2, 3, 'c')]);
+        assert_eq!(multidigraph.all_simple_paths(1, 3).len(), 2);
+
+        let mut multigraph = MultiGraph::<i32, char>::new();
+        multigraph.extend_with_edges([(1, 2, 'a'), (2, 3, 'b')]);
+        assert!(multigraph.is_connected(3, 1));
+    }
+
+    #[test]
+    fn chained_insertion() {
+        let mut digraph = DiGraph::<&str>::new();
+        digraph
+            .with_node("a")
+            .with_node("b")
+            .with_node("c")
+            .with_edge("a", "b")
+            .with_edge("b", "c");
+        assert!(digraph.is_connected("a", "c"));
+
+        let mut multidigraph = MultiDiGraph::<i32, char>::new();
+        multidigraph
+            .with_node(1)
+            .with_node(2)
+            .with_edge(1, 2, 'a')
+            .with_edge(1, 2, 'b');
+        assert_eq!(multidigraph.get_neighbors(1).len(), 2);
+
+        let mut multigraph = MultiGraph::<i32, char>::new();
+        multigraph.with_node(1).with_node(2).with_edge(1, 2, 'a');
+        assert!(multigraph.is_directly_connected_by(2, 1, 'a'));
+    }
 }
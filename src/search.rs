@@ -0,0 +1,149 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::collections::BinaryHeap;
+use std::ops::Add;
+use std::vec::Vec;
+
+/// Returns the cheapest path from `start` to `goal`, both included, and its
+/// cost, `None` if `goal` can't be reached, with the A* algorithm on a graph
+/// that is never built: `successors(n)` returns the nodes `n` points to with
+/// the cost of each edge, and only the nodes the search reaches are
+/// generated, e.g. for puzzles with more states than would fit in memory.
+/// `heuristic(n)` estimates the cost from `n` to `goal` and must never
+/// overestimate it. Costs must not be negative. If `goal` can't be reached
+/// the search only ends once every reachable node was expanded
+pub fn astar_implicit<T, W>(
+    start: T,
+    goal: T,
+    successors: impl Fn(&T) -> Vec<(T, W)>,
+    heuristic: impl Fn(&T) -> W,
+) -> Option<(Vec<T>, W)>
+where
+    T: Ord + Clone,
+    W: Copy + PartialOrd + Add<Output = W> + Default,
+{
+    let mut heap = BinaryHeap::from([Closest(heuristic(&start), 0)]);
+    let mut ids = BTreeMap::from([(start.clone(), 0)]);
+    let mut nodes = vec![start];
+    let mut parent: Vec<Option<usize>> = vec![None];
+    let mut cost = vec![W::default()];
+    let mut closed = vec![false];
+    while let Some(Closest(_, i)) = heap.pop() {
+        if closed[i] {
+            continue;
+        }
+        if nodes[i] == goal {
+            let mut path = vec![nodes[i].clone()];
+            let mut j = i;
+            while let Some(p) = parent[j] {
+                path.push(nodes[p].clone());
+                j = p;
+            }
+            path.reverse();
+            return Some((path, cost[i]));
+        }
+        closed[i] = true;
+        for (m, w) in successors(&nodes[i]) {
+            let g = cost[i] + w;
+            let j = match ids.get(&m) {
+                Some(j) => {
+                    if g.partial_cmp(&cost[*j]) != Some(Ordering::Less) {
+                        continue;
+                    }
+                    // reached again with a lower cost, expand it again
+                    closed[*j] = false;
+                    *j
+                }
+                None => {
+                    ids.insert(m.clone(), nodes.len());
+                    nodes.push(m);
+                    parent.push(None);
+                    cost.push(g);
+                    closed.push(false);
+                    nodes.len() - 1
+                }
+            };
+            cost[j] = g;
+            parent[j] = Some(i);
+            heap.push(Closest(g + heuristic(&nodes[j]), j));
+        }
+    }
+    None
+}
+
+/// Same as `astar_implicit` without a heuristic (Dijkstra's algorithm)
+pub fn dijkstra_implicit<T, W>(
+    start: T,
+    goal: T,
+    successors: impl Fn(&T) -> Vec<(T, W)>,
+) -> Option<(Vec<T>, W)>
+where
+    T: Ord + Clone,
+    W: Copy + PartialOrd + Add<Output = W> + Default,
+{
+    astar_implicit(start, goal, successors, |_| W::default())
+}
+
+/// Node id and its estimated total cost, ordered so the cheapest node is
+/// the greatest
+struct Closest<W>(W, usize);
+
+impl<W: PartialOrd> PartialEq for Closest<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: PartialOrd> Eq for Closest<W> {}
+
+impl<W: PartialOrd> PartialOrd for Closest<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: PartialOrd> Ord for Closest<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .0
+            .partial_cmp(&self.0)
+            .unwrap_or(Ordering::Equal)
+            .then(other.1.cmp(&self.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::astar_implicit;
+    use super::dijkstra_implicit;
+
+    #[test]
+    fn astar_on_implicit_graphs() {
+        // an unbounded grid with a wall at x = 2 for y < 5
+        let moves = |(x, y): &(i64, i64)| -> Vec<((i64, i64), u32)> {
+            [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .into_iter()
+                .map(|(dx, dy)| (x + dx, y + dy))
+                .filter(|(x, y)| !(*x == 2 && *y < 5))
+                .map(|n| (n, 1))
+                .collect()
+        };
+        let manhattan = |(x, y): &(i64, i64)| ((4 - x).abs() + y.abs()) as u32;
+        let (path, cost) = astar_implicit((0, 0), (4, 0), moves, manhattan).unwrap();
+        assert_eq!(cost, 14);
+        assert_eq!(path.len(), 15);
+        assert_eq!((path[0], path[14]), ((0, 0), (4, 0)));
+        assert!(path.contains(&(2, 5)));
+        assert_eq!(dijkstra_implicit((0, 0), (4, 0), moves).unwrap().1, 14);
+
+        // reach 37 from 1 with +1 costing 1 and *2 costing 1.5
+        let ops = |n: &u64| vec![(n + 1, 1.0), (n * 2, 1.5)];
+        let (path, cost) = dijkstra_implicit(1, 37, ops).unwrap();
+        assert_eq!(path, vec![1, 2, 4, 8, 9, 18, 36, 37]);
+        assert_eq!(cost, 9.0);
+
+        let bounded = |n: &u8| if *n < 10 { vec![(n + 1, 1)] } else { vec![] };
+        assert_eq!(astar_implicit(0, 20, bounded, |_| 0), None);
+        assert_eq!(astar_implicit(3, 3, bounded, |_| 0), Some((vec![3], 0)));
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::visit::VisitMap;
+
+/// Immutable snapshot of a `DiGraph` taken with `DiGraph::snapshot`. Cloning
+/// it is cheap and, when `T` is, it can be sent to other threads, so
+/// analytics can run on a consistent view while the graph keeps changing.
+/// Consecutive snapshots share the nodes and successor lists that didn't
+/// change in between
+#[derive(Clone)]
+pub struct DiGraphSnapshot<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    nodes: Arc<Vec<T>>,
+    index: Arc<BTreeMap<T, usize>>,
+    succ: Arc<Vec<Arc<Vec<usize>>>>,
+}
+
+/// Last snapshot taken of a graph and whether the graph changed since
+pub(crate) struct SnapshotCache<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    last: Option<DiGraphSnapshot<T>>,
+    stale: bool,
+}
+
+impl<T> SnapshotCache<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        SnapshotCache {
+            last: None,
+            stale: true,
+        }
+    }
+
+    /// Marks the last snapshot as outdated, called on every modification
+    pub fn invalidate(&mut self) {
+        self.stale = true;
+    }
+
+    /// Forgets the last snapshot, called when edges are removed or moved so
+    /// the next one doesn't share successor lists that changed in place
+    pub fn clear(&mut self) {
+        self.last = None;
+        self.stale = true;
+    }
+}
+
+impl<T> DiGraphSnapshot<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns `true` if node `node` exists
+    pub fn node_exists(&self, node: &T) -> bool {
+        self.index.contains_key(node)
+    }
+
+    /// Returns if the snapshot doesn't contain nodes
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns how many nodes are in the snapshot
+    pub fn count_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns how many edges are in the snapshot
+    pub fn count_edges(&self) -> usize {
+        self.succ.iter().map(|s| s.len()).sum()
+    }
+
+    /// Returns a vector of the elements, in insertion order
+    pub fn get_nodes(&self) -> Vec<T> {
+        self.nodes.to_vec()
+    }
+
+    /// Returns a vector containing the `neighbors` of node `from`
+    pub fn get_neighbors(&self, from: &T) -> Vec<T> {
+        match self.index.get(from) {
+            Some(i) => self.succ[*i]
+                .iter()
+                .map(|j| self.nodes[*j].clone())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns if node `to` is a neighbor of `from`
+    pub fn is_directly_connected(&self, from: &T, to: &T) -> bool {
+        match (self.index.get(from), self.index.get(to)) {
+            (Some(i), Some(j)) => self.succ[*i].contains(j),
+            _ => false,
+        }
+    }
+
+    /// Returns if a node `from` is connected to a node `to` by a path of at
+    /// least one edge
+    pub fn is_connected(&self, from: &T, to: &T) -> bool {
+        let (Some(i), Some(j)) = (self.index.get(from), self.index.get(to)) else {
+            return false;
+        };
+        let mut seen = VisitMap::new(self.nodes.len());
+        let mut queue = VecDeque::from([*i]);
+        while let Some(k) = queue.pop_front() {
+            for m in self.succ[k].iter() {
+                if m == j {
+                    return true;
+                }
+                if seen.visit(*m) {
+                    queue.push_back(*m);
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns a new mutable `DiGraph` with the nodes and edges of the snapshot
+    pub fn to_digraph(&self) -> DiGraph<T> {
+        DiGraph::from_index_lists(
+            self.nodes.to_vec(),
+            self.succ.iter().map(|s| s.to_vec()).collect(),
+        )
+    }
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns an immutable snapshot of the graph. Taking a snapshot of an
+    /// unchanged graph is O(1); otherwise only the successor lists of the
+    /// nodes that got new edges are rebuilt and the rest is shared with the
+    /// previous snapshot (edges are only appended between two snapshots, so
+    /// unchanged lists are the ones with the same length; removing or moving
+    /// edges forces a full rebuild)
+    pub fn snapshot(&self) -> DiGraphSnapshot<T> {
+        let mut cache = self.snapshot.lock().unwrap_or_else(PoisonError::into_inner);
+        if let (Some(last), false) = (&cache.last, cache.stale) {
+            return last.clone();
+        }
+
+        let count = self.count_nodes();
+        let (nodes, index) = match &cache.last {
+            Some(last) if last.nodes.len() == count => (last.nodes.clone(), last.index.clone()),
+            _ => {
+                let nodes = self.get_nodes();
+                let index = nodes.iter().cloned().zip(0..).collect();
+                (Arc::new(nodes), Arc::new(index))
+            }
+        };
+        let succ = (0..count)
+            .map(|i| match &cache.last {
+                Some(last)
+                    if i < last.succ.len() && last.succ[i].len() == self.out_degree_at(i) =>
+                {
+                    last.succ[i].clone()
+                }
+                _ => Arc::new(
+                    self.get_neighbors(nodes[i].clone())
+                        .iter()
+                        .map(|m| index[m])
+                        .collect(),
+                ),
+            })
+            .collect();
+
+        let snapshot = DiGraphSnapshot {
+            nodes,
+            index,
+            succ: Arc::new(succ),
+        };
+        cache.last = Some(snapshot.clone());
+        cache.stale = false;
+        snapshot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use std::sync::Arc;
+
+    #[test]
+    fn digraph_snapshot() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 1..=4 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+
+        let first = graph.snapshot();
+        let again = graph.snapshot();
+        assert!(Arc::ptr_eq(&first.succ, &again.succ));
+
+        graph.add_edge(3, 4);
+        let second = graph.snapshot();
+        assert!(Arc::ptr_eq(&first.nodes, &second.nodes));
+        assert!(Arc::ptr_eq(&first.succ[0], &second.succ[0]));
+        assert!(!Arc::ptr_eq(&first.succ[2], &second.succ[2]));
+
+        graph.add_node(5);
+        graph.add_edge(4, 5);
+        let handle = std::thread::spawn(move || {
+            (
+                first.is_connected(&1, &4),
+                second.is_connected(&1, &4),
+                second.count_edges(),
+            )
+        });
+        assert_eq!(handle.join().unwrap(), (false, true, 3));
+
+        let third = graph.snapshot();
+        assert_eq!(third.count_nodes(), 5);
+        assert_eq!(third.get_neighbors(&4), vec![5]);
+        assert!(third.is_directly_connected(&1, &2));
+        assert!(!third.node_exists(&6));
+        let copy = third.to_digraph();
+        assert_eq!(copy.get_neighbors(4), vec![5]);
+    }
+
+    #[test]
+    fn digraph_snapshot_after_retain_edges() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (1, 3)]);
+        graph.add_node(4);
+        let before = graph.snapshot();
+        graph.retain_edges(|_, to| *to != 2);
+        graph.add_edge(1, 4);
+        let after = graph.snapshot();
+        assert_eq!(after.get_neighbors(&1), graph.get_neighbors(1));
+        assert_eq!(after.get_neighbors(&1), vec![3, 4]);
+        assert_eq!(before.get_neighbors(&1), vec![2, 3]);
+    }
+
+    #[test]
+    fn digraph_snapshot_after_reverse() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3), (3, 1)]);
+        let before = graph.snapshot();
+        graph.reverse();
+        let after = graph.snapshot();
+        for n in 1..=3 {
+            assert_eq!(after.get_neighbors(&n), graph.get_neighbors(n));
+        }
+        assert!(after.is_directly_connected(&2, &1));
+        assert!(before.is_directly_connected(&1, &2));
+    }
+
+    #[test]
+    fn digraph_snapshot_after_split_node() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3)]);
+        graph.snapshot();
+        graph.split_node(2, 2, 20).unwrap();
+        let after = graph.snapshot();
+        for n in graph.get_nodes() {
+            assert_eq!(after.get_neighbors(&n), graph.get_neighbors(n));
+        }
+        assert!(after.is_connected(&1, &3));
+        assert_eq!(after.get_neighbors(&2), vec![20]);
+    }
+}
@@ -0,0 +1,283 @@
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+
+/// Identifier of a node of a `StableDiGraph`. It stays valid until the node is
+/// removed and is never reused for another node: freed slots get a new
+/// generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId {
+    index: usize,
+    generation: u32,
+}
+
+impl NodeId {
+    /// Position of the node slot, only unique among the living nodes
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+struct Slot<T> {
+    generation: u32,
+    node: Option<StableNode<T>>,
+}
+
+struct StableNode<T> {
+    elem: T,
+    /// Slot indexes of the successors and predecessors
+    succ: Vec<usize>,
+    pred: Vec<usize>,
+}
+
+/// `StableDiGraph` is a `generic` directed graph whose nodes are referred by
+/// `NodeId`s that remain valid across removals of other nodes, so external
+/// systems can hold long-lived references to nodes. Unlike `DiGraph`, several
+/// nodes may hold the same value
+pub struct StableDiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    slots: Vec<Slot<T>>,
+    /// Slots of removed nodes, reused by `add_node`
+    free: Vec<usize>,
+    count: usize,
+}
+
+impl<T> StableDiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        StableDiGraph::<T> {
+            slots: Vec::new(),
+            free: Vec::new(),
+            count: 0,
+        }
+    }
+
+    fn get(&self, id: NodeId) -> Option<&StableNode<T>> {
+        self.slots
+            .get(id.index)
+            .filter(|s| s.generation == id.generation)
+            .and_then(|s| s.node.as_ref())
+    }
+
+    fn id(&self, index: usize) -> NodeId {
+        NodeId {
+            index,
+            generation: self.slots[index].generation,
+        }
+    }
+
+    /// Adds a new node `elem` and returns its identifier
+    pub fn add_node(&mut self, elem: T) -> NodeId {
+        let node = StableNode {
+            elem,
+            succ: Vec::new(),
+            pred: Vec::new(),
+        };
+        self.count += 1;
+        match self.free.pop() {
+            Some(index) => {
+                self.slots[index].node = Some(node);
+                self.id(index)
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    node: Some(node),
+                });
+                self.id(self.slots.len() - 1)
+            }
+        }
+    }
+
+    /// Removes node `id` and its edges, returning its value. Returns `None` if
+    /// `id` is not a node of the graph
+    pub fn remove_node(&mut self, id: NodeId) -> Option<T> {
+        self.get(id)?;
+        let slot = &mut self.slots[id.index];
+        let node = slot.node.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        for s in node.succ.iter().filter(|s| **s != id.index) {
+            if let Some(n) = self.slots[*s].node.as_mut() {
+                n.pred.retain(|p| *p != id.index);
+            }
+        }
+        for p in node.pred.iter().filter(|p| **p != id.index) {
+            if let Some(n) = self.slots[*p].node.as_mut() {
+                n.succ.retain(|s| *s != id.index);
+            }
+        }
+        self.free.push(id.index);
+        self.count -= 1;
+        Some(node.elem)
+    }
+
+    /// Returns `true` if `id` is a node of the graph
+    pub fn contains_node(&self, id: NodeId) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Returns the value of node `id`
+    pub fn node(&self, id: NodeId) -> Option<&T> {
+        self.get(id).map(|n| &n.elem)
+    }
+
+    /// Returns the first node holding `elem`
+    pub fn find_node(&self, elem: &T) -> Option<NodeId> {
+        self.node_ids()
+            .into_iter()
+            .find(|id| self.node(*id) == Some(elem))
+    }
+
+    /// Creates a new edge from node `from` to node `to`. Returns an error if
+    /// one of them is not a node of the graph
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) -> Result<(), &'static str> {
+        if !self.contains_node(from) || !self.contains_node(to) {
+            return Err("Node not found");
+        }
+        let n = self.slots[from.index].node.as_mut().unwrap();
+        if n.succ.contains(&to.index) {
+            return Ok(());
+        }
+        n.succ.push(to.index);
+        self.slots[to.index]
+            .node
+            .as_mut()
+            .unwrap()
+            .pred
+            .push(from.index);
+        Ok(())
+    }
+
+    /// Removes the edge from `from` to `to`. Returns `false` if there is no
+    /// such edge
+    pub fn remove_edge(&mut self, from: NodeId, to: NodeId) -> bool {
+        if !self.is_directly_connected(from, to) {
+            return false;
+        }
+        let n = self.slots[from.index].node.as_mut().unwrap();
+        n.succ.retain(|s| *s != to.index);
+        let m = self.slots[to.index].node.as_mut().unwrap();
+        m.pred.retain(|p| *p != from.index);
+        true
+    }
+
+    /// Returns if there is an edge from `from` to `to`
+    pub fn is_directly_connected(&self, from: NodeId, to: NodeId) -> bool {
+        self.contains_node(to) && self.get(from).is_some_and(|n| n.succ.contains(&to.index))
+    }
+
+    /// Returns the successors of node `from`, empty if it is not a node
+    pub fn get_neighbors(&self, from: NodeId) -> Vec<NodeId> {
+        match self.get(from) {
+            Some(n) => n.succ.iter().map(|s| self.id(*s)).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the predecessors of node `to`, empty if it is not a node
+    pub fn get_predecessors(&self, to: NodeId) -> Vec<NodeId> {
+        match self.get(to) {
+            Some(n) => n.pred.iter().map(|p| self.id(*p)).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the identifiers of all the nodes, by slot
+    pub fn node_ids(&self) -> Vec<NodeId> {
+        (0..self.slots.len())
+            .filter(|i| self.slots[*i].node.is_some())
+            .map(|i| self.id(i))
+            .collect()
+    }
+
+    /// Returns how many nodes are in the graph
+    pub fn count_nodes(&self) -> usize {
+        self.count
+    }
+
+    /// Returns if a graph doesn't contain nodes
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns a `DiGraph` with the same nodes and edges, to run the
+    /// algorithms of `DiGraph`. Nodes holding the same value are merged
+    pub fn to_digraph(&self) -> DiGraph<T> {
+        let mut graph = DiGraph::<T>::new();
+        let ids = self.node_ids();
+        for id in ids.iter() {
+            graph.add_node(self.node(*id).unwrap().clone());
+        }
+        for id in ids.iter() {
+            for m in self.get_neighbors(*id) {
+                graph.add_edge(
+                    self.node(*id).unwrap().clone(),
+                    self.node(m).unwrap().clone(),
+                );
+            }
+        }
+        graph
+    }
+}
+
+impl<T> Default for StableDiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StableDiGraph;
+    use crate::rugraph::IDiGraph;
+
+    #[test]
+    fn stable_ids() {
+        let mut graph = StableDiGraph::<&str>::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b).unwrap();
+        graph.add_edge(b, c).unwrap();
+        graph.add_edge(c, a).unwrap();
+        graph.add_edge(b, b).unwrap();
+
+        assert_eq!(graph.remove_node(b), Some("b"));
+        assert_eq!(graph.remove_node(b), None);
+        assert_eq!(graph.count_nodes(), 2);
+        assert!(!graph.contains_node(b));
+        assert!(graph.get_neighbors(a).is_empty());
+        assert!(graph.get_predecessors(c).is_empty());
+        assert_eq!(graph.get_neighbors(c), vec![a]);
+        assert!(graph.add_edge(a, b).is_err());
+
+        // The slot is reused but the old identifier stays invalid
+        let d = graph.add_node("d");
+        assert_eq!(d.index(), b.index());
+        assert_ne!(d, b);
+        assert_eq!(graph.node(b), None);
+        assert_eq!(graph.node(d), Some(&"d"));
+        assert_eq!(graph.node(c), Some(&"c"));
+        assert_eq!(graph.find_node(&"d"), Some(d));
+        assert_eq!(graph.node_ids(), vec![a, d, c]);
+
+        graph.add_edge(a, d).unwrap();
+        assert!(graph.is_directly_connected(a, d));
+        assert!(graph.remove_edge(a, d));
+        assert!(!graph.remove_edge(a, d));
+        graph.add_edge(d, a).unwrap();
+
+        let digraph = graph.to_digraph();
+        assert_eq!(digraph.get_neighbors("c"), vec!["a"]);
+        assert_eq!(digraph.get_neighbors("d"), vec!["a"]);
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt;
+
+use crate::adjacency::Adjacency;
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+
+/// Summary of the structure of a graph returned by `stats()`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats {
+    /// Number of nodes
+    pub node_count: usize,
+    /// Number of edges. Undirected edges are counted once
+    pub edge_count: usize,
+    /// Ratio between the number of edges and the maximum possible number of edges
+    pub density: f64,
+    /// Number of (weakly) connected components
+    pub components: usize,
+    /// Maximum degree of a node
+    pub max_degree: usize,
+    /// Minimum degree of a node
+    pub min_degree: usize,
+    /// Average degree of the nodes
+    pub avg_degree: f64,
+    /// Whether the graph is a directed acyclic graph. Undirected graphs
+    /// are only considered acyclic when they don't have edges
+    pub is_dag: bool,
+}
+
+impl fmt::Display for GraphStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "nodes: {}, edges: {}, density: {:.4}, components: {}, degree (min/avg/max): {}/{:.2}/{}, dag: {}",
+            self.node_count,
+            self.edge_count,
+            self.density,
+            self.components,
+            self.min_degree,
+            self.avg_degree,
+            self.max_degree,
+            self.is_dag
+        )
+    }
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a `GraphStats` summary of the graph
+    pub fn stats(&self) -> GraphStats {
+        stats(&self.adjacency(), true)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a `GraphStats` summary of the graph
+    pub fn stats(&self) -> GraphStats {
+        stats(&self.adjacency(), false)
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a `GraphStats` summary of the graph. Parallel edges are counted
+    /// as different edges, so the density may be greater than 1
+    pub fn stats(&self) -> GraphStats {
+        stats(&self.adjacency(), true)
+    }
+}
+
+fn stats<T>(adj: &Adjacency<T>, directed: bool) -> GraphStats
+where
+    T: Ord + Clone,
+{
+    let n = adj.len();
+    let stored: usize = adj.succ.iter().map(|s| s.len()).sum();
+    let edge_count = if directed {
+        stored
+    } else {
+        // Every edge is stored in both directions except self loops
+        let loops = (0..n).filter(|i| adj.succ[*i].contains(i)).count();
+        (stored + loops) / 2
+    };
+
+    let mut density = 0.0;
+    if n > 1 {
+        let possible = (n * (n - 1)) as f64;
+        density = if directed {
+            edge_count as f64 / possible
+        } else {
+            2.0 * edge_count as f64 / possible
+        };
+    }
+
+    let degrees = adj.degrees(directed);
+    let avg_degree = if n > 0 {
+        degrees.iter().sum::<usize>() as f64 / n as f64
+    } else {
+        0.0
+    };
+
+    GraphStats {
+        node_count: n,
+        edge_count,
+        density,
+        components: adj.components().len(),
+        max_degree: degrees.iter().copied().max().unwrap_or(0),
+        min_degree: degrees.iter().copied().min().unwrap_or(0),
+        avg_degree,
+        is_dag: if directed {
+            adj.topological_order().is_some()
+        } else {
+            edge_count == 0
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn stats_digraph() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 1..=5 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(1, 3);
+        graph.add_edge(4, 5);
+
+        let s = graph.stats();
+        assert_eq!(s.node_count, 5);
+        assert_eq!(s.edge_count, 4);
+        assert_eq!(s.density, 0.2);
+        assert_eq!(s.components, 2);
+        assert_eq!(s.max_degree, 2);
+        assert_eq!(s.min_degree, 1);
+        assert_eq!(s.avg_degree, 1.6);
+        assert!(s.is_dag);
+
+        graph.add_edge(3, 1);
+        assert!(!graph.stats().is_dag);
+        assert_eq!(
+            graph.stats().to_string(),
+            "nodes: 5, edges: 5, density: 0.2500, components: 2, degree (min/avg/max): 1/2.00/3, dag: false"
+        );
+    }
+
+    #[test]
+    fn stats_graph() {
+        let mut graph = Graph::<i32>::new();
+        for n in 1..=4 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 3);
+
+        let s = graph.stats();
+        assert_eq!(s.edge_count, 3);
+        assert_eq!(s.density, 0.5);
+        assert_eq!(s.components, 2);
+        assert_eq!(s.min_degree, 0);
+        assert!(!s.is_dag);
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::BTreeSet;
+use std::vec::Vec;
+
+use crate::adjacency::Adjacency;
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+use crate::multigraph::MultiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+/// Returns the nodes at most `radius` hops away from `center`, following
+/// the edges only in their direction if `directed`
+fn ball<T>(mut adj: Adjacency<T>, center: &T, radius: usize, directed: bool) -> Vec<T>
+where
+    T: Ord + Clone,
+{
+    let Some(i) = adj.nodes.iter().position(|n| n == center) else {
+        return Vec::new();
+    };
+    if !directed {
+        for (j, p) in adj.predecessors().into_iter().enumerate() {
+            adj.succ[j].extend(p);
+        }
+    }
+    let dist = adj.bfs_distances(i);
+    adj.nodes
+        .into_iter()
+        .zip(dist)
+        .filter(|(_, d)| d.is_some_and(|d| d <= radius))
+        .map(|(n, _)| n)
+        .collect()
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the subgraph induced by the nodes at most `radius` hops away
+    /// from `center`, following the edges only in their direction if
+    /// `directed`. Empty if `center` is not in the graph
+    pub fn ego_graph(&self, center: &T, radius: usize, directed: bool) -> Self {
+        self.subgraph(ball(self.adjacency(), center, radius, directed))
+    }
+
+    /// Returns the subgraph induced by `nodes`: those nodes, in the order of
+    /// the graph, and every edge between two of them, with their attributes.
+    /// Nodes not in the graph are ignored
+    pub fn subgraph(&self, nodes: impl IntoIterator<Item = T>) -> Self {
+        let keep: BTreeSet<T> = nodes.into_iter().collect();
+        let nodes: Vec<T> = self
+            .get_nodes()
+            .into_iter()
+            .filter(|n| keep.contains(n))
+            .collect();
+        let mut graph = DiGraph::new();
+        for n in nodes.iter() {
+            graph.add_node(n.clone());
+        }
+        for n in nodes.iter() {
+            for m in self.get_neighbors(n.clone()) {
+                if keep.contains(&m) {
+                    graph.add_edge(n.clone(), m);
+                }
+            }
+        }
+        graph.attrs.nodes = self.attrs.nodes.clone();
+        graph.attrs.nodes.retain(|n, _| keep.contains(n));
+        graph.attrs.edges = self.attrs.edges.clone();
+        graph
+            .attrs
+            .edges
+            .retain(|(a, b), _| keep.contains(a) && keep.contains(b));
+        graph
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the subgraph induced by the nodes at most `radius` hops away
+    /// from `center`. Empty if `center` is not in the graph
+    pub fn ego_graph(&self, center: &T, radius: usize) -> Self {
+        self.subgraph(ball(self.adjacency(), center, radius, true))
+    }
+
+    /// Returns the subgraph induced by `nodes`, see `DiGraph::subgraph`
+    pub fn subgraph(&self, nodes: impl IntoIterator<Item = T>) -> Self {
+        Graph {
+            digraph: self.digraph.subgraph(nodes),
+        }
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the subgraph induced by the nodes at most `radius` hops away
+    /// from `center`, see `DiGraph::ego_graph`
+    pub fn ego_graph(&self, center: &T, radius: usize, directed: bool) -> Self {
+        self.subgraph(ball(self.adjacency(), center, radius, directed))
+    }
+
+    /// Returns the subgraph induced by `nodes`: those nodes, in the order of
+    /// the graph, and every edge between two of them, with their attributes.
+    /// Nodes not in the graph are ignored
+    pub fn subgraph(&self, nodes: impl IntoIterator<Item = T>) -> Self {
+        let keep: BTreeSet<T> = nodes.into_iter().collect();
+        let nodes: Vec<T> = self
+            .get_nodes()
+            .into_iter()
+            .filter(|n| keep.contains(n))
+            .collect();
+        let mut graph = MultiDiGraph::new();
+        for n in nodes.iter() {
+            graph.add_node(n.clone());
+        }
+        for n in nodes.iter() {
+            for (m, e) in self.get_neighbors(n.clone()) {
+                if keep.contains(&m) {
+                    graph.add_edge(n.clone(), m, e);
+                }
+            }
+        }
+        graph.attrs.nodes = self.attrs.nodes.clone();
+        graph.attrs.nodes.retain(|n, _| keep.contains(n));
+        graph.attrs.edges = self.attrs.edges.clone();
+        graph
+            .attrs
+            .edges
+            .retain(|(a, b, _), _| keep.contains(a) && keep.contains(b));
+        graph
+    }
+}
+
+impl<T, E> MultiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns the subgraph induced by the nodes at most `radius` hops away
+    /// from `center`. Empty if `center` is not in the graph
+    pub fn ego_graph(&self, center: &T, radius: usize) -> Self {
+        MultiGraph {
+            multidigraph: self.multidigraph.ego_graph(center, radius, true),
+        }
+    }
+
+    /// Returns the subgraph induced by `nodes`, see `MultiDiGraph::subgraph`
+    pub fn subgraph(&self, nodes: impl IntoIterator<Item = T>) -> Self {
+        MultiGraph {
+            multidigraph: self.multidigraph.subgraph(nodes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::multigraph::MultiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+    use crate::rugraph::IMultiGraph;
+
+    #[test]
+    fn induced_subgraph() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3), (3, 4), (4, 1), (1, 3)]);
+        graph.node_attrs_mut(3).insert("color", "red");
+        graph.node_attrs_mut(4).insert("color", "blue");
+        graph.edge_attrs_mut(1, 3).insert("w", 2);
+        graph.edge_attrs_mut(3, 4).insert("w", 5);
+
+        let sub = graph.subgraph([3, 1, 2, 9]);
+        assert_eq!(sub.get_nodes(), vec![1, 2, 3]);
+        assert_eq!(sub.get_neighbors(1), vec![2, 3]);
+        assert_eq!(sub.get_neighbors(3), Vec::<i32>::new());
+        assert_eq!(sub.node_attrs(&3).unwrap().get_str("color"), Some("red"));
+        assert!(sub.node_attrs(&4).is_none());
+        assert_eq!(sub.edge_attrs(&1, &3).unwrap().get_int("w"), Some(2));
+        assert!(sub.edge_attrs(&3, &4).is_none());
+
+        let mut graph = Graph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3)]);
+        let sub = graph.subgraph([2, 3]);
+        assert!(sub.is_directly_connected(3, 2));
+        assert!(!sub.node_exists(1));
+
+        let mut graph = MultiDiGraph::<i32, char>::new();
+        graph.extend_with_edges([(1, 2, 'a'), (1, 2, 'b'), (2, 3, 'c')]);
+        graph.edge_attrs_mut(2, 3, 'c').insert("w", 1);
+        let sub = graph.subgraph(1..=2);
+        assert_eq!(sub.get_neighbors(1), vec![(2, 'a'), (2, 'b')]);
+        assert!(sub.get_neighbors(2).is_empty());
+        assert!(sub.edge_attrs(&2, &3, &'c').is_none());
+
+        let mut graph = MultiGraph::<i32, char>::new();
+        graph.extend_with_edges([(1, 2, 'a'), (2, 3, 'b')]);
+        let sub = graph.subgraph(vec![1, 2]);
+        assert!(sub.is_directly_connected_by(2, 1, 'a'));
+        assert_eq!(sub.count_nodes(), 2);
+    }
+
+    #[test]
+    fn ego_graph() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3), (3, 4), (5, 1), (6, 5)]);
+        assert_eq!(graph.ego_graph(&1, 2, true).get_nodes(), vec![1, 2, 3]);
+        let ego = graph.ego_graph(&1, 1, false);
+        assert_eq!(ego.get_nodes(), vec![1, 2, 5]);
+        assert_eq!(ego.get_neighbors(5), vec![1]);
+        assert_eq!(graph.ego_graph(&1, 0, false).get_nodes(), vec![1]);
+        assert_eq!(graph.ego_graph(&9, 3, false).count_nodes(), 0);
+
+        let mut graph = Graph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3), (3, 4)]);
+        assert_eq!(graph.ego_graph(&3, 1).get_nodes(), vec![2, 3, 4]);
+
+        let mut graph = MultiDiGraph::<i32, char>::new();
+        graph.extend_with_edges([(1, 2, 'a'), (3, 2, 'b'), (3, 4, 'c')]);
+        assert_eq!(graph.ego_graph(&2, 5, true).get_nodes(), vec![2]);
+        assert_eq!(graph.ego_graph(&2, 1, false).get_nodes(), vec![1, 2, 3]);
+
+        let mut graph = MultiGraph::<i32, char>::new();
+        graph.extend_with_edges([(1, 2, 'a'), (2, 3, 'b')]);
+        assert_eq!(graph.ego_graph(&1, 1).get_nodes(), vec![1, 2]);
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+
+/// `TemporalDiGraph` is a `generic` directed graph where every edge, labeled
+/// with a value of type `E`, is only valid during the closed time interval
+/// `[start, end]`. Events are edges with `start == end`.
+/// Both `T` and `E` must implement:
+/// `Ord + Clone + std::fmt::Display + std::fmt::Debug`
+pub struct TemporalDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    nodes: BTreeSet<T>,
+    edges: Vec<TemporalEdge<T, E>>,
+}
+
+/// Edge from `from` to `to` valid from `start` to `end`, both included
+struct TemporalEdge<T, E> {
+    from: T,
+    to: T,
+    edge: E,
+    start: i64,
+    end: i64,
+}
+
+impl<T, E> TemporalDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        TemporalDiGraph::<T, E> {
+            nodes: BTreeSet::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Adds a new node `elem`
+    pub fn add_node(&mut self, elem: T) {
+        self.nodes.insert(elem);
+    }
+
+    /// Returns `true` if node `node` exists
+    pub fn node_exists(&self, node: &T) -> bool {
+        self.nodes.contains(node)
+    }
+
+    /// Creates a new edge from node `from` to node `to` labeled `edge`, valid
+    /// from `start` to `end`. Returns an error if a node was not added or the
+    /// interval is empty
+    pub fn add_edge(
+        &mut self,
+        from: T,
+        to: T,
+        edge: E,
+        start: i64,
+        end: i64,
+    ) -> Result<(), &'static str> {
+        if !self.nodes.contains(&from) || !self.nodes.contains(&to) {
+            return Err("Node not found");
+        }
+        if end < start {
+            return Err("Edge interval ends before it starts");
+        }
+        self.edges.push(TemporalEdge {
+            from,
+            to,
+            edge,
+            start,
+            end,
+        });
+        Ok(())
+    }
+
+    /// Creates a new edge from node `from` to node `to` labeled `edge` that
+    /// only happens at time `t`
+    pub fn add_event(&mut self, from: T, to: T, edge: E, t: i64) -> Result<(), &'static str> {
+        self.add_edge(from, to, edge, t, t)
+    }
+
+    /// Returns the nodes, sorted
+    pub fn get_nodes(&self) -> Vec<T> {
+        self.nodes.iter().cloned().collect()
+    }
+
+    /// Returns how many nodes are in the graph
+    pub fn count_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns how many edges are in the graph
+    pub fn count_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns every edge as `(from, to, edge, start, end)`, in order of
+    /// addition
+    pub fn get_edges(&self) -> Vec<(T, T, E, i64, i64)> {
+        self.edges
+            .iter()
+            .map(|e| (e.from.clone(), e.to.clone(), e.edge.clone(), e.start, e.end))
+            .collect()
+    }
+
+    /// Returns the edges valid at time `t` as `(from, to, edge)`
+    pub fn edges_at(&self, t: i64) -> Vec<(T, T, E)> {
+        self.edges
+            .iter()
+            .filter(|e| e.start <= t && t <= e.end)
+            .map(|e| (e.from.clone(), e.to.clone(), e.edge.clone()))
+            .collect()
+    }
+
+    /// Returns a graph with every node and the edges valid at time `t`
+    pub fn snapshot_at(&self, t: i64) -> DiGraph<T> {
+        let mut graph = DiGraph::<T>::new();
+        for n in self.nodes.iter() {
+            graph.add_node(n.clone());
+        }
+        for (from, to, _) in self.edges_at(t) {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    /// Returns the earliest time every node can be reached leaving `from` at
+    /// time `start`, following edges at non-decreasing times (an edge is
+    /// taken at any time of its interval and crossing it takes no time).
+    /// Unreachable nodes are not in the map
+    pub fn earliest_arrivals(&self, from: &T, start: i64) -> BTreeMap<T, i64> {
+        self.arrivals(from, start)
+            .into_iter()
+            .map(|(n, (t, _))| (n, t))
+            .collect()
+    }
+
+    /// Returns a time-respecting path from `from` to `to` leaving at time
+    /// `start` that arrives as early as possible, as a list of edges
+    /// `(from, to, edge, time)`. `None` if `to` can't be reached
+    pub fn time_respecting_path(
+        &self,
+        from: &T,
+        to: &T,
+        start: i64,
+    ) -> Option<Vec<(T, T, E, i64)>> {
+        let arrivals = self.arrivals(from, start);
+        arrivals.get(to)?;
+        let mut path = Vec::<(T, T, E, i64)>::new();
+        let mut node = to;
+        while let Some((t, Some(e))) = arrivals.get(node) {
+            let e = &self.edges[*e];
+            path.push((e.from.clone(), e.to.clone(), e.edge.clone(), *t));
+            node = &e.from;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Earliest arrival time of every reachable node with the index of the
+    /// edge used to get there (`None` for the starting node)
+    fn arrivals(&self, from: &T, start: i64) -> BTreeMap<T, (i64, Option<usize>)> {
+        let mut arrivals = BTreeMap::<T, (i64, Option<usize>)>::new();
+        if !self.nodes.contains(from) {
+            return arrivals;
+        }
+        arrivals.insert(from.clone(), (start, None));
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (i, e) in self.edges.iter().enumerate() {
+                let Some((t, _)) = arrivals.get(&e.from) else {
+                    continue;
+                };
+                if *t > e.end {
+                    continue;
+                }
+                let t = (*t).max(e.start);
+                if arrivals.get(&e.to).is_none_or(|(old, _)| t < *old) {
+                    arrivals.insert(e.to.clone(), (t, Some(i)));
+                    changed = true;
+                }
+            }
+        }
+        arrivals
+    }
+}
+
+impl<T, E> Default for TemporalDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TemporalDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn temporal_digraph() {
+        let mut graph = TemporalDiGraph::<&str, &str>::new();
+        for n in ["a", "b", "c", "d"] {
+            graph.add_node(n);
+        }
+        graph.add_edge("a", "b", "call", 1, 5).unwrap();
+        graph.add_event("b", "c", "mail", 3).unwrap();
+        graph.add_event("c", "d", "mail", 2).unwrap();
+        graph.add_event("a", "c", "sms", 10).unwrap();
+        graph.add_event("c", "d", "sms", 12).unwrap();
+        assert!(graph.add_edge("a", "x", "call", 1, 2).is_err());
+        assert!(graph.add_edge("a", "b", "call", 3, 2).is_err());
+        assert_eq!(graph.count_edges(), 5);
+
+        let snapshot = graph.snapshot_at(3);
+        assert_eq!(snapshot.count_nodes(), 4);
+        assert_eq!(snapshot.get_neighbors("a"), vec!["b"]);
+        assert_eq!(snapshot.get_neighbors("b"), vec!["c"]);
+        assert!(snapshot.get_neighbors("c").is_empty());
+        assert_eq!(graph.edges_at(12), vec![("c", "d", "sms")]);
+
+        let arrivals = graph.earliest_arrivals(&"a", 0);
+        assert_eq!(arrivals[&"b"], 1);
+        assert_eq!(arrivals[&"c"], 3);
+        // c -> d at time 2 happens before c is reached
+        assert_eq!(arrivals[&"d"], 12);
+        assert_eq!(
+            graph.time_respecting_path(&"a", &"d", 0),
+            Some(vec![
+                ("a", "b", "call", 1),
+                ("b", "c", "mail", 3),
+                ("c", "d", "sms", 12)
+            ])
+        );
+        assert_eq!(graph.earliest_arrivals(&"a", 4).get(&"c"), Some(&10));
+        assert_eq!(graph.time_respecting_path(&"a", &"d", 13), None);
+        assert_eq!(graph.time_respecting_path(&"a", &"a", 0), Some(vec![]));
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a Trivial Graph Format representation of the graph. Nodes get
+    /// the ids `1`, `2`... and their value as label
+    pub fn to_tgf_string(&self) -> String {
+        let adj = self.adjacency();
+        let edges: Vec<(usize, usize, Option<String>)> = adj
+            .edges(true)
+            .into_iter()
+            .map(|(i, j)| (i, j, None))
+            .collect();
+        write_tgf(&adj.nodes, &edges)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a Trivial Graph Format representation of the graph. Nodes get
+    /// the ids `1`, `2`... and their value as label. Every edge is written once
+    pub fn to_tgf_string(&self) -> String {
+        let adj = self.adjacency();
+        let edges: Vec<(usize, usize, Option<String>)> = adj
+            .edges(false)
+            .into_iter()
+            .map(|(i, j)| (i, j, None))
+            .collect();
+        write_tgf(&adj.nodes, &edges)
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a Trivial Graph Format representation of the graph. Nodes get
+    /// the ids `1`, `2`... and their value as label, edges are labeled with
+    /// their value
+    pub fn to_tgf_string(&self) -> String {
+        let nodes = self.get_nodes();
+        let mut edges = Vec::<(usize, usize, Option<String>)>::new();
+        for (i, n) in nodes.iter().enumerate() {
+            for (m, e) in self.get_neighbors(n.clone()) {
+                let j = nodes.iter().position(|x| *x == m).unwrap();
+                edges.push((i, j, Some(e.to_string())));
+            }
+        }
+        write_tgf(&nodes, &edges)
+    }
+}
+
+/// Reads a directed graph from a Trivial Graph Format file. Node labels (or
+/// the node ids when there is no label) are parsed as `T`
+pub fn digraph_from_tgf_str<T>(content: &str) -> Result<DiGraph<T>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let tgf = read_tgf(content)?;
+    let mut graph = DiGraph::<T>::new();
+    for n in tgf.nodes.iter() {
+        graph.add_node(parse_value(n)?);
+    }
+    for (from, to, _) in tgf.edges.iter() {
+        graph.add_edge(parse_value(from)?, parse_value(to)?);
+    }
+    Ok(graph)
+}
+
+/// Reads an undirected graph from a Trivial Graph Format file. Node labels
+/// (or the node ids when there is no label) are parsed as `T`
+pub fn graph_from_tgf_str<T>(content: &str) -> Result<Graph<T>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let tgf = read_tgf(content)?;
+    let mut graph = Graph::<T>::new();
+    for n in tgf.nodes.iter() {
+        graph.add_node(parse_value(n)?);
+    }
+    for (from, to, _) in tgf.edges.iter() {
+        graph.add_edge(parse_value(from)?, parse_value(to)?);
+    }
+    Ok(graph)
+}
+
+/// Reads a multi directed graph from a Trivial Graph Format file. Node labels
+/// (or the node ids when there is no label) are parsed as `T` and edge labels
+/// (empty if missing) as `E`
+pub fn multidigraph_from_tgf_str<T, E>(content: &str) -> Result<MultiDiGraph<T, E>, &'static str>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let tgf = read_tgf(content)?;
+    let mut graph = MultiDiGraph::<T, E>::new();
+    for n in tgf.nodes.iter() {
+        graph.add_node(parse_value(n)?);
+    }
+    for (from, to, label) in tgf.edges.iter() {
+        graph.add_edge(parse_value(from)?, parse_value(to)?, parse_value(label)?);
+    }
+    Ok(graph)
+}
+
+fn parse_value<V: FromStr>(s: &str) -> Result<V, &'static str> {
+    V::from_str(s).map_err(|_| "TGF file not correct. Value can't be parsed.")
+}
+
+fn write_tgf<T>(nodes: &[T], edges: &[(usize, usize, Option<String>)]) -> String
+where
+    T: std::fmt::Display,
+{
+    let mut s = String::new();
+    for (i, n) in nodes.iter().enumerate() {
+        s += &format!("{} {}\n", i + 1, n);
+    }
+    s += "#\n";
+    for (i, j, label) in edges.iter() {
+        match label {
+            None => s += &format!("{} {}\n", i + 1, j + 1),
+            Some(l) => s += &format!("{} {} {}\n", i + 1, j + 1, l),
+        }
+    }
+    s
+}
+
+/// Splits `line` in its first word and the rest of the line
+fn split_word(line: &str) -> (&str, &str) {
+    let line = line.trim();
+    match line.find(char::is_whitespace) {
+        Some(i) => (&line[..i], line[i..].trim_start()),
+        None => (line, ""),
+    }
+}
+
+/// Node values and edges `(from, to, label)` given by node values
+struct Tgf {
+    nodes: Vec<String>,
+    edges: Vec<(String, String, String)>,
+}
+
+fn read_tgf(content: &str) -> Result<Tgf, &'static str> {
+    let mut ids = BTreeMap::<String, String>::new();
+    let mut tgf = Tgf {
+        nodes: Vec::new(),
+        edges: Vec::new(),
+    };
+    let mut lines = content.lines().filter(|l| !l.trim().is_empty());
+    for line in lines.by_ref() {
+        if line.trim() == "#" {
+            break;
+        }
+        let (id, label) = split_word(line);
+        let value = if label.is_empty() { id } else { label };
+        ids.insert(id.to_string(), value.to_string());
+        tgf.nodes.push(value.to_string());
+    }
+    for line in lines {
+        let (from, rest) = split_word(line);
+        let (to, label) = split_word(rest);
+        match (ids.get(from), ids.get(to)) {
+            (Some(from), Some(to)) => tgf
+                .edges
+                .push((from.clone(), to.clone(), label.to_string())),
+            _ => return Err("TGF file not correct. Edge with unknown node."),
+        }
+    }
+    Ok(tgf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::digraph_from_tgf_str;
+    use super::graph_from_tgf_str;
+    use super::multidigraph_from_tgf_str;
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn tgf_round_trip() {
+        let mut graph = MultiDiGraph::<String, String>::new();
+        graph.add_node(String::from("first node"));
+        graph.add_node(String::from("second"));
+        graph.add_edge(
+            String::from("first node"),
+            String::from("second"),
+            String::from("an edge"),
+        );
+        graph.add_edge(
+            String::from("first node"),
+            String::from("second"),
+            String::from("another"),
+        );
+        let s = graph.to_tgf_string();
+        assert_eq!(s, "1 first node\n2 second\n#\n1 2 an edge\n1 2 another\n");
+        let read = multidigraph_from_tgf_str::<String, String>(&s).unwrap();
+        assert_eq!(read.get_nodes(), graph.get_nodes());
+        assert_eq!(
+            read.get_neighbors(String::from("first node")),
+            graph.get_neighbors(String::from("first node"))
+        );
+
+        let mut graph = Graph::<i32>::new();
+        for n in 1..=3 {
+            graph.add_node(n * 10);
+        }
+        graph.add_edge(10, 20);
+        graph.add_edge(30, 20);
+        let s = graph.to_tgf_string();
+        assert_eq!(s, "1 10\n2 20\n3 30\n#\n1 2\n2 3\n");
+        let read = graph_from_tgf_str::<i32>(&s).unwrap();
+        assert_eq!(read.get_neighbors(20), vec![10, 30]);
+
+        let mut graph = DiGraph::<i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_edge(2, 1);
+        let read = digraph_from_tgf_str::<i32>(&graph.to_tgf_string()).unwrap();
+        assert_eq!(read.get_neighbors(2), vec![1]);
+        assert!(read.get_neighbors(1).is_empty());
+    }
+
+    #[test]
+    fn tgf_without_labels() {
+        let graph = digraph_from_tgf_str::<String>("a\nb Node B\n\n#\na b\nb a label\n").unwrap();
+        assert_eq!(graph.get_nodes(), vec!["a", "Node B"]);
+        assert_eq!(graph.get_neighbors(String::from("Node B")), vec!["a"]);
+        assert!(digraph_from_tgf_str::<String>("1\n#\n1 2\n").is_err());
+        assert!(multidigraph_from_tgf_str::<String, i32>("1\n2\n#\n1 2 x\n").is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::layout::positions;
+use crate::layout::Layout;
+use crate::multidigraph::MultiDiGraph;
+use crate::rugraph::IMultiDiGraph;
+
+/// Distance in cm between neighbor nodes in the picture
+const SCALE: f64 = 1.5;
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a TikZ `tikzpicture` drawing the graph with the nodes placed
+    /// following `layout`
+    pub fn to_tikz_string(&self, layout: Layout) -> String {
+        let adj = self.adjacency();
+        let edges: Vec<(usize, usize, Option<String>)> = adj
+            .edges(true)
+            .into_iter()
+            .map(|(i, j)| (i, j, None))
+            .collect();
+        write_tikz(true, &adj.nodes, &positions(&adj, layout), &edges)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a TikZ `tikzpicture` drawing the graph with the nodes placed
+    /// following `layout`
+    pub fn to_tikz_string(&self, layout: Layout) -> String {
+        let adj = self.adjacency();
+        let edges: Vec<(usize, usize, Option<String>)> = adj
+            .edges(false)
+            .into_iter()
+            .map(|(i, j)| (i, j, None))
+            .collect();
+        write_tikz(false, &adj.nodes, &positions(&adj, layout), &edges)
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a TikZ `tikzpicture` drawing the graph with the nodes placed
+    /// following `layout`. Edges are labeled with their value and parallel
+    /// edges are bent so they don't overlap
+    pub fn to_tikz_string(&self, layout: Layout) -> String {
+        let adj = self.adjacency();
+        let mut edges = Vec::<(usize, usize, Option<String>)>::new();
+        for (i, n) in adj.nodes.iter().enumerate() {
+            for (m, e) in self.get_neighbors(n.clone()) {
+                let j = adj.nodes.iter().position(|x| *x == m).unwrap();
+                edges.push((i, j, Some(e.to_string())));
+            }
+        }
+        write_tikz(true, &adj.nodes, &positions(&adj, layout), &edges)
+    }
+}
+
+/// Escapes the characters with a special meaning in LaTeX
+fn escape(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => ret.push_str("\\textbackslash{}"),
+            '~' => ret.push_str("\\textasciitilde{}"),
+            '^' => ret.push_str("\\textasciicircum{}"),
+            '{' | '}' | '$' | '&' | '#' | '_' | '%' => {
+                ret.push('\\');
+                ret.push(c);
+            }
+            _ => ret.push(c),
+        }
+    }
+    ret
+}
+
+fn write_tikz<T>(
+    directed: bool,
+    nodes: &[T],
+    pos: &[(f64, f64)],
+    edges: &[(usize, usize, Option<String>)],
+) -> String
+where
+    T: std::fmt::Display,
+{
+    let mut s = String::from("\\begin{tikzpicture}[>=stealth]\n");
+    for (i, n) in nodes.iter().enumerate() {
+        s += &format!(
+            "  \\node[draw, circle] (n{}) at ({:.3}, {:.3}) {{{}}};\n",
+            i,
+            pos[i].0 * SCALE,
+            pos[i].1 * SCALE,
+            escape(&n.to_string())
+        );
+    }
+    let arrow = if directed { "->" } else { "-" };
+    for (k, (i, j, label)) in edges.iter().enumerate() {
+        // Edges already drawn between the same two nodes
+        let parallel = edges[..k]
+            .iter()
+            .filter(|(a, b, _)| (a, b) == (i, j) || (!directed && (b, a) == (i, j)))
+            .count();
+        let path = if i == j {
+            format!("to[loop above, looseness={}]", 8 + 4 * parallel)
+        } else if parallel > 0 {
+            format!("to[bend left={}]", 20 * parallel)
+        } else {
+            String::from("--")
+        };
+        let label = match label {
+            Some(l) => format!(" node[midway, above, sloped] {{{}}}", escape(l)),
+            None => String::new(),
+        };
+        s += &format!("  \\draw[{}] (n{}) {}{} (n{});\n", arrow, i, path, label, j);
+    }
+    s += "\\end{tikzpicture}\n";
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::layout::Layout;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+
+    #[test]
+    fn tikz_export() {
+        let mut graph = DiGraph::<String>::new();
+        graph.add_node(String::from("a_1"));
+        graph.add_node(String::from("b"));
+        graph.add_edge(String::from("a_1"), String::from("b"));
+        assert_eq!(
+            graph.to_tikz_string(Layout::Grid),
+            "\\begin{tikzpicture}[>=stealth]
+  \\node[draw, circle] (n0) at (0.000, 0.000) {a\\_1};
+  \\node[draw, circle] (n1) at (1.500, 0.000) {b};
+  \\draw[->] (n0) -- (n1);
+\\end{tikzpicture}
+"
+        );
+
+        let mut graph = MultiDiGraph::<i32, String>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_edge(1, 2, String::from("x"));
+        graph.add_edge(1, 2, String::from("50%"));
+        graph.add_edge(2, 2, String::from("y"));
+        let s = graph.to_tikz_string(Layout::Circular);
+        assert!(s.contains("\\draw[->] (n0) -- node[midway, above, sloped] {x} (n1);"));
+        assert!(s.contains("to[bend left=20] node[midway, above, sloped] {50\\%} (n1);"));
+        assert!(s.contains("\\draw[->] (n1) to[loop above, looseness=8]"));
+    }
+}
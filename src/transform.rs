@@ -0,0 +1,482 @@
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use crate::adjacency::Adjacency;
+use crate::attributes::undirected_key;
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::multidigraph::MultiDiGraph;
+use crate::multigraph::MultiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::IMultiDiGraph;
+
+/// Returns the block `partition` maps every node of `adj` to and the edges
+/// between different blocks, with how many edges of `adj` each one merges,
+/// in order of first appearance. Undirected edges are keyed by their ordered
+/// blocks
+fn quotient_edges<T, K>(
+    adj: &Adjacency<T>,
+    partition: impl Fn(&T) -> K,
+    directed: bool,
+) -> (Vec<K>, Vec<(K, K, i64)>)
+where
+    T: Ord + Clone,
+    K: Ord + Clone,
+{
+    let keys: Vec<K> = adj.nodes.iter().map(partition).collect();
+    let mut counts = BTreeMap::new();
+    let mut edges = Vec::new();
+    for (i, j) in adj.edges(directed) {
+        if keys[i] == keys[j] {
+            continue;
+        }
+        let key = if directed {
+            (keys[i].clone(), keys[j].clone())
+        } else {
+            undirected_key(keys[i].clone(), keys[j].clone())
+        };
+        let count = counts.entry(key.clone()).or_insert(0);
+        if *count == 0 {
+            edges.push(key);
+        }
+        *count += 1;
+    }
+    let edges = edges
+        .into_iter()
+        .map(|(a, b)| {
+            let count = counts[&(a.clone(), b.clone())];
+            (a, b, count)
+        })
+        .collect();
+    (keys, edges)
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a graph with the same structure and attributes where every
+    /// node `n` is replaced by `f(n)`. `f` is called once per node, in
+    /// insertion order. Nodes mapped to the same value are merged
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> DiGraph<U>
+    where
+        U: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        self.filter_map(|n| Some(f(n)), |_, _| true)
+    }
+
+    /// Returns the quotient graph by `partition`: a node per key the nodes
+    /// are mapped to, in order of first appearance, and an edge between two
+    /// keys if there is one between any of their nodes, with the number of
+    /// edges it merges as its `count` attribute. Edges between nodes mapped
+    /// to the same key are dropped. Other attributes are not kept
+    pub fn quotient<K>(&self, partition: impl Fn(&T) -> K) -> DiGraph<K>
+    where
+        K: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        let (keys, edges) = quotient_edges(&self.adjacency(), partition, true);
+        let mut graph = DiGraph::new();
+        for k in keys {
+            graph.add_node(k);
+        }
+        for (from, to, count) in edges {
+            graph.add_edge(from.clone(), to.clone());
+            graph.edge_attrs_mut(from, to).insert("count", count);
+        }
+        graph
+    }
+
+    /// Same as `map` but dropping the nodes for which `node_f` returns
+    /// `None` and the edges `(from, to)` rejected by `edge_f`, with their
+    /// attributes: a new graph with the content of a `filter` view.
+    /// `edge_f` is only called for edges between kept nodes
+    pub fn filter_map<U>(
+        &self,
+        mut node_f: impl FnMut(&T) -> Option<U>,
+        mut edge_f: impl FnMut(&T, &T) -> bool,
+    ) -> DiGraph<U>
+    where
+        U: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        let nodes = self.get_nodes();
+        let mapped: Vec<Option<U>> = nodes.iter().map(&mut node_f).collect();
+        let mut graph = DiGraph::<U>::new();
+        for (n, u) in nodes.iter().zip(mapped.iter()) {
+            let Some(u) = u else {
+                continue;
+            };
+            graph.add_node(u.clone());
+            if let Some(a) = self.node_attrs(n) {
+                graph.attrs.nodes.insert(u.clone(), a.clone());
+            }
+        }
+        for (i, n) in nodes.iter().enumerate() {
+            for j in self.neighbor_ids(i) {
+                let (Some(from), Some(to)) = (&mapped[i], &mapped[*j]) else {
+                    continue;
+                };
+                if !edge_f(n, &nodes[*j]) {
+                    continue;
+                }
+                graph.add_edge(from.clone(), to.clone());
+                if let Some(a) = self.edge_attrs(n, &nodes[*j]) {
+                    graph
+                        .attrs
+                        .edges
+                        .insert((from.clone(), to.clone()), a.clone());
+                }
+            }
+        }
+        graph
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a graph with the same structure and attributes where every
+    /// node `n` is replaced by `f(n)`, see `DiGraph::map`
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Graph<U>
+    where
+        U: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        self.filter_map(|n| Some(f(n)), |_, _| true)
+    }
+
+    /// Returns the quotient graph by `partition`, see `DiGraph::quotient`
+    pub fn quotient<K>(&self, partition: impl Fn(&T) -> K) -> Graph<K>
+    where
+        K: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        let (keys, edges) = quotient_edges(&self.adjacency(), partition, false);
+        let mut graph = Graph::new();
+        for k in keys {
+            graph.add_node(k);
+        }
+        for (a, b, count) in edges {
+            graph.add_edge(a.clone(), b.clone());
+            graph.edge_attrs_mut(a, b).insert("count", count);
+        }
+        graph
+    }
+
+    /// Same as `map` but dropping the nodes for which `node_f` returns
+    /// `None` and the edges rejected by `edge_f`, see `DiGraph::filter_map`.
+    /// `edge_f` is called once per edge, with its nodes in either order
+    pub fn filter_map<U>(
+        &self,
+        node_f: impl FnMut(&T) -> Option<U>,
+        mut edge_f: impl FnMut(&T, &T) -> bool,
+    ) -> Graph<U>
+    where
+        U: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        let mut decided = BTreeMap::new();
+        let mut graph = Graph {
+            digraph: self.digraph.filter_map(node_f, |a, b| {
+                *decided
+                    .entry(undirected_key(a.clone(), b.clone()))
+                    .or_insert_with(|| edge_f(a, b))
+            }),
+        };
+        // edge attributes are keyed by the ordered pair of nodes, which the
+        // mapping may have flipped
+        let edges = std::mem::take(&mut graph.digraph.attrs.edges);
+        for ((a, b), attrs) in edges {
+            *graph.edge_attrs_mut(a, b) = attrs;
+        }
+        graph
+    }
+}
+
+impl<T, E> MultiDiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a graph with the same structure and attributes where every
+    /// node `n` is replaced by `node_f(n)` and every edge value `e` by
+    /// `edge_f(e)`. Each function is called once per node or edge, in
+    /// insertion order. Nodes or parallel edges mapped to the same value are
+    /// merged
+    pub fn map<U, F>(
+        &self,
+        mut node_f: impl FnMut(&T) -> U,
+        mut edge_f: impl FnMut(&E) -> F,
+    ) -> MultiDiGraph<U, F>
+    where
+        U: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+        F: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        self.filter_map(|n| Some(node_f(n)), |_, _, e| Some(edge_f(e)))
+    }
+
+    /// Returns the quotient graph by `partition`, see `DiGraph::quotient`.
+    /// Parallel edges are counted one by one
+    pub fn quotient<K>(&self, partition: impl Fn(&T) -> K) -> DiGraph<K>
+    where
+        K: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        let (keys, edges) = quotient_edges(&self.adjacency(), partition, true);
+        let mut graph = DiGraph::new();
+        for k in keys {
+            graph.add_node(k);
+        }
+        for (from, to, count) in edges {
+            graph.add_edge(from.clone(), to.clone());
+            graph.edge_attrs_mut(from, to).insert("count", count);
+        }
+        graph
+    }
+
+    /// Same as `map` but dropping, with their attributes, the nodes for
+    /// which `node_f` returns `None` and the edges `(from, to, edge)` for
+    /// which `edge_f` does. `edge_f` is only called for edges between kept
+    /// nodes
+    pub fn filter_map<U, F>(
+        &self,
+        mut node_f: impl FnMut(&T) -> Option<U>,
+        mut edge_f: impl FnMut(&T, &T, &E) -> Option<F>,
+    ) -> MultiDiGraph<U, F>
+    where
+        U: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+        F: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        let nodes = self.get_nodes();
+        let mapped: BTreeMap<&T, Option<U>> = nodes.iter().map(|n| (n, node_f(n))).collect();
+        let mut graph = MultiDiGraph::<U, F>::new();
+        for n in nodes.iter() {
+            let Some(u) = &mapped[n] else {
+                continue;
+            };
+            graph.add_node(u.clone());
+            if let Some(a) = self.node_attrs(n) {
+                graph.attrs.nodes.insert(u.clone(), a.clone());
+            }
+        }
+        for n in nodes.iter() {
+            for (m, e) in self.get_neighbors(n.clone()) {
+                let (Some(from), Some(to)) = (&mapped[n], &mapped[&m]) else {
+                    continue;
+                };
+                let Some(edge) = edge_f(n, &m, &e) else {
+                    continue;
+                };
+                graph.add_edge(from.clone(), to.clone(), edge.clone());
+                if let Some(a) = self.edge_attrs(n, &m, &e) {
+                    graph
+                        .attrs
+                        .edges
+                        .insert((from.clone(), to.clone(), edge), a.clone());
+                }
+            }
+        }
+        graph
+    }
+}
+
+impl<T, E> MultiGraph<T, E>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    E: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a graph with the same structure where every node `n` is
+    /// replaced by `node_f(n)` and every edge value `e` by `edge_f(e)`, see
+    /// `MultiDiGraph::map`
+    pub fn map<U, F>(
+        &self,
+        mut node_f: impl FnMut(&T) -> U,
+        mut edge_f: impl FnMut(&E) -> F,
+    ) -> MultiGraph<U, F>
+    where
+        U: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+        F: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        self.filter_map(|n| Some(node_f(n)), |_, _, e| Some(edge_f(e)))
+    }
+
+    /// Same as `map` but dropping the nodes for which `node_f` returns
+    /// `None` and the edges `(a, b, edge)` for which `edge_f` does, see
+    /// `MultiDiGraph::filter_map`. `edge_f` is called once per edge, with
+    /// its nodes in either order
+    pub fn filter_map<U, F>(
+        &self,
+        node_f: impl FnMut(&T) -> Option<U>,
+        mut edge_f: impl FnMut(&T, &T, &E) -> Option<F>,
+    ) -> MultiGraph<U, F>
+    where
+        U: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+        F: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    {
+        let mut decided = BTreeMap::new();
+        MultiGraph {
+            multidigraph: self.multidigraph.filter_map(node_f, |a, b, e| {
+                decided
+                    .entry((undirected_key(a.clone(), b.clone()), e.clone()))
+                    .or_insert_with(|| edge_f(a, b, e))
+                    .clone()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::multidigraph::MultiDiGraph;
+    use crate::multigraph::MultiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::rugraph::IMultiDiGraph;
+    use crate::rugraph::IMultiGraph;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn map_graphs() {
+        let mut graph = DiGraph::<String>::new();
+        graph.extend_with_edges(
+            [("a", "b"), ("b", "c"), ("c", "a")].map(|(a, b)| (a.to_string(), b.to_string())),
+        );
+        graph.node_attrs_mut("b".to_string()).insert("color", "red");
+        graph
+            .edge_attrs_mut("c".to_string(), "a".to_string())
+            .insert("w", 3);
+
+        let mut interner = BTreeMap::new();
+        let ids = graph.map(|n| {
+            let next = interner.len() as u32;
+            *interner.entry(n.clone()).or_insert(next)
+        });
+        assert_eq!(ids.get_nodes(), vec![0, 1, 2]);
+        assert_eq!(ids.get_neighbors(2), vec![0]);
+        assert_eq!(ids.node_attrs(&1).unwrap().get_str("color"), Some("red"));
+        assert_eq!(ids.edge_attrs(&2, &0).unwrap().get_int("w"), Some(3));
+        assert_eq!(interner["c"], 2);
+
+        let merged = ids.map(|n| n % 2);
+        assert_eq!(merged.get_nodes(), vec![0, 1]);
+        assert_eq!(merged.get_neighbors(0), vec![1, 0]);
+
+        let mut graph = Graph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3)]);
+        graph.edge_attrs_mut(1, 2).insert("w", 1);
+        let negated = graph.map(|n| -n);
+        assert!(negated.is_directly_connected(-1, -2));
+        assert_eq!(negated.edge_attrs(&-2, &-1).unwrap().get_int("w"), Some(1));
+
+        let mut graph = MultiDiGraph::<i32, char>::new();
+        graph.extend_with_edges([(1, 2, 'a'), (1, 2, 'b')]);
+        graph.edge_attrs_mut(1, 2, 'b').insert("w", 2);
+        let upper = graph.map(|n| n * 10, |e| e.to_ascii_uppercase());
+        assert_eq!(upper.get_neighbors(10), vec![(20, 'A'), (20, 'B')]);
+        assert_eq!(
+            upper.edge_attrs(&10, &20, &'B').unwrap().get_int("w"),
+            Some(2)
+        );
+
+        let mut graph = MultiGraph::<i32, char>::new();
+        graph.extend_with_edges([(1, 2, 'a')]);
+        let codes = graph.map(|n| n.to_string(), |e| *e as u32);
+        assert!(codes.is_directly_connected_by("2".to_string(), "1".to_string(), 97));
+    }
+
+    #[test]
+    fn filter_map_graphs() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3), (3, 4), (4, 1), (1, 3)]);
+        graph.node_attrs_mut(3).insert("color", "red");
+        graph.edge_attrs_mut(1, 3).insert("w", 2);
+        let odd = graph.filter_map(|n| (n % 2 == 1).then(|| n.to_string()), |_, _| true);
+        assert_eq!(odd.get_nodes(), vec!["1".to_string(), "3".to_string()]);
+        assert_eq!(odd.get_neighbors("1".to_string()), vec!["3".to_string()]);
+        assert_eq!(
+            odd.edge_attrs(&"1".to_string(), &"3".to_string())
+                .unwrap()
+                .get_int("w"),
+            Some(2)
+        );
+        assert!(odd.node_attrs(&"3".to_string()).is_some());
+        let forward = graph.filter_map(|n| Some(*n), |a, b| a < b);
+        assert_eq!(forward.count_nodes(), 4);
+        assert!(!forward.is_connected(4, 1));
+
+        let mut graph = Graph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3)]);
+        let mut calls = 0;
+        let sub = graph.filter_map(
+            |n| Some(*n),
+            |a, b| {
+                calls += 1;
+                a.max(b) != &3
+            },
+        );
+        assert_eq!(calls, 2);
+        assert!(sub.is_directly_connected(2, 1));
+        assert!(!sub.is_directly_connected(3, 2));
+
+        let mut graph = MultiDiGraph::<i32, u32>::new();
+        graph.extend_with_edges([(1, 2, 2019), (1, 2, 2023), (2, 3, 2024)]);
+        let recent = graph.filter_map(
+            |n| (*n != 3).then_some(*n),
+            |_, _, y| (*y > 2020).then(|| y - 2000),
+        );
+        assert_eq!(recent.get_nodes(), vec![1, 2]);
+        assert_eq!(recent.get_neighbors(1), vec![(2, 23)]);
+
+        let mut graph = MultiGraph::<i32, char>::new();
+        graph.extend_with_edges([(1, 2, 'a'), (2, 3, 'b')]);
+        let sub = graph.filter_map(|n| Some(*n), |_, _, e| (*e == 'b').then_some('B'));
+        assert!(sub.is_directly_connected_by(3, 2, 'B'));
+        assert!(sub.get_neighbors(1).is_empty());
+    }
+
+    #[test]
+    fn quotient() {
+        let mut graph = DiGraph::<&str>::new();
+        graph.extend_with_edges([
+            ("net::connect", "io::read"),
+            ("net::send", "io::write"),
+            ("net::send", "net::connect"),
+            ("io::write", "io::flush"),
+            ("app::main", "net::send"),
+            ("app::main", "io::read"),
+        ]);
+        let module = |n: &&str| n.split("::").next().unwrap().to_string();
+        let modules = graph.quotient(module);
+        assert_eq!(modules.get_nodes(), vec!["net", "io", "app"]);
+        assert_eq!(modules.get_neighbors("net".to_string()), vec!["io"]);
+        assert_eq!(
+            modules.get_neighbors("io".to_string()),
+            Vec::<String>::new()
+        );
+        assert_eq!(modules.get_neighbors("app".to_string()), vec!["net", "io"]);
+        let count = |a: &str, b: &str| {
+            let edge = modules.edge_attrs(&a.to_string(), &b.to_string());
+            edge.and_then(|e| e.get_int("count"))
+        };
+        assert_eq!(count("net", "io"), Some(2));
+        assert_eq!(count("app", "io"), Some(1));
+
+        let mut graph = Graph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3), (3, 4), (4, 1), (1, 3)]);
+        let parity = graph.quotient(|n| n % 2);
+        assert_eq!(parity.get_nodes(), vec![1, 0]);
+        assert_eq!(parity.edge_attrs(&0, &1).unwrap().get_int("count"), Some(4));
+
+        let mut graph = MultiDiGraph::<i32, char>::new();
+        graph.extend_with_edges([(1, 2, 'a'), (1, 2, 'b'), (2, 1, 'c'), (1, 3, 'd')]);
+        let blocks = graph.quotient(|n| *n > 1);
+        assert_eq!(blocks.get_neighbors(false), vec![true]);
+        assert_eq!(
+            blocks.edge_attrs(&false, &true).unwrap().get_int("count"),
+            Some(3)
+        );
+        assert_eq!(
+            blocks.edge_attrs(&true, &false).unwrap().get_int("count"),
+            Some(1)
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+
+/// `Tree` is a `generic` rooted tree, or forest when it has several roots,
+/// stored as a `DiGraph` with edges from parents to children. Every node has
+/// at most one parent and there are no cycles. Each node of type `T` must
+/// implement: `T: Ord + Clone + std::fmt::Display + std::fmt::Debug`
+pub struct Tree<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    digraph: DiGraph<T>,
+    parents: BTreeMap<T, T>,
+}
+
+impl<T> Tree<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        Tree::<T> {
+            digraph: DiGraph::<T>::new(),
+            parents: BTreeMap::new(),
+        }
+    }
+
+    /// Adds `child` under `parent`. `child` is added if needed; if it
+    /// already exists it must be a root and not an ancestor of `parent`.
+    /// Returns an error if `parent` doesn't exist or the tree invariants
+    /// would be broken
+    pub fn add_child(&mut self, parent: T, child: T) -> Result<(), &'static str> {
+        if !self.digraph.node_exists(parent.clone()) {
+            return Err("Parent not found");
+        }
+        if self.parents.contains_key(&child) {
+            return Err("Node already has a parent");
+        }
+        if child == parent || self.ancestors(&parent).contains(&child) {
+            return Err("Edge would create a cycle");
+        }
+        self.digraph.add_node(child.clone());
+        self.digraph.add_edge(parent.clone(), child.clone());
+        self.parents.insert(child, parent);
+        Ok(())
+    }
+
+    /// Returns the parent of `node`, `None` for roots and unknown nodes
+    pub fn parent(&self, node: &T) -> Option<T> {
+        self.parents.get(node).cloned()
+    }
+
+    /// Returns the children of `node` in insertion order
+    pub fn children(&self, node: &T) -> Vec<T> {
+        self.digraph.get_neighbors(node.clone())
+    }
+
+    /// Returns the nodes without parent
+    pub fn roots(&self) -> Vec<T> {
+        self.digraph
+            .get_nodes()
+            .into_iter()
+            .filter(|n| !self.parents.contains_key(n))
+            .collect()
+    }
+
+    /// Returns if `node` has no children
+    pub fn is_leaf(&self, node: &T) -> bool {
+        self.children(node).is_empty()
+    }
+
+    /// Returns the ancestors of `node` from its parent up to its root
+    pub fn ancestors(&self, node: &T) -> Vec<T> {
+        let mut ret = Vec::<T>::new();
+        let mut current = node;
+        while let Some(p) = self.parents.get(current) {
+            ret.push(p.clone());
+            current = p;
+        }
+        ret
+    }
+
+    /// Returns the descendants of `node` in depth first preorder
+    pub fn descendants(&self, node: &T) -> Vec<T> {
+        let mut ret = Vec::<T>::new();
+        let mut to_process: Vec<T> = self.children(node).into_iter().rev().collect();
+        while let Some(n) = to_process.pop() {
+            to_process.extend(self.children(&n).into_iter().rev());
+            ret.push(n);
+        }
+        ret
+    }
+
+    /// Returns a copy of the subtree rooted at `node`, `None` if the node
+    /// doesn't exist
+    pub fn subtree(&self, node: &T) -> Option<Tree<T>> {
+        if !self.digraph.node_exists(node.clone()) {
+            return None;
+        }
+        let mut tree = Tree::<T>::new();
+        tree.add_node(node.clone());
+        for n in self.descendants(node) {
+            let p = self.parents[&n].clone();
+            tree.add_child(p, n).unwrap();
+        }
+        Some(tree)
+    }
+
+    /// Returns the number of edges from the root of `node` to `node`, `None`
+    /// if the node doesn't exist
+    pub fn depth(&self, node: &T) -> Option<usize> {
+        if !self.digraph.node_exists(node.clone()) {
+            return None;
+        }
+        Some(self.ancestors(node).len())
+    }
+
+    /// Returns the largest depth of a node, 0 for an empty tree
+    pub fn height(&self) -> usize {
+        self.digraph
+            .get_nodes()
+            .iter()
+            .map(|n| self.ancestors(n).len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the underlying directed graph, to run the algorithms of
+    /// `DiGraph`
+    pub fn as_digraph(&self) -> &DiGraph<T> {
+        &self.digraph
+    }
+}
+
+impl<T> Default for Tree<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> IGraph<T> for Tree<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Adds `elem` as a new root
+    fn add_node(&mut self, elem: T) {
+        self.digraph.add_node(elem);
+    }
+
+    fn node_exists(&self, node: T) -> bool {
+        self.digraph.node_exists(node)
+    }
+
+    /// Returns if `to` is `from` or one of its descendants
+    fn is_connected(&self, from: T, to: T) -> bool {
+        self.digraph.node_exists(from.clone())
+            && (from == to || self.ancestors(&to).contains(&from))
+    }
+
+    fn is_directly_connected(&self, from: T, to: T) -> bool {
+        self.parents.get(&to) == Some(&from)
+    }
+
+    fn to_dot_string(&self, graph_name: &str) -> String {
+        self.digraph.to_dot_string(graph_name)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.digraph.is_empty()
+    }
+
+    fn count_nodes(&self) -> usize {
+        self.digraph.count_nodes()
+    }
+
+    fn get_nodes(&self) -> Vec<T> {
+        self.digraph.get_nodes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tree;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn tree_queries() {
+        //     1       7
+        //    / \
+        //   2   3
+        //  / \
+        // 4   5
+        let mut tree = Tree::<i32>::new();
+        tree.add_node(1);
+        tree.add_node(7);
+        tree.add_child(1, 2).unwrap();
+        tree.add_child(1, 3).unwrap();
+        tree.add_child(2, 4).unwrap();
+        tree.add_child(2, 5).unwrap();
+
+        assert!(tree.add_child(3, 4).is_err());
+        assert!(tree.add_child(9, 8).is_err());
+        assert!(tree.add_child(4, 1).is_err());
+        assert!(tree.add_child(4, 4).is_err());
+
+        assert_eq!(tree.roots(), vec![1, 7]);
+        assert_eq!(tree.parent(&4), Some(2));
+        assert_eq!(tree.parent(&1), None);
+        assert_eq!(tree.children(&2), vec![4, 5]);
+        assert_eq!(tree.ancestors(&5), vec![2, 1]);
+        assert_eq!(tree.descendants(&1), vec![2, 4, 5, 3]);
+        assert_eq!(tree.depth(&5), Some(2));
+        assert_eq!(tree.depth(&8), None);
+        assert_eq!(tree.height(), 2);
+        assert!(tree.is_leaf(&3));
+        assert!(tree.is_connected(1, 5));
+        assert!(!tree.is_connected(5, 1));
+        assert!(tree.is_directly_connected(2, 5));
+
+        let sub = tree.subtree(&2).unwrap();
+        assert_eq!(sub.roots(), vec![2]);
+        assert_eq!(sub.get_nodes(), vec![2, 4, 5]);
+        assert_eq!(sub.height(), 1);
+
+        // A root can be attached under another tree
+        tree.add_child(3, 7).unwrap();
+        assert_eq!(tree.roots(), vec![1]);
+        assert_eq!(tree.depth(&7), Some(2));
+    }
+}
@@ -0,0 +1,137 @@
+use std::vec::Vec;
+
+use crate::graph::Graph;
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns an approximate shortest tour visiting every node exactly once
+    /// and going back to the start, e.g. `[a, c, b, a]`. `weight(a, b)` gives
+    /// the cost of the edge between `a` and `b`.
+    ///
+    /// The tour is built with the nearest neighbor heuristic from the first
+    /// node and improved with 2-opt moves. Only existing edges are used, so it
+    /// works best on complete graphs. Returns an error if no tour is found
+    pub fn traveling_salesman(
+        &self,
+        weight: impl Fn(&T, &T) -> f64,
+    ) -> Result<Vec<T>, &'static str> {
+        let adj = self.adjacency();
+        let n = adj.len();
+        if n <= 1 {
+            return Ok(adj.nodes);
+        }
+
+        let mut connected = vec![vec![false; n]; n];
+        for (i, s) in adj.succ.iter().enumerate() {
+            for j in s.iter() {
+                connected[i][*j] = true;
+            }
+        }
+        let w = |a: usize, b: usize| weight(&adj.nodes[a], &adj.nodes[b]);
+
+        // Nearest neighbor
+        let mut tour = vec![0usize];
+        let mut visited = vec![false; n];
+        visited[0] = true;
+        while tour.len() < n {
+            let last = *tour.last().unwrap();
+            let mut next: Option<(usize, f64)> = None;
+            for j in adj.succ[last].iter() {
+                if visited[*j] {
+                    continue;
+                }
+                let c = w(last, *j);
+                if next.is_none_or(|(_, best)| c < best) {
+                    next = Some((*j, c));
+                }
+            }
+            match next {
+                None => return Err("No tour found"),
+                Some((j, _)) => {
+                    visited[j] = true;
+                    tour.push(j);
+                }
+            }
+        }
+        if !connected[tour[n - 1]][tour[0]] {
+            return Err("No tour found");
+        }
+
+        // 2-opt: replace edges (a, b) and (c, d) by (a, c) and (b, d)
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for i in 0..n - 1 {
+                for j in i + 2..n {
+                    if i == 0 && j == n - 1 {
+                        continue;
+                    }
+                    let (a, b) = (tour[i], tour[i + 1]);
+                    let (c, d) = (tour[j], tour[(j + 1) % n]);
+                    if !connected[a][c] || !connected[b][d] {
+                        continue;
+                    }
+                    let delta = w(a, c) + w(b, d) - w(a, b) - w(c, d);
+                    if delta < -1e-12 {
+                        tour[i + 1..=j].reverse();
+                        improved = true;
+                    }
+                }
+            }
+        }
+
+        let mut ret: Vec<T> = tour.iter().map(|i| adj.nodes[*i].clone()).collect();
+        ret.push(adj.nodes[tour[0]].clone());
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    // Corners of a square, inserted in a crossing order
+    const POINTS: [(f64, f64); 4] = [(0.0, 0.0), (1.0, 1.0), (1.0, 0.0), (0.0, 1.0)];
+
+    fn distance(a: &usize, b: &usize) -> f64 {
+        let dx = POINTS[*a].0 - POINTS[*b].0;
+        let dy = POINTS[*a].1 - POINTS[*b].1;
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    #[test]
+    fn tsp_square() {
+        let mut graph = Graph::<usize>::new();
+        for a in 0..POINTS.len() {
+            graph.add_node(a);
+        }
+        for a in 0..POINTS.len() {
+            for b in 0..POINTS.len() {
+                if a != b {
+                    graph.add_edge(a, b);
+                }
+            }
+        }
+
+        let tour = graph.traveling_salesman(distance).unwrap();
+        assert_eq!(tour.len(), 5);
+        assert_eq!(tour.first(), tour.last());
+        let length: f64 = tour.windows(2).map(|p| distance(&p[0], &p[1])).sum();
+        assert!((length - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tsp_no_tour() {
+        let mut graph = Graph::<i32>::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_node(3);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        assert!(graph.traveling_salesman(|_, _| 1.0).is_err());
+    }
+}
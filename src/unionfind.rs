@@ -0,0 +1,163 @@
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+
+/// Companion structure of a graph that keeps its connected components up to
+/// date as edges are added (union-find with union by size and path
+/// compression), so `same_component` doesn't need a graph traversal.
+/// Edge direction is ignored. Edges can't be removed
+pub struct DynamicConnectivity<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    index: BTreeMap<T, usize>,
+    parent: Vec<Cell<usize>>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl<T> DynamicConnectivity<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        DynamicConnectivity::<T> {
+            index: BTreeMap::new(),
+            parent: Vec::new(),
+            size: Vec::new(),
+            components: 0,
+        }
+    }
+
+    /// Returns the structure for the nodes and edges already in `graph`
+    pub fn from_graph<G>(graph: &G) -> Self
+    where
+        G: IGraph<T> + IDiGraph<T>,
+    {
+        let mut ret = Self::new();
+        for n in graph.get_nodes() {
+            ret.add_node(n.clone());
+            for m in graph.get_neighbors(n.clone()) {
+                ret.add_edge(n.clone(), m);
+            }
+        }
+        ret
+    }
+
+    /// Adds a new node `elem` in a component of its own
+    pub fn add_node(&mut self, elem: T) {
+        if !self.index.contains_key(&elem) {
+            let i = self.parent.len();
+            self.index.insert(elem, i);
+            self.parent.push(Cell::new(i));
+            self.size.push(1);
+            self.components += 1;
+        }
+    }
+
+    /// Joins the components of `a` and `b`, adding the nodes if they are new
+    pub fn add_edge(&mut self, a: T, b: T) {
+        self.add_node(a.clone());
+        self.add_node(b.clone());
+        let ra = self.find(self.index[&a]);
+        let rb = self.find(self.index[&b]);
+        if ra == rb {
+            return;
+        }
+        let (big, small) = if self.size[ra] >= self.size[rb] {
+            (ra, rb)
+        } else {
+            (rb, ra)
+        };
+        self.parent[small].set(big);
+        self.size[big] += self.size[small];
+        self.components -= 1;
+    }
+
+    /// Returns if `a` and `b` are in the same component. Unknown nodes are
+    /// in no component
+    pub fn same_component(&self, a: &T, b: &T) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(i), Some(j)) => self.find(*i) == self.find(*j),
+            _ => false,
+        }
+    }
+
+    /// Returns how many nodes are in the component of `node`, 0 if unknown
+    pub fn component_size(&self, node: &T) -> usize {
+        self.index.get(node).map_or(0, |i| self.size[self.find(*i)])
+    }
+
+    /// Returns how many components there are
+    pub fn count_components(&self) -> usize {
+        self.components
+    }
+
+    /// Returns how many nodes there are
+    pub fn count_nodes(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Returns the representative of the component of node index `i`,
+    /// halving the path on the way
+    fn find(&self, mut i: usize) -> usize {
+        while self.parent[i].get() != i {
+            let grandparent = self.parent[self.parent[i].get()].get();
+            self.parent[i].set(grandparent);
+            i = grandparent;
+        }
+        i
+    }
+}
+
+impl<T> Default for DynamicConnectivity<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DynamicConnectivity;
+    use crate::digraph::DiGraph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn dynamic_connectivity() {
+        let mut dc = DynamicConnectivity::<i32>::new();
+        for n in 1..=6 {
+            dc.add_node(n);
+        }
+        assert_eq!(dc.count_components(), 6);
+        dc.add_edge(1, 2);
+        dc.add_edge(3, 4);
+        dc.add_edge(2, 3);
+        dc.add_edge(4, 1);
+        assert!(dc.same_component(&1, &4));
+        assert!(!dc.same_component(&1, &5));
+        assert!(!dc.same_component(&1, &9));
+        assert_eq!(dc.count_components(), 3);
+        assert_eq!(dc.component_size(&3), 4);
+        dc.add_edge(6, 7);
+        assert_eq!(dc.count_nodes(), 7);
+        assert!(dc.same_component(&7, &6));
+
+        let mut graph = DiGraph::<i32>::new();
+        for n in 1..=4 {
+            graph.add_node(n);
+        }
+        graph.add_edge(2, 1);
+        graph.add_edge(3, 4);
+        let dc = DynamicConnectivity::from_graph(&graph);
+        assert!(dc.same_component(&1, &2));
+        assert!(!dc.same_component(&1, &3));
+        assert_eq!(dc.count_components(), 2);
+    }
+}
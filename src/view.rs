@@ -0,0 +1,294 @@
+use std::collections::BTreeSet;
+use std::vec::Vec;
+
+use crate::digraph::DiGraph;
+use crate::graph::Graph;
+use crate::rugraph::simple_paths_dfs;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+use crate::rugraph::LimitedPaths;
+use crate::rugraph::PathBounds;
+
+type NodePred<'a, T> = Box<dyn Fn(&T) -> bool + 'a>;
+type EdgePred<'a, T> = Box<dyn Fn(&T, &T) -> bool + 'a>;
+
+/// Read-only view of a graph `G` that hides the nodes rejected by a node
+/// predicate and the edges rejected by an edge predicate, without copying
+/// the graph. Edges touching a hidden node are hidden too. Created by
+/// `filter`, it implements `IGraph` and `IDiGraph` so it can be passed to
+/// any algorithm taking a graph; adding nodes or edges does nothing
+pub struct GraphView<'a, T, G>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    G: IGraph<T> + IDiGraph<T>,
+{
+    graph: &'a G,
+    node_pred: NodePred<'a, T>,
+    edge_pred: EdgePred<'a, T>,
+}
+
+impl<'a, T, G> GraphView<'a, T, G>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    G: IGraph<T> + IDiGraph<T>,
+{
+    /// Returns a view of `graph` keeping the nodes accepted by `node_pred`
+    /// and the edges `(from, to)` accepted by `edge_pred`
+    pub fn new(
+        graph: &'a G,
+        node_pred: impl Fn(&T) -> bool + 'a,
+        edge_pred: impl Fn(&T, &T) -> bool + 'a,
+    ) -> Self {
+        GraphView {
+            graph,
+            node_pred: Box::new(node_pred),
+            edge_pred: Box::new(edge_pred),
+        }
+    }
+
+    /// Returns a new `DiGraph` with the visible nodes and edges
+    pub fn to_digraph(&self) -> DiGraph<T> {
+        let mut graph = DiGraph::<T>::new();
+        let nodes = self.get_nodes();
+        for n in nodes.iter() {
+            graph.add_node(n.clone());
+        }
+        for n in nodes.iter() {
+            for m in self.get_neighbors(n.clone()) {
+                graph.add_edge(n.clone(), m);
+            }
+        }
+        graph
+    }
+}
+
+impl<'a, T, G> IGraph<T> for GraphView<'a, T, G>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    G: IGraph<T> + IDiGraph<T>,
+{
+    /// Views are read-only, does nothing
+    fn add_node(&mut self, _elem: T) {}
+
+    /// Returns `true` if node `node` exists and is visible
+    fn node_exists(&self, node: T) -> bool {
+        (self.node_pred)(&node) && self.graph.node_exists(node)
+    }
+
+    /// Returns if a node `from` is connected to a node `to` through visible
+    /// nodes and edges. Only the nodes reached are visited
+    fn is_connected(&self, from: T, to: T) -> bool {
+        if !self.node_exists(from.clone()) || !self.node_exists(to.clone()) {
+            return false;
+        }
+        let mut visited = BTreeSet::<T>::new();
+        let mut to_process = vec![from];
+        while let Some(n) = to_process.pop() {
+            for m in self.get_neighbors(n) {
+                if m == to {
+                    return true;
+                }
+                if visited.insert(m.clone()) {
+                    to_process.push(m);
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns if node `to` is a visible neighbor of `from`
+    fn is_directly_connected(&self, from: T, to: T) -> bool {
+        (self.node_pred)(&from)
+            && (self.node_pred)(&to)
+            && (self.edge_pred)(&from, &to)
+            && self.graph.is_directly_connected(from, to)
+    }
+
+    /// Returns the dot representation of the visible nodes and edges
+    fn to_dot_string(&self, graph_name: &str) -> String {
+        self.to_digraph().to_dot_string(graph_name)
+    }
+
+    /// Returns if no node is visible
+    fn is_empty(&self) -> bool {
+        self.count_nodes() == 0
+    }
+
+    /// Returns how many nodes are visible
+    fn count_nodes(&self) -> usize {
+        self.graph
+            .get_nodes()
+            .iter()
+            .filter(|n| (self.node_pred)(n))
+            .count()
+    }
+
+    /// Returns a vector of the visible elements
+    fn get_nodes(&self) -> Vec<T> {
+        self.graph
+            .get_nodes()
+            .into_iter()
+            .filter(|n| (self.node_pred)(n))
+            .collect()
+    }
+}
+
+impl<'a, T, G> IDiGraph<T> for GraphView<'a, T, G>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    G: IGraph<T> + IDiGraph<T>,
+{
+    /// Views are read-only, does nothing
+    fn add_edge(&mut self, _from: T, _to: T) {}
+
+    /// Returns a `Vec<Vec<T>>` containing all the simple paths from node
+    /// `from` to node `to` through visible nodes and edges
+    fn all_simple_paths(&self, from: T, to: T) -> Vec<Vec<T>> {
+        self.all_simple_paths_with_cutoff(from, to, usize::MAX)
+    }
+
+    /// Returns a vector containing the visible `neighbors` of node `from`
+    fn get_neighbors(&self, from: T) -> Vec<T> {
+        if !(self.node_pred)(&from) {
+            return Vec::new();
+        }
+        self.graph
+            .get_neighbors(from.clone())
+            .into_iter()
+            .filter(|n| (self.node_pred)(n) && (self.edge_pred)(&from, n))
+            .collect()
+    }
+
+    /// Same as `all_simple_paths_with_cutoff` but stops after finding
+    /// `limit` paths. Returns the paths and `true` if there were more
+    fn all_simple_paths_limited(
+        &self,
+        from: T,
+        to: T,
+        max_len: usize,
+        limit: usize,
+    ) -> LimitedPaths<T> {
+        let mut ret = Vec::<Vec<T>>::new();
+        if !self.node_exists(from.clone()) || !self.node_exists(to.clone()) {
+            return (ret, false);
+        }
+        let bounds = PathBounds { max_len, limit };
+        let mut current_path = vec![from];
+        simple_paths_dfs(&to, &bounds, &mut ret, &mut current_path, &|n| {
+            self.get_neighbors(n)
+        });
+        bounds.truncate(ret)
+    }
+}
+
+impl<T> DiGraph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a read-only view of the graph with the nodes accepted by
+    /// `node_pred` and the edges `(from, to)` accepted by `edge_pred`
+    pub fn filter<'a>(
+        &'a self,
+        node_pred: impl Fn(&T) -> bool + 'a,
+        edge_pred: impl Fn(&T, &T) -> bool + 'a,
+    ) -> GraphView<'a, T, DiGraph<T>> {
+        GraphView::new(self, node_pred, edge_pred)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+{
+    /// Returns a read-only view of the graph with the nodes accepted by
+    /// `node_pred` and the edges accepted by `edge_pred`. The predicate is
+    /// asked for both directions of an edge, so it should be symmetric
+    pub fn filter<'a>(
+        &'a self,
+        node_pred: impl Fn(&T) -> bool + 'a,
+        edge_pred: impl Fn(&T, &T) -> bool + 'a,
+    ) -> GraphView<'a, T, Graph<T>> {
+        GraphView::new(self, node_pred, edge_pred)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digraph::DiGraph;
+    use crate::graph::Graph;
+    use crate::rugraph::IDiGraph;
+    use crate::rugraph::IGraph;
+    use crate::unionfind::DynamicConnectivity;
+
+    #[test]
+    fn filtered_view() {
+        let mut graph = DiGraph::<i32>::new();
+        for n in 1..=5 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(1, 4);
+        graph.add_edge(4, 3);
+        graph.add_edge(3, 5);
+
+        let disabled = [(1, 2)];
+        let view = graph.filter(|n| *n != 5, |a, b| !disabled.contains(&(*a, *b)));
+        assert_eq!(view.count_nodes(), 4);
+        assert!(!view.node_exists(5));
+        assert_eq!(view.get_neighbors(1), vec![4]);
+        assert_eq!(view.get_neighbors(3), Vec::<i32>::new());
+        assert!(!view.is_directly_connected(1, 2));
+        assert!(view.is_connected(1, 3));
+        assert!(!view.is_connected(1, 5));
+        assert!(!view.is_connected(1, 1));
+        assert_eq!(view.all_simple_paths(1, 3), vec![vec![1, 4, 3]]);
+        assert_eq!(graph.all_simple_paths(1, 3).len(), 2);
+
+        let copy = view.to_digraph();
+        assert_eq!(copy.get_nodes(), vec![1, 2, 3, 4]);
+        assert_eq!(copy.get_neighbors(2), vec![3]);
+
+        let mut graph = Graph::<i32>::new();
+        for n in 1..=3 {
+            graph.add_node(n);
+        }
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        let view = graph.filter(|_| true, |a, b| (*a).min(*b) != 1);
+        assert!(!view.is_connected(3, 1));
+        assert!(view.is_connected(3, 2));
+        assert!(!view.is_empty());
+    }
+
+    #[test]
+    fn view_as_graph() {
+        let mut graph = DiGraph::<i32>::new();
+        graph.extend_with_edges([(1, 2), (2, 3), (3, 1), (4, 5)]);
+        let mut view = graph.filter(|n| *n != 3, |a, b| (*a, *b) != (4, 5));
+
+        let components = DynamicConnectivity::from_graph(&view);
+        assert_eq!(components.count_components(), 3);
+        assert!(components.same_component(&1, &2));
+        assert!(!components.same_component(&4, &5));
+
+        assert!(view.is_directly_connected(1, 2));
+        assert!(!view.is_directly_connected(2, 3));
+        assert!(!view.is_connected(2, 1));
+        assert!(!view.is_connected(1, 3));
+        assert_eq!(
+            view.all_simple_paths_limited(1, 2, 5, 5),
+            (vec![vec![1, 2]], false)
+        );
+        assert!(view.all_simple_paths(3, 3).is_empty());
+        assert_eq!(
+            view.to_dot_string("v"),
+            view.to_digraph().to_dot_string("v")
+        );
+
+        view.add_node(6);
+        view.add_edge(2, 1);
+        assert!(!view.node_exists(6));
+        assert!(!view.is_connected(2, 1));
+    }
+}
@@ -0,0 +1,83 @@
+use std::vec::Vec;
+
+const WORD: usize = 64;
+
+/// Bitset of visited node indexes used by the traversals. It can be reused
+/// between traversals: `reset` clears it without freeing its memory, so hot
+/// query loops don't allocate
+#[derive(Debug, Clone, Default)]
+pub struct VisitMap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl VisitMap {
+    /// Returns an empty map for node indexes below `len`
+    pub fn new(len: usize) -> Self {
+        VisitMap {
+            words: vec![0; len.div_ceil(WORD)],
+            len,
+        }
+    }
+
+    /// Unmarks every node and makes room for node indexes below `len`
+    pub fn reset(&mut self, len: usize) {
+        self.words.clear();
+        self.words.resize(len.div_ceil(WORD), 0);
+        self.len = len;
+    }
+
+    /// Returns how many node indexes the map holds
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns if the map holds no node indexes
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Marks node `i` as visited, returning `true` if it wasn't
+    pub fn visit(&mut self, i: usize) -> bool {
+        let bit = 1 << (i % WORD);
+        let new = self.words[i / WORD] & bit == 0;
+        self.words[i / WORD] |= bit;
+        new
+    }
+
+    /// Unmarks node `i`
+    pub fn unvisit(&mut self, i: usize) {
+        self.words[i / WORD] &= !(1 << (i % WORD));
+    }
+
+    /// Returns if node `i` was visited
+    pub fn is_visited(&self, i: usize) -> bool {
+        self.words[i / WORD] & (1 << (i % WORD)) != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VisitMap;
+
+    #[test]
+    fn visit_map() {
+        let mut visited = VisitMap::new(130);
+        assert_eq!(visited.len(), 130);
+        assert!(visited.visit(0));
+        assert!(visited.visit(129));
+        assert!(!visited.visit(129));
+        assert!(visited.is_visited(0));
+        assert!(!visited.is_visited(64));
+        visited.unvisit(0);
+        assert!(!visited.is_visited(0));
+        assert!(visited.is_visited(129));
+
+        visited.reset(10);
+        assert!(!visited.is_visited(9));
+        visited.reset(200);
+        assert!(!visited.is_visited(129));
+        assert!(visited.visit(199));
+        assert!(VisitMap::default().is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+use std::vec::Vec;
+use wasm_bindgen::prelude::*;
+
+use crate::digraph::DiGraph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+
+/// `JsDiGraph` exposes a `DiGraph<String>` to JavaScript, so browser tools
+/// run the same graph logic as native code. Methods use camelCase names on
+/// the JavaScript side
+#[wasm_bindgen]
+pub struct JsDiGraph {
+    graph: DiGraph<String>,
+}
+
+#[wasm_bindgen]
+impl JsDiGraph {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        JsDiGraph {
+            graph: DiGraph::new(),
+        }
+    }
+
+    /// Adds a new node `elem`
+    #[wasm_bindgen(js_name = addNode)]
+    pub fn add_node(&mut self, elem: String) {
+        self.graph.add_node(elem);
+    }
+
+    /// Creates a new edge from node `from` to node `to`, both must be
+    /// previously added
+    #[wasm_bindgen(js_name = addEdge)]
+    pub fn add_edge(&mut self, from: String, to: String) {
+        self.graph.add_edge(from, to);
+    }
+
+    /// Returns `true` if node `node` exists
+    #[wasm_bindgen(js_name = nodeExists)]
+    pub fn node_exists(&self, node: String) -> bool {
+        self.graph.node_exists(node)
+    }
+
+    /// Returns how many nodes are in the graph
+    #[wasm_bindgen(js_name = countNodes)]
+    pub fn count_nodes(&self) -> usize {
+        self.graph.count_nodes()
+    }
+
+    /// Returns the nodes of the graph
+    #[wasm_bindgen(js_name = getNodes)]
+    pub fn get_nodes(&self) -> Vec<String> {
+        self.graph.get_nodes()
+    }
+
+    /// Returns the neighbors of node `from`
+    #[wasm_bindgen(js_name = getNeighbors)]
+    pub fn get_neighbors(&self, from: String) -> Vec<String> {
+        self.graph.get_neighbors(from)
+    }
+
+    /// Returns a path with the fewest edges from node `from` to node `to`,
+    /// both included. Empty if `to` can't be reached
+    #[wasm_bindgen(js_name = shortestPath)]
+    pub fn shortest_path(&self, from: String, to: String) -> Vec<String> {
+        self.graph.shortest_path(from, to).unwrap_or_default()
+    }
+
+    /// Returns the DOT representation of the graph
+    #[wasm_bindgen(js_name = toDot)]
+    pub fn to_dot(&self, graph_name: &str) -> String {
+        self.graph.to_dot_string(graph_name)
+    }
+
+    /// Returns the node-link JSON representation of the graph
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        self.graph.to_json_string()
+    }
+}
+
+impl Default for JsDiGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsDiGraph;
+
+    #[test]
+    fn js_digraph() {
+        let mut graph = JsDiGraph::new();
+        for n in ["a", "b", "c", "d", "e"] {
+            graph.add_node(n.to_string());
+        }
+        graph.add_edge("a".to_string(), "b".to_string());
+        graph.add_edge("b".to_string(), "c".to_string());
+        graph.add_edge("c".to_string(), "d".to_string());
+        graph.add_edge("a".to_string(), "c".to_string());
+        assert_eq!(graph.count_nodes(), 5);
+        assert!(graph.node_exists("e".to_string()));
+        assert_eq!(graph.get_neighbors("a".to_string()), vec!["b", "c"]);
+        assert_eq!(
+            graph.shortest_path("a".to_string(), "d".to_string()),
+            vec!["a", "c", "d"]
+        );
+        assert_eq!(
+            graph.shortest_path("a".to_string(), "a".to_string()),
+            vec!["a"]
+        );
+        assert!(graph
+            .shortest_path("d".to_string(), "a".to_string())
+            .is_empty());
+        assert!(graph
+            .shortest_path("a".to_string(), "z".to_string())
+            .is_empty());
+        assert!(graph.to_dot("g").contains("a -> c;"));
+        assert!(graph.to_json().contains("\"directed\":true"));
+    }
+}
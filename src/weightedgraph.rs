@@ -0,0 +1,278 @@
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::str::FromStr;
+use std::vec::Vec;
+
+use crate::dot::attr;
+use crate::dot::parse_dot_reader;
+use crate::dot::DotStatement;
+use crate::dot::DotStyle;
+use crate::graph::Graph;
+use crate::rugraph::IDiGraph;
+use crate::rugraph::IGraph;
+
+/// `WeightedGraph` is a `generic` undirected graph with a numeric weight `W`
+/// on every edge, where each node of type `T` must implement:
+/// `T: Ord + Clone + std::fmt::Display + std::fmt::Debug`
+pub struct WeightedGraph<T, W>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    W: Copy + PartialOrd + std::fmt::Display + std::fmt::Debug,
+{
+    graph: Graph<T>,
+    /// Keyed by the ordered pair of nodes
+    weights: BTreeMap<(T, T), W>,
+}
+
+impl<T, W> WeightedGraph<T, W>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    W: Copy + PartialOrd + std::fmt::Display + std::fmt::Debug,
+{
+    pub fn new() -> Self {
+        WeightedGraph::<T, W> {
+            graph: Graph::<T>::new(),
+            weights: BTreeMap::new(),
+        }
+    }
+
+    fn key(a: T, b: T) -> (T, T) {
+        if a <= b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+
+    /// Creates a new edge between nodes `a` and `b` with weight `weight`, or
+    /// updates its weight if it already exists.
+    /// Nodes `a` and `b` must be previously added to the graph
+    pub fn add_edge(&mut self, a: T, b: T, weight: W) {
+        if !self.graph.node_exists(a.clone()) || !self.graph.node_exists(b.clone()) {
+            return;
+        }
+        self.graph.add_edge(a.clone(), b.clone());
+        self.weights.insert(Self::key(a, b), weight);
+    }
+
+    /// Changes the weight of the edge between `a` and `b`. Returns an error if
+    /// there is no such edge
+    pub fn set_weight(&mut self, a: T, b: T, weight: W) -> Result<(), &'static str> {
+        match self.weights.get_mut(&Self::key(a, b)) {
+            Some(w) => {
+                *w = weight;
+                Ok(())
+            }
+            None => Err("Edge not found"),
+        }
+    }
+
+    /// Returns the weight of the edge between `a` and `b`, `None` if there is
+    /// no such edge
+    pub fn get_weight(&self, a: T, b: T) -> Option<W> {
+        self.weights.get(&Self::key(a, b)).copied()
+    }
+
+    /// Returns the neighbors of node `from` with the weight of the edge
+    /// joining them
+    pub fn get_neighbors(&self, from: T) -> Vec<(T, W)> {
+        self.graph
+            .get_neighbors(from.clone())
+            .into_iter()
+            .map(|m| {
+                let w = self.weights[&Self::key(from.clone(), m.clone())];
+                (m, w)
+            })
+            .collect()
+    }
+
+    /// Returns every edge once as `(a, b, weight)` with `a <= b`
+    pub fn get_edges(&self) -> Vec<(T, T, W)> {
+        self.weights
+            .iter()
+            .map(|((a, b), w)| (a.clone(), b.clone(), *w))
+            .collect()
+    }
+
+    /// Returns the unweighted graph, to run the algorithms that ignore weights
+    pub fn as_graph(&self) -> &Graph<T> {
+        &self.graph
+    }
+
+    /// Returns an approximate shortest tour using the edge weights as costs,
+    /// see `Graph::traveling_salesman`
+    pub fn traveling_salesman(&self) -> Result<Vec<T>, &'static str>
+    where
+        W: Into<f64>,
+    {
+        self.graph
+            .traveling_salesman(|a, b| self.weights[&Self::key(a.clone(), b.clone())].into())
+    }
+}
+
+impl<T, W> Default for WeightedGraph<T, W>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    W: Copy + PartialOrd + std::fmt::Display + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, W> IGraph<T> for WeightedGraph<T, W>
+where
+    T: Ord + Clone + std::fmt::Display + std::fmt::Debug,
+    W: Copy + PartialOrd + std::fmt::Display + std::fmt::Debug,
+{
+    fn add_node(&mut self, elem: T) {
+        self.graph.add_node(elem);
+    }
+
+    fn node_exists(&self, node: T) -> bool {
+        self.graph.node_exists(node)
+    }
+
+    fn is_connected(&self, from: T, to: T) -> bool {
+        self.graph.is_connected(from, to)
+    }
+
+    fn is_directly_connected(&self, from: T, to: T) -> bool {
+        self.graph.is_directly_connected(from, to)
+    }
+
+    /// Returns a dot representation of the graph with a statement for every
+    /// node and every edge (written once with its `weight` attribute), so it
+    /// parses back into an equal graph
+    fn to_dot_string(&self, graph_name: &str) -> String {
+        self.to_dot_string_with(graph_name, &DotStyle::new())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.graph.is_empty()
+    }
+
+    fn count_nodes(&self) -> usize {
+        self.graph.count_nodes()
+    }
+
+    fn get_nodes(&self) -> Vec<T> {
+        self.graph.get_nodes()
+    }
+}
+
+/// Returns a weighted string graph `WeightedGraph<String, W>` from a dot
+/// file content. The `weight` attribute of every edge is parsed as `W`
+pub fn weightedgraph_from_dot_string<W>(
+    content: &String,
+) -> Result<WeightedGraph<String, W>, &'static str>
+where
+    W: Copy + PartialOrd + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    weightedgraph_from_dot_reader(content.as_bytes())
+}
+
+/// Same as `weightedgraph_from_dot_string` reading the dot file
+/// incrementally from `reader`, so big files don't need to be loaded in memory
+pub fn weightedgraph_from_dot_reader<W>(
+    reader: impl BufRead,
+) -> Result<WeightedGraph<String, W>, &'static str>
+where
+    W: Copy + PartialOrd + std::fmt::Display + std::fmt::Debug + FromStr,
+{
+    let mut graph = WeightedGraph::<String, W>::new();
+    let mut error = None;
+    let header = parse_dot_reader(reader, |stmt| match stmt {
+        DotStatement::Node(id) => graph.add_node(id),
+        DotStatement::Edge(a, b, attrs) => match attr(&attrs, "weight").map(|w| w.parse()) {
+            Some(Ok(w)) => graph.add_edge(a, b, w),
+            Some(Err(_)) => error = Some("Dot file not correct. Weight can't be parsed."),
+            None => error = Some("Dot file not correct. Edge without weight."),
+        },
+    })?;
+    if let Some(e) = error {
+        return Err(e);
+    }
+    if header.directed {
+        return Err("Dot file not correct. graph expected.");
+    }
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::weightedgraph_from_dot_string;
+    use super::WeightedGraph;
+    use crate::rugraph::IGraph;
+
+    #[test]
+    fn weighted_graph() {
+        let mut graph = WeightedGraph::<char, f64>::new();
+        for n in ['a', 'b', 'c', 'd'] {
+            graph.add_node(n);
+        }
+        graph.add_edge('a', 'b', 1.0);
+        graph.add_edge('c', 'b', 2.5);
+        graph.add_edge('a', 'z', 1.0);
+        assert_eq!(graph.get_weight('b', 'c'), Some(2.5));
+        assert_eq!(graph.get_weight('a', 'c'), None);
+        assert_eq!(graph.get_neighbors('b'), vec![('a', 1.0), ('c', 2.5)]);
+
+        graph.set_weight('b', 'a', 4.0).unwrap();
+        graph.add_edge('c', 'b', 3.0);
+        assert_eq!(graph.get_weight('a', 'b'), Some(4.0));
+        assert_eq!(graph.get_weight('b', 'c'), Some(3.0));
+        assert!(graph.set_weight('a', 'd', 1.0).is_err());
+        assert_eq!(graph.get_edges(), vec![('a', 'b', 4.0), ('b', 'c', 3.0)]);
+        assert!(graph.as_graph().is_connected('a', 'c'));
+        assert!(!graph.is_connected('a', 'd'));
+    }
+
+    #[test]
+    fn weighted_graph_tsp() {
+        // Square with cheap sides and expensive diagonals
+        let mut graph = WeightedGraph::<i32, i32>::new();
+        for n in 0..4 {
+            graph.add_node(n);
+        }
+        for (a, b, w) in [
+            (0, 1, 1),
+            (1, 2, 1),
+            (2, 3, 1),
+            (3, 0, 1),
+            (0, 2, 5),
+            (1, 3, 5),
+        ] {
+            graph.add_edge(a, b, w);
+        }
+        let tour = graph.traveling_salesman().unwrap();
+        let cost: i32 = tour
+            .windows(2)
+            .map(|p| graph.get_weight(p[0], p[1]).unwrap())
+            .sum();
+        assert_eq!(cost, 4);
+    }
+
+    #[test]
+    fn weighted_graph_dot() {
+        let mut graph = WeightedGraph::<String, i32>::new();
+        graph.add_node(String::from("a"));
+        graph.add_node(String::from("b"));
+        graph.add_node(String::from("c"));
+        graph.add_edge(String::from("b"), String::from("a"), 7);
+        let s = graph.to_dot_string("w");
+        assert_eq!(
+            s,
+            "graph w {\n    a;\n    b;\n    c;\n    a -- b [weight=\"7\"];\n}\n"
+        );
+        let read = weightedgraph_from_dot_string::<i32>(&s).unwrap();
+        assert_eq!(read.get_nodes(), graph.get_nodes());
+        assert_eq!(read.get_edges(), graph.get_edges());
+
+        let missing = String::from("graph { a -- b }");
+        assert!(weightedgraph_from_dot_string::<i32>(&missing).is_err());
+        let wrong = String::from("graph { a -- b [weight=x] }");
+        assert!(weightedgraph_from_dot_string::<i32>(&wrong).is_err());
+    }
+}